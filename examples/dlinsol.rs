@@ -18,9 +18,13 @@ fn main() {
 
     let file_path = args[1].to_string();
 
-    let mut a = CompColMatrix::<f64>::from_harwell_boeing(file_path);
+    let mut a = CompColMatrix::<f64>::from_harwell_boeing(file_path.clone())
+        .unwrap_or_else(|err| {
+            println!("Problem opening file '{file_path}': {err}");
+            process::exit(1);
+        });
     let num_rows = a.num_rows();
-    a.print("a");
+    println!("a = {a}");
 
     // Make the true solution vector
     let x_true = vec![1.0; num_rows];
@@ -30,19 +34,19 @@ fn main() {
     let rhs = &mut a * &x_true;
     let b = DenseMatrix::from_vectors(num_rows, nrhs, rhs);
 
-    b.print("b");
+    println!("b = {b}");
 
     let mut stat = CSuperluStat::new();
 
     let SimpleSolution {
-	mut x, ..
+	x, ..
     } = SimpleSystem {
 	a,
 	b,
     }.solve(&mut stat, ColumnPermPolicy::Natural)
 	.expect("Failed to solve system");
 
-    x.print("X");
+    println!("X = {x}");
 
     // Access the solution matrix
     let solution = x.column_major_values();
@@ -0,0 +1,56 @@
+//! This example compares the fill-in and the number of
+//! floating-point operations in the factorisation of the g20
+//! matrix (the 5-point Laplacian on a 20x20 grid), using the
+//! column orderings built into SuperLU and the orderings in the
+//! ordering module.
+//!
+//! The nnz(L) and nnz(U) columns are the counts in the two
+//! factors returned by SuperLU, which split the entries of $U$
+//! inside the supernodes from the rest of $U$, and both include
+//! the diagonal. As in the SuperLU examples, the total fill is
+//! reported as nnz(L+U) = nnz(L) + nnz(U) - n.
+
+use csuperlu::c::options::ColumnPermPolicy;
+use csuperlu::c::stat::CSuperluStat;
+use csuperlu::comp_col::CompColMatrix;
+use csuperlu::dense::DenseMatrix;
+use csuperlu::ordering::{NestedDissection, ReverseCuthillMcKee};
+use csuperlu::simple_driver::{SimpleSolution, SimpleSystem};
+
+fn main() {
+    let policies: Vec<(&str, ColumnPermPolicy)> = vec![
+        ("Natural", ColumnPermPolicy::Natural),
+        ("MMD on A^T A", ColumnPermPolicy::MmdAtA),
+        ("MMD on A^T + A", ColumnPermPolicy::MmdAtPlusA),
+        ("COLAMD", ColumnPermPolicy::ColAMD),
+        ("Reverse Cuthill-McKee", ColumnPermPolicy::Custom(Box::new(ReverseCuthillMcKee::new()))),
+        ("Nested dissection", ColumnPermPolicy::Custom(Box::new(NestedDissection::new()))),
+    ];
+
+    println!(
+        "{:<24}{:>10}{:>10}{:>10}{:>14}",
+        "Ordering", "nnz(L)", "nnz(U)", "nnz(L+U)", "Flops"
+    );
+    for (name, policy) in policies {
+        let a = CompColMatrix::<f64>::from_harwell_boeing("c/g20.rua".to_string())
+            .expect("Failed to read the matrix");
+        let num_rows = a.num_rows();
+        let b = DenseMatrix::from_vectors(num_rows, 1, vec![1.0; num_rows]);
+
+        let mut stat = CSuperluStat::new();
+        let SimpleSolution { lu, .. } = SimpleSystem { a, b }
+            .solve(&mut stat, policy)
+            .expect("Failed to solve the system");
+
+        let l_non_zeros = lu.l().num_non_zeros();
+        let u_non_zeros = lu.u().non_zero_values().len();
+        println!(
+            "{:<24}{:>10}{:>10}{:>10}{:>14.0}",
+            name,
+            l_non_zeros,
+            u_non_zeros,
+            l_non_zeros + u_non_zeros - num_rows,
+            stat.factor_flops()
+        );
+    }
+}
@@ -66,9 +66,9 @@ fn main() {
     
     // Now solve again with the same pattern
     let SimpleSolution {
-	mut x,
-	mut a,
-	mut lu,
+	x,
+	a,
+	lu,
 	column_perm,
 	..
     } = SamePattern {
@@ -85,8 +85,8 @@ fn main() {
     stat.print();
     
     // Print solution
-    a.print("A");
-    lu.print();
+    println!("A = {a}");
+    println!("{lu}");
 
     println!("{:?}", a.non_zero_values());
     println!("{:?}", a.column_offsets());
@@ -94,7 +94,7 @@ fn main() {
 
     println!("{}", a.value(0, 0));
 
-    x.print("X");
+    println!("X = {x:.6}");
     let x_vals = x.column_major_values();
     println!("{:?}", x_vals);
 }
@@ -33,6 +33,7 @@ fn main() {
     a.print_structure(3);
     
     let ccf = a.compressed_column_format();
-    ccf.print("CCF");
+    println!("CCF = {ccf}");
+    println!("CCF = {ccf:#.1}");
     
 }
@@ -54,9 +54,9 @@ fn main() {
     let mut stat = CSuperluStat::new();
 
     let SimpleSolution {
-	mut a,
-	mut x,
-	mut lu,
+	a,
+	x,
+	lu,
 	..
     } = SimpleSystem {
 	a,
@@ -68,8 +68,8 @@ fn main() {
     stat.print();
 
     // Print solution
-    a.print("A");
-    lu.print();
+    println!("A = {a}");
+    println!("{lu}");
 
     println!("{:?}", a.non_zero_values());
     println!("{:?}", a.column_offsets());
@@ -77,7 +77,7 @@ fn main() {
 
     println!("{}", a.value(0, 0));
 
-    x.print("X");
+    println!("X = {x:.6}");
     let x_vals = x.column_major_values();
     println!("{:?}", x_vals);
 }
@@ -0,0 +1,74 @@
+//! This example sweeps SuperLU's tuning parameters (the panel
+//! size, the relaxation parameter and the maximum supernode
+//! size) on a Harwell-Boeing matrix, and reports the time to
+//! solve the system, the number of supernodes in $L$, and the
+//! number of non-zeros in the factors.
+//!
+//! Pass the path of a matrix as the first argument (the default
+//! is the g20 matrix in the c directory), and the number of
+//! repetitions for each setting as the second argument.
+
+use std::env;
+use std::time::Instant;
+
+use csuperlu::c::options::ColumnPermPolicy;
+use csuperlu::c::stat::CSuperluStat;
+use csuperlu::c::tuning::TuningParams;
+use csuperlu::comp_col::CompColMatrix;
+use csuperlu::dense::DenseMatrix;
+use csuperlu::simple_driver::{SimpleSolution, SimpleSystem};
+
+fn main() {
+    let mut args = env::args().skip(1);
+    let file_path = args.next().unwrap_or_else(|| "c/g20.rua".to_string());
+    let repetitions: u32 = args.next().map_or(10, |arg| arg.parse().expect("Invalid number of repetitions"));
+
+    println!(
+        "{:>6}{:>6}{:>10}{:>14}{:>12}{:>10}{:>10}",
+        "Panel", "Relax", "Max super", "Time (us)", "Supernodes", "nnz(L)", "nnz(U)"
+    );
+    for panel_size in [4, 8, 16, 32] {
+        for relax in [2, 5, 10, 20] {
+            for max_super in [25, 100, 200] {
+                let params = TuningParams::new()
+                    .panel_size(panel_size)
+                    .relax(relax)
+                    .max_super(max_super);
+
+                let mut elapsed = 0.0;
+                let mut factors = None;
+                for _ in 0..repetitions {
+                    let a = CompColMatrix::<f64>::from_harwell_boeing(file_path.clone())
+                        .expect("Failed to read the matrix");
+                    let num_rows = a.num_rows();
+                    let b = DenseMatrix::from_vectors(num_rows, 1, vec![1.0; num_rows]);
+                    let mut stat = CSuperluStat::new();
+
+                    let start = Instant::now();
+                    let SimpleSolution { lu, .. } = SimpleSystem { a, b }
+                        .solve_with_tuning(&mut stat, ColumnPermPolicy::ColAMD, params)
+                        .expect("Failed to solve the system");
+                    elapsed += start.elapsed().as_secs_f64();
+
+                    factors = Some((
+                        lu.l().num_super_nodes(),
+                        lu.l().num_non_zeros(),
+                        lu.u().non_zero_values().len(),
+                    ));
+                }
+
+                let (num_super_nodes, nnz_l, nnz_u) = factors.expect("No repetitions");
+                println!(
+                    "{:>6}{:>6}{:>10}{:>14.1}{:>12}{:>10}{:>10}",
+                    panel_size,
+                    relax,
+                    max_super,
+                    1e6 * elapsed / repetitions as f64,
+                    num_super_nodes,
+                    nnz_l,
+                    nnz_u
+                );
+            }
+        }
+    }
+}
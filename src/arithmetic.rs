@@ -0,0 +1,218 @@
+//! Arithmetic on compressed-column matrices
+//!
+//! The operations work directly on the compressed-column arrays,
+//! one column of the result at a time. The values in each column
+//! are gathered into a dense accumulator (of length num_rows),
+//! which keeps track of which rows are stored, so the cost is
+//! proportional to the number of operations on non-zeros (plus
+//! the size of the matrix):
+//!
+//! * [CompColMatrix::add_scaled] computes $\alpha A + \beta B$.
+//!   The pattern of the result is the union of the patterns of
+//!   $A$ and $B$. Elements that cancel are still stored (as
+//!   explicit zeros), so that the pattern does not depend on the
+//!   values, and can be reused with
+//!   [SamePattern](crate::simple_driver::SamePattern).
+//! * The product $AB$ uses Gustavson's algorithm: column $j$ of
+//!   $AB$ is the sum of the columns $k$ of $A$ scaled by $B_{kj}$.
+//! * [CompColMatrix::transpose] and
+//!   [CompColMatrix::conjugate_transpose] use a counting sort on
+//!   the row indices.
+//!
+//! The operators `+`, `-` and `*` (by a scalar or by another
+//! matrix) are implemented for references to matrices. All the
+//! results have sorted row indices in each column (the input
+//! matrices do not need to be sorted).
+//!
+
+use std::ops::{Add, Mul, Sub};
+
+use crate::c::value_type::ValueType;
+use crate::comp_col::{to_index, CompColMatrix};
+
+/// Accumulates the values in one column of a result, keeping
+/// track of the rows that are stored
+struct ColumnAccumulator<P: ValueType> {
+    values: Vec<P>,
+    is_stored: Vec<bool>,
+    rows: Vec<usize>,
+}
+
+impl<P: ValueType> ColumnAccumulator<P> {
+    fn new(num_rows: usize) -> Self {
+        Self {
+            values: vec![P::zero(); num_rows],
+            is_stored: vec![false; num_rows],
+            rows: Vec::new(),
+        }
+    }
+
+    /// Add the value to the element in the row (storing the
+    /// element even if the value is zero)
+    fn add(&mut self, row: usize, value: P) {
+        if !self.is_stored[row] {
+            self.is_stored[row] = true;
+            self.rows.push(row);
+        }
+        self.values[row] = self.values[row] + value;
+    }
+
+    /// Append the column to the compressed-column arrays, with
+    /// sorted row indices, and reset the accumulator
+    fn finish(&mut self, non_zero_values: &mut Vec<P>, row_indices: &mut Vec<i32>) {
+        self.rows.sort_unstable();
+        for &row in &self.rows {
+            non_zero_values.push(self.values[row]);
+            row_indices.push(to_index(row));
+            self.values[row] = P::zero();
+            self.is_stored[row] = false;
+        }
+        self.rows.clear();
+    }
+}
+
+/// Compute the sum of the scaled matrices, which must all be the
+/// same size
+fn linear_combination<P: ValueType>(terms: &[(P, &CompColMatrix<P>)]) -> CompColMatrix<P> {
+    let (num_rows, num_columns) = (terms[0].1.num_rows(), terms[0].1.num_columns());
+    let num_non_zeros = terms.iter().map(|(_, a)| a.non_zero_values().len()).sum();
+    let mut accumulator = ColumnAccumulator::new(num_rows);
+    let mut non_zero_values = Vec::with_capacity(num_non_zeros);
+    let mut row_indices = Vec::with_capacity(num_non_zeros);
+    let mut column_offsets = vec![0];
+    for col in 0..num_columns {
+        for &(alpha, a) in terms {
+            for (row, value) in a.column(col) {
+                accumulator.add(row, alpha * value);
+            }
+        }
+        accumulator.finish(&mut non_zero_values, &mut row_indices);
+        column_offsets.push(to_index(row_indices.len()));
+    }
+    CompColMatrix::from_vectors(num_rows, non_zero_values, row_indices, column_offsets)
+}
+
+impl<P: ValueType> CompColMatrix<P> {
+    /// Compute $\alpha A + \beta B$, where $A$ is this matrix
+    /// (see [arithmetic](crate::arithmetic))
+    ///
+    /// # Panics
+    ///
+    /// If the matrices are not the same size.
+    ///
+    pub fn add_scaled(&self, alpha: P, b: &CompColMatrix<P>, beta: P) -> CompColMatrix<P> {
+        assert!(
+            self.num_rows() == b.num_rows() && self.num_columns() == b.num_columns(),
+            "Cannot add; incompatible dimensions"
+        );
+        linear_combination(&[(alpha, self), (beta, b)])
+    }
+
+    /// Compute $\alpha A$ (the pattern is unchanged)
+    pub fn scale(&self, alpha: P) -> CompColMatrix<P> {
+        linear_combination(&[(alpha, self)])
+    }
+
+    /// Compute the matrix product $AB$, where $A$ is this matrix
+    /// (see [arithmetic](crate::arithmetic))
+    ///
+    /// # Panics
+    ///
+    /// If the number of columns of $A$ does not match the number
+    /// of rows of $B$.
+    ///
+    pub fn matrix_product(&self, b: &CompColMatrix<P>) -> CompColMatrix<P> {
+        assert!(
+            self.num_columns() == b.num_rows(),
+            "Cannot multiply; incompatible dimensions"
+        );
+        let mut accumulator = ColumnAccumulator::new(self.num_rows());
+        let mut non_zero_values = Vec::new();
+        let mut row_indices = Vec::new();
+        let mut column_offsets = vec![0];
+        for col in 0..b.num_columns() {
+            for (k, b_value) in b.column(col) {
+                for (row, a_value) in self.column(k) {
+                    accumulator.add(row, a_value * b_value);
+                }
+            }
+            accumulator.finish(&mut non_zero_values, &mut row_indices);
+            column_offsets.push(to_index(row_indices.len()));
+        }
+        CompColMatrix::from_vectors(self.num_rows(), non_zero_values, row_indices, column_offsets)
+    }
+
+    /// Compute the transpose $A^T$
+    pub fn transpose(&self) -> CompColMatrix<P> {
+        self.transpose_map(|value| value)
+    }
+
+    /// Compute the conjugate transpose $A^H$ (the same as the
+    /// transpose for real matrices)
+    pub fn conjugate_transpose(&self) -> CompColMatrix<P> {
+        self.transpose_map(P::conj)
+    }
+
+    /// Transpose the matrix, applying f to each value
+    fn transpose_map(&self, f: impl Fn(P) -> P) -> CompColMatrix<P> {
+        let num_non_zeros = self.non_zero_values().len();
+        // Count the elements in each row, which become the
+        // columns of the transpose
+        let mut column_offsets = vec![0; self.num_rows() + 1];
+        for &row in self.row_indices() {
+            column_offsets[row as usize + 1] += 1;
+        }
+        for row in 0..self.num_rows() {
+            column_offsets[row + 1] += column_offsets[row];
+        }
+        // Visiting the columns in order puts the new row indices
+        // in order
+        let mut next = column_offsets.clone();
+        let mut non_zero_values = vec![P::zero(); num_non_zeros];
+        let mut row_indices = vec![0; num_non_zeros];
+        for col in 0..self.num_columns() {
+            for (row, value) in self.column(col) {
+                let position = next[row] as usize;
+                non_zero_values[position] = f(value);
+                row_indices[position] = to_index(col);
+                next[row] += 1;
+            }
+        }
+        CompColMatrix::from_vectors(self.num_columns(), non_zero_values, row_indices, column_offsets)
+    }
+}
+
+impl<P: ValueType> Add for &CompColMatrix<P> {
+    type Output = CompColMatrix<P>;
+
+    fn add(self, b: &CompColMatrix<P>) -> CompColMatrix<P> {
+        self.add_scaled(P::one(), b, P::one())
+    }
+}
+
+impl<P: ValueType> Sub for &CompColMatrix<P> {
+    type Output = CompColMatrix<P>;
+
+    fn sub(self, b: &CompColMatrix<P>) -> CompColMatrix<P> {
+        self.add_scaled(P::one(), b, P::zero() - P::one())
+    }
+}
+
+impl<P: ValueType> Mul<P> for &CompColMatrix<P> {
+    type Output = CompColMatrix<P>;
+
+    fn mul(self, alpha: P) -> CompColMatrix<P> {
+        self.scale(alpha)
+    }
+}
+
+impl<P: ValueType> Mul for &CompColMatrix<P> {
+    type Output = CompColMatrix<P>;
+
+    fn mul(self, b: &CompColMatrix<P>) -> CompColMatrix<P> {
+        self.matrix_product(b)
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,125 @@
+use super::*;
+use crate::test_utils::dense;
+use num::Complex;
+
+/// The matrix
+///
+///  1  0 -3
+/// -2  4  0
+///  0  0  5
+fn test_matrix() -> CompColMatrix<f64> {
+    CompColMatrix::from_vectors(
+        3,
+        vec![1.0, -2.0, 4.0, -3.0, 5.0],
+        vec![0, 1, 1, 0, 2],
+        vec![0, 2, 3, 5],
+    )
+}
+
+/// The matrix (with unsorted row indices in column 0)
+///
+///  0  2  0
+///  1  0  0
+///  6  0 -1
+fn other_matrix() -> CompColMatrix<f64> {
+    CompColMatrix::from_vectors(
+        3,
+        vec![6.0, 1.0, 2.0, -1.0],
+        vec![2, 1, 0, 2],
+        vec![0, 2, 3, 4],
+    )
+}
+
+fn assert_sorted<P: ValueType>(a: &CompColMatrix<P>) {
+    for col in 0..a.num_columns() {
+        let start = a.column_offsets()[col] as usize;
+        let end = a.column_offsets()[col + 1] as usize;
+        assert!(a.row_indices()[start..end].windows(2).all(|rows| rows[0] < rows[1]));
+    }
+}
+
+#[test]
+fn add_and_subtract() {
+    let (a, b) = (test_matrix(), other_matrix());
+    let sum = &a + &b;
+    assert_sorted(&sum);
+    assert_eq!(
+        dense(&sum),
+        vec![vec![1.0, 2.0, -3.0], vec![-1.0, 4.0, 0.0], vec![6.0, 0.0, 4.0]]
+    );
+    let difference = &a - &b;
+    assert_eq!(
+        dense(&difference),
+        vec![vec![1.0, -2.0, -3.0], vec![-3.0, 4.0, 0.0], vec![-6.0, 0.0, 6.0]]
+    );
+}
+
+#[test]
+fn cancelled_elements_are_stored() {
+    let a = test_matrix();
+    let zero = a.add_scaled(2.0, &a, -2.0);
+    assert_eq!(zero.row_indices(), a.row_indices());
+    assert_eq!(zero.column_offsets(), a.column_offsets());
+    assert!(zero.non_zero_values().iter().all(|&value| value == 0.0));
+}
+
+#[test]
+fn scalar_multiplication() {
+    let scaled = &other_matrix() * 0.5;
+    assert_sorted(&scaled);
+    assert_eq!(scaled.row_indices(), &[1, 2, 0, 2]);
+    assert_eq!(scaled.non_zero_values(), &[0.5, 3.0, 1.0, -0.5]);
+}
+
+#[test]
+fn matrix_product() {
+    let product = &test_matrix() * &other_matrix();
+    assert_sorted(&product);
+    assert_eq!(
+        dense(&product),
+        vec![vec![-18.0, 2.0, 3.0], vec![4.0, -4.0, 0.0], vec![30.0, 0.0, -5.0]]
+    );
+    // Rectangular product: a 3x1 column times a 1x2 row
+    let column = CompColMatrix::from_vectors(3, vec![1.0, 2.0], vec![0, 2], vec![0, 2]);
+    let row = CompColMatrix::from_vectors(1, vec![3.0, 4.0], vec![0, 0], vec![0, 1, 2]);
+    let outer = &column * &row;
+    assert_eq!((outer.num_rows(), outer.num_columns()), (3, 2));
+    assert_eq!(dense(&outer), vec![vec![3.0, 4.0], vec![0.0, 0.0], vec![6.0, 8.0]]);
+}
+
+#[test]
+#[should_panic]
+fn incompatible_dimensions() {
+    let column = CompColMatrix::from_vectors(3, vec![1.0], vec![0], vec![0, 1]);
+    let _ = &test_matrix() + &column;
+}
+
+#[test]
+fn transpose() {
+    let a = other_matrix();
+    let transpose = a.transpose();
+    assert_sorted(&transpose);
+    for row in 0..3 {
+        for col in 0..3 {
+            assert_eq!(transpose.value(row, col), a.value(col, row));
+        }
+    }
+    let row = CompColMatrix::from_vectors(1, vec![3.0, 4.0], vec![0, 0], vec![0, 1, 2]);
+    let column = row.transpose();
+    assert_eq!((column.num_rows(), column.num_columns()), (2, 1));
+    assert_eq!(column.non_zero_values(), &[3.0, 4.0]);
+}
+
+#[test]
+fn conjugate_transpose() {
+    let a = CompColMatrix::from_vectors(
+        2,
+        vec![Complex::new(1.0, 2.0), Complex::new(0.0, -1.0)],
+        vec![1, 0],
+        vec![0, 1, 2],
+    );
+    let hermitian = a.conjugate_transpose();
+    assert_eq!(hermitian.value(0, 1), Complex::new(1.0, -2.0));
+    assert_eq!(hermitian.value(1, 0), Complex::new(0.0, 1.0));
+    assert_eq!(a.transpose().value(0, 1), Complex::new(1.0, 2.0));
+}
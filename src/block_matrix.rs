@@ -0,0 +1,399 @@
+//! Assemble sparse matrices from blocks
+//!
+//! Many sparse systems have a natural block structure. For
+//! example, in modified nodal analysis (MNA) the unknowns are
+//! split into node voltages and branch currents, giving a
+//! matrix of the form
+//!
+//! $$\begin{pmatrix} G & B \\\\ C & D \end{pmatrix}.$$
+//!
+//! A [BlockMatrix] describes this structure using named row and
+//! column partitions, which are laid out consecutively in the
+//! order they are added. Blocks ([SparseMat] or [CompColMatrix])
+//! are inserted either into a named (row, column) partition pair,
+//! or at an explicit row and column offset, and the whole matrix
+//! is then assembled into one [CompColMatrix]. Elements from
+//! overlapping blocks are added together.
+//!
+//! The partitions can then be used to extract blocks from the
+//! assembled matrix, and to extract the segments of a
+//! right-hand side or solution vector corresponding to a
+//! partition.
+//!
+
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Range;
+
+use crate::c::value_type::ValueType;
+use crate::comp_col::CompColMatrix;
+use crate::dense::DenseMatrix;
+use crate::sparse_matrix::SparseMat;
+
+/// Errors that can occur when assembling or splitting a
+/// block matrix
+#[derive(Debug, PartialEq)]
+pub enum BlockError {
+    /// No partition with this name exists
+    UnknownPartition(String),
+    /// A partition with this name already exists
+    DuplicatePartition(String),
+    /// The block does not have the same size as the
+    /// partitions it is inserted into
+    SizeMismatch {
+        expected: (usize, usize),
+        found: (usize, usize),
+    },
+    /// The block (or vector) does not fit inside the matrix
+    OutOfRange,
+}
+
+impl std::error::Error for BlockError {}
+
+impl fmt::Display for BlockError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UnknownPartition(name) => write!(f, "No partition named '{name}'"),
+            Self::DuplicatePartition(name) => write!(f, "A partition named '{name}' already exists"),
+            Self::SizeMismatch { expected, found } => write!(
+                f,
+                "Block size {}x{} does not match the partition size {}x{}",
+                found.0, found.1, expected.0, expected.1
+            ),
+            Self::OutOfRange => write!(f, "Block does not fit inside the matrix"),
+        }
+    }
+}
+
+/// A matrix which can be inserted as a block of a [BlockMatrix]
+pub trait Block<P: ValueType> {
+    /// Get the (number of rows, number of columns) in the block
+    fn block_size(&self) -> (usize, usize);
+    /// Call f(row, column, value) for each stored element
+    fn for_each_element(&self, f: &mut dyn FnMut(usize, usize, P));
+}
+
+impl<P: ValueType> Block<P> for SparseMat<P> {
+    fn block_size(&self) -> (usize, usize) {
+        (self.num_rows(), self.num_cols())
+    }
+    fn for_each_element(&self, f: &mut dyn FnMut(usize, usize, P)) {
+        for (&(row, col), &value) in self.non_zero_vals() {
+            f(row, col, value);
+        }
+    }
+}
+
+impl<P: ValueType> Block<P> for CompColMatrix<P> {
+    fn block_size(&self) -> (usize, usize) {
+        (self.num_rows(), self.num_columns())
+    }
+    fn for_each_element(&self, f: &mut dyn FnMut(usize, usize, P)) {
+        let column_offsets = self.column_offsets();
+        for col in 0..self.num_columns() {
+            let start = column_offsets[col] as usize;
+            let end = column_offsets[col + 1] as usize;
+            for k in start..end {
+                f(self.row_indices()[k] as usize, col, self.non_zero_values()[k]);
+            }
+        }
+    }
+}
+
+/// A list of named, consecutive ranges of indices
+#[derive(Debug, Clone, Default)]
+struct Partitions {
+    names: Vec<String>,
+    ranges: HashMap<String, Range<usize>>,
+    size: usize,
+}
+
+impl Partitions {
+    fn add(&mut self, name: &str, size: usize) -> Result<Range<usize>, BlockError> {
+        if self.ranges.contains_key(name) {
+            return Err(BlockError::DuplicatePartition(name.to_string()));
+        }
+        let range = self.size..self.size + size;
+        self.size += size;
+        self.names.push(name.to_string());
+        self.ranges.insert(name.to_string(), range.clone());
+        Ok(range)
+    }
+
+    fn range(&self, name: &str) -> Result<Range<usize>, BlockError> {
+        self.ranges
+            .get(name)
+            .cloned()
+            .ok_or_else(|| BlockError::UnknownPartition(name.to_string()))
+    }
+
+    fn boundaries(&self) -> Vec<usize> {
+        self.names
+            .iter()
+            .map(|name| self.ranges[name].start)
+            .filter(|&start| start > 0)
+            .collect()
+    }
+}
+
+/// Builder for a sparse matrix made of blocks, with named
+/// row and column partitions
+///
+/// # Example
+///
+/// ```ignore
+/// let mut mna = BlockMatrix::new();
+/// mna.add_partition("nodes", 3)?;
+/// mna.add_partition("branches", 1)?;
+/// mna.insert("nodes", "nodes", &g)?;
+/// mna.insert("nodes", "branches", &b)?;
+/// mna.insert("branches", "nodes", &c)?;
+/// let a = mna.assemble();
+/// ```
+///
+#[derive(Debug, Clone)]
+pub struct BlockMatrix<P: ValueType> {
+    rows: Partitions,
+    columns: Partitions,
+    elements: SparseMat<P>,
+}
+
+impl<P: ValueType> Default for BlockMatrix<P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<P: ValueType> BlockMatrix<P> {
+    /// Create a block matrix with no partitions
+    pub fn new() -> Self {
+        Self {
+            rows: Partitions::default(),
+            columns: Partitions::default(),
+            elements: SparseMat::empty(),
+        }
+    }
+
+    /// Add a row partition with the given number of rows after
+    /// the existing row partitions, and return its row range
+    ///
+    /// # Errors
+    ///
+    /// If a row partition with the same name already exists.
+    ///
+    pub fn add_row_partition(&mut self, name: &str, size: usize) -> Result<Range<usize>, BlockError> {
+        let range = self.rows.add(name, size)?;
+        self.elements.resize_rows(self.rows.size);
+        Ok(range)
+    }
+
+    /// Add a column partition with the given number of columns
+    /// after the existing column partitions, and return its
+    /// column range
+    ///
+    /// # Errors
+    ///
+    /// If a column partition with the same name already exists.
+    ///
+    pub fn add_column_partition(&mut self, name: &str, size: usize) -> Result<Range<usize>, BlockError> {
+        let range = self.columns.add(name, size)?;
+        self.elements.resize_cols(self.columns.size);
+        Ok(range)
+    }
+
+    /// Add a row partition and a column partition with the same
+    /// name and size (for square blocks on the diagonal)
+    pub fn add_partition(&mut self, name: &str, size: usize) -> Result<(), BlockError> {
+        if self.columns.ranges.contains_key(name) {
+            return Err(BlockError::DuplicatePartition(name.to_string()));
+        }
+        self.add_row_partition(name, size)?;
+        self.add_column_partition(name, size)?;
+        Ok(())
+    }
+
+    /// Get the range of rows in a row partition
+    pub fn row_range(&self, name: &str) -> Result<Range<usize>, BlockError> {
+        self.rows.range(name)
+    }
+
+    /// Get the range of columns in a column partition
+    pub fn column_range(&self, name: &str) -> Result<Range<usize>, BlockError> {
+        self.columns.range(name)
+    }
+
+    /// Get the names of the row partitions, in order
+    pub fn row_partitions(&self) -> &[String] {
+        &self.rows.names
+    }
+
+    /// Get the names of the column partitions, in order
+    pub fn column_partitions(&self) -> &[String] {
+        &self.columns.names
+    }
+
+    /// Get the first row of each row partition (apart from
+    /// row 0), for drawing partition lines (for example, in a
+    /// [SpyPlot](crate::spy::SpyPlot))
+    pub fn row_boundaries(&self) -> Vec<usize> {
+        self.rows.boundaries()
+    }
+
+    /// Get the first column of each column partition (apart
+    /// from column 0)
+    pub fn column_boundaries(&self) -> Vec<usize> {
+        self.columns.boundaries()
+    }
+
+    /// Get the number of rows in the matrix (the sum of the
+    /// sizes of the row partitions)
+    pub fn num_rows(&self) -> usize {
+        self.rows.size
+    }
+
+    /// Get the number of columns in the matrix (the sum of the
+    /// sizes of the column partitions)
+    pub fn num_columns(&self) -> usize {
+        self.columns.size
+    }
+
+    /// Insert a block into a (row partition, column partition)
+    /// pair. The block must be the same size as the partitions.
+    /// Elements are added to any elements already inserted.
+    ///
+    /// # Errors
+    ///
+    /// If either partition does not exist, or if the block size
+    /// does not match the partition sizes.
+    ///
+    pub fn insert(&mut self, row_partition: &str, column_partition: &str, block: &impl Block<P>)
+                  -> Result<(), BlockError> {
+        let rows = self.rows.range(row_partition)?;
+        let columns = self.columns.range(column_partition)?;
+        let expected = (rows.len(), columns.len());
+        if block.block_size() != expected {
+            return Err(BlockError::SizeMismatch { expected, found: block.block_size() });
+        }
+        self.insert_at(rows.start, columns.start, block)
+    }
+
+    /// Insert a block with its top-left element at (row_offset,
+    /// column_offset). Elements are added to any elements
+    /// already inserted.
+    ///
+    /// # Errors
+    ///
+    /// If the block does not fit inside the matrix.
+    ///
+    pub fn insert_at(&mut self, row_offset: usize, column_offset: usize, block: &impl Block<P>)
+                     -> Result<(), BlockError> {
+        let (num_rows, num_columns) = block.block_size();
+        if row_offset + num_rows > self.num_rows() || column_offset + num_columns > self.num_columns() {
+            return Err(BlockError::OutOfRange);
+        }
+        let elements = &mut self.elements;
+        block.for_each_element(&mut |row, col, value| {
+            let (row, col) = (row_offset + row, column_offset + col);
+            elements.insert(row, col, elements.get(row, col) + value);
+        });
+        Ok(())
+    }
+
+    /// Get the elements inserted so far, as a sparse matrix
+    pub fn to_sparse_mat(&self) -> SparseMat<P> {
+        self.elements.clone()
+    }
+
+    /// Assemble the blocks into a compressed-column matrix
+    pub fn assemble(&self) -> CompColMatrix<P> {
+        self.elements.compressed_column_format()
+    }
+
+    /// Extract the block of an assembled matrix in a (row
+    /// partition, column partition) pair
+    ///
+    /// # Errors
+    ///
+    /// If either partition does not exist, or if the matrix is
+    /// not the same size as this block matrix.
+    ///
+    pub fn block(&self, a: &CompColMatrix<P>, row_partition: &str, column_partition: &str)
+                 -> Result<SparseMat<P>, BlockError> {
+        let rows = self.rows.range(row_partition)?;
+        let columns = self.columns.range(column_partition)?;
+        if a.num_rows() != self.num_rows() || a.num_columns() != self.num_columns() {
+            return Err(BlockError::SizeMismatch {
+                expected: (self.num_rows(), self.num_columns()),
+                found: (a.num_rows(), a.num_columns()),
+            });
+        }
+        let mut block = SparseMat::new(rows.len(), columns.len());
+        let column_offsets = a.column_offsets();
+        for col in columns.clone() {
+            let start = column_offsets[col] as usize;
+            let end = column_offsets[col + 1] as usize;
+            for k in start..end {
+                let row = a.row_indices()[k] as usize;
+                if rows.contains(&row) {
+                    block.insert(row - rows.start, col - columns.start, a.non_zero_values()[k]);
+                }
+            }
+        }
+        Ok(block)
+    }
+
+    /// Get the segment of a right-hand side vector $b$ (which
+    /// has one element per row of $A$) in a row partition
+    ///
+    /// # Errors
+    ///
+    /// If the partition does not exist, or if the length of b
+    /// is not equal to the number of rows.
+    ///
+    pub fn row_segment<'a>(&self, b: &'a [P], row_partition: &str) -> Result<&'a [P], BlockError> {
+        if b.len() != self.num_rows() {
+            return Err(BlockError::OutOfRange);
+        }
+        Ok(&b[self.rows.range(row_partition)?])
+    }
+
+    /// Get the segment of a solution vector $x$ (which has one
+    /// element per column of $A$) in a column partition
+    ///
+    /// # Errors
+    ///
+    /// If the partition does not exist, or if the length of x
+    /// is not equal to the number of columns.
+    ///
+    pub fn column_segment<'a>(&self, x: &'a [P], column_partition: &str) -> Result<&'a [P], BlockError> {
+        if x.len() != self.num_columns() {
+            return Err(BlockError::OutOfRange);
+        }
+        Ok(&x[self.columns.range(column_partition)?])
+    }
+
+    /// Extract the rows of a solution matrix $X$ (one row per
+    /// column of $A$, one column per right-hand side) in a
+    /// column partition
+    ///
+    /// # Errors
+    ///
+    /// If the partition does not exist, or if the number of rows
+    /// of X is not equal to the number of columns.
+    ///
+    pub fn solution_block(&self, x: &DenseMatrix<P>, column_partition: &str)
+                          -> Result<DenseMatrix<P>, BlockError> {
+        if x.num_rows() != self.num_columns() {
+            return Err(BlockError::OutOfRange);
+        }
+        let rows = self.columns.range(column_partition)?;
+        let mut values = Vec::with_capacity(rows.len() * x.num_columns());
+        for col in 0..x.num_columns() {
+            values.extend_from_slice(&x.column(col)[rows.clone()]);
+        }
+        Ok(DenseMatrix::from_vectors(rows.len(), x.num_columns(), values))
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,93 @@
+use super::*;
+
+/// A small MNA-style system with three nodes and one branch
+fn mna() -> BlockMatrix<f64> {
+    let mut g = SparseMat::new(3, 3);
+    g.insert(0, 0, 2.0);
+    g.insert(0, 1, -1.0);
+    g.insert(1, 0, -1.0);
+    g.insert(1, 1, 2.0);
+    g.insert(2, 2, 1.0);
+    let b = CompColMatrix::from_vectors(3, vec![1.0], vec![0], vec![0, 1]);
+    let mut c = SparseMat::new(1, 3);
+    c.insert(0, 0, 1.0);
+
+    let mut mna = BlockMatrix::new();
+    mna.add_partition("nodes", 3).unwrap();
+    mna.add_partition("branches", 1).unwrap();
+    mna.insert("nodes", "nodes", &g).unwrap();
+    mna.insert("nodes", "branches", &b).unwrap();
+    mna.insert("branches", "nodes", &c).unwrap();
+    mna
+}
+
+#[test]
+fn assemble_blocks() {
+    let mna = mna();
+    assert_eq!(mna.row_range("branches"), Ok(3..4));
+    assert_eq!(mna.column_boundaries(), vec![3]);
+    let a = mna.assemble();
+    assert_eq!(a.num_rows(), 4);
+    assert_eq!(a.num_columns(), 4);
+    assert_eq!(a.value(0, 3), 1.0);
+    assert_eq!(a.value(3, 0), 1.0);
+    assert_eq!(a.value(1, 1), 2.0);
+    assert_eq!(a.value(3, 3), 0.0);
+    assert_eq!(a.non_zero_values().len(), 7);
+}
+
+#[test]
+fn overlapping_blocks_are_added() {
+    let mut m = BlockMatrix::<f64>::new();
+    m.add_partition("x", 2).unwrap();
+    let mut stamp = SparseMat::new(2, 2);
+    stamp.insert(0, 0, 1.0);
+    stamp.insert(1, 1, 1.0);
+    stamp.insert(0, 1, -1.0);
+    stamp.insert(1, 0, -1.0);
+    m.insert("x", "x", &stamp).unwrap();
+    m.insert("x", "x", &stamp).unwrap();
+    let mut single = SparseMat::new(1, 1);
+    single.insert(0, 0, -2.0);
+    m.insert_at(1, 1, &single).unwrap();
+    let a = m.assemble();
+    assert_eq!(a.value(0, 0), 2.0);
+    assert_eq!(a.value(0, 1), -2.0);
+    // The (1, 1) element cancels out, so it is not stored
+    assert_eq!(a.value(1, 1), 0.0);
+    assert_eq!(a.non_zero_values().len(), 3);
+}
+
+#[test]
+fn errors() {
+    let mut m = mna();
+    assert_eq!(m.add_row_partition("nodes", 1), Err(BlockError::DuplicatePartition("nodes".into())));
+    let block = SparseMat::<f64>::new(2, 2);
+    assert_eq!(m.insert("nodes", "foo", &block), Err(BlockError::UnknownPartition("foo".into())));
+    assert_eq!(
+        m.insert("nodes", "branches", &block),
+        Err(BlockError::SizeMismatch { expected: (3, 1), found: (2, 2) })
+    );
+    assert_eq!(m.insert_at(3, 3, &block), Err(BlockError::OutOfRange));
+}
+
+#[test]
+fn extract_blocks_and_segments() {
+    let mna = mna();
+    let a = mna.assemble();
+    let g = mna.block(&a, "nodes", "nodes").unwrap();
+    assert_eq!(g.num_rows(), 3);
+    assert_eq!(g.get(1, 0), -1.0);
+    assert_eq!(g.num_non_zeros(), 5);
+    let c = mna.block(&a, "branches", "nodes").unwrap();
+    assert_eq!(c.get(0, 0), 1.0);
+
+    let x = vec![1.0, 2.0, 3.0, 4.0];
+    assert_eq!(mna.column_segment(&x, "nodes"), Ok(&[1.0, 2.0, 3.0][..]));
+    assert_eq!(mna.row_segment(&x, "branches"), Ok(&[4.0][..]));
+    assert_eq!(mna.column_segment(&x[..3], "nodes"), Err(BlockError::OutOfRange));
+
+    let x = DenseMatrix::from_vectors(4, 2, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0]);
+    let currents = mna.solution_block(&x, "branches").unwrap();
+    assert_eq!(currents.to_vec(), vec![4.0, 8.0]);
+}
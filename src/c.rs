@@ -12,3 +12,5 @@ pub mod free;
 pub mod options;
 pub mod stat;
 pub mod super_matrix;
+#[cfg(feature = "tuning")]
+pub mod tuning;
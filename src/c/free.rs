@@ -2,7 +2,7 @@
 //!
 
 use csuperlu_sys::{Destroy_CompCol_Matrix,
-		   Destroy_Dense_Matrix, Destroy_SuperNode_Matrix, SuperMatrix};
+		   Destroy_Dense_Matrix, Destroy_SuperNode_Matrix, Destroy_SuperMatrix_Store, SuperMatrix};
 
 use crate::c::super_matrix::CSuperMatrix;
 
@@ -26,3 +26,22 @@ pub unsafe fn c_destroy_dense_matrix(a: &mut CSuperMatrix) {
 pub unsafe fn c_destroy_super_node_matrix(a: &mut CSuperMatrix) {
     Destroy_SuperNode_Matrix(a.super_matrix() as *const SuperMatrix as *mut SuperMatrix);
 }
+
+/// Deallocate the store of a c_SuperMatrix structure, but not the
+/// vectors inside it
+///
+/// This is used for the factors stored in a user-supplied work
+/// buffer, where the vectors belong to the buffer. The matrix
+/// must not be used afterwards.
+///
+/// # Safety
+///
+/// The matrix must be a factor $L$ or $U$ returned by an expert
+/// driver routine with a user-supplied work buffer (lwork > 0),
+/// so that its arrays live in the buffer. Calling this on any
+/// other matrix leaks its arrays. The store must only be freed
+/// once: this function must be called at most once for each
+/// matrix, and the matrix must not be freed in any other way.
+pub unsafe fn c_destroy_super_matrix_store(a: &CSuperMatrix) {
+    Destroy_SuperMatrix_Store(a.super_matrix() as *const SuperMatrix as *mut SuperMatrix);
+}
@@ -7,6 +7,9 @@
 
 use std::mem::MaybeUninit;
 
+#[cfg(feature = "tuning")]
+use crate::c::tuning::TuningParams;
+
 use csuperlu_sys::{superlu_options_t, set_default_options, colperm_t_NATURAL, colperm_t_MMD_ATA, colperm_t_MMD_AT_PLUS_A, colperm_t_COLAMD, colperm_t_MY_PERMC, rowperm_t_MY_PERMR, yes_no_t_YES, yes_no_t_NO};
 
 /// Options for the simple driver routines
@@ -40,6 +43,8 @@ use csuperlu_sys::{superlu_options_t, set_default_options, colperm_t_NATURAL, co
 pub struct SimpleDriverOptions {
     options: CSuperluOptions,
     diagonally_dominant: bool,
+    #[cfg(feature = "tuning")]
+    tuning_params: TuningParams,
 }
 
 impl SimpleDriverOptions {
@@ -55,6 +60,8 @@ impl SimpleDriverOptions {
 	Self {
 	    options: CSuperluOptions::new(),
 	    diagonally_dominant: false,
+	    #[cfg(feature = "tuning")]
+	    tuning_params: TuningParams::new(),
 	}
     }
     
@@ -163,8 +170,9 @@ impl SimpleDriverOptions {
     /// SuperLU will perform traditional partial pivoting (selecting
     /// the maximum-magnitude element as the pivot).
     ///
-    /// It is not possible to specify a user-defined row
-    /// permutation in the simple driver; see the expert driver.
+    /// It is not possible to pass a user-defined row permutation
+    /// directly to the simple driver; see UserRowPerm in the
+    /// simple_driver module.
     ///
     /// # Panics
     ///
@@ -181,6 +189,20 @@ impl SimpleDriverOptions {
 	self.options.set_diagonal_pivot_threshold(u);
     }
 
+    /// Set the tuning parameters that SuperLU reads from sp_ienv
+    /// (see [tuning](crate::c::tuning)). They only apply to the
+    /// solve that uses these options.
+    #[cfg(feature = "tuning")]
+    pub fn set_tuning_params(&mut self, tuning_params: TuningParams) {
+	self.tuning_params = tuning_params;
+    }
+
+    /// Get the tuning parameters
+    #[cfg(feature = "tuning")]
+    pub fn tuning_params(&self) -> &TuningParams {
+	&self.tuning_params
+    }
+
     /// Get the underlying superlu_options_t 
     pub fn get_options(&self) -> &superlu_options_t {
 	&self.options.get_options()
@@ -203,6 +225,27 @@ pub enum ColumnPermPolicy {
     /// It usually gives comparable orderings as MmdAtA, but
     /// is faster.
     ColAMD,
+    /// A user-defined ordering, computed from the pattern of A
+    /// (see [ordering](crate::ordering)). The solver computes the
+    /// permutation and passes it to SuperLU (as MY_PERMC). A
+    /// `Box<dyn ColumnOrdering>` converts to this box by trait
+    /// upcasting, which needs Rust 1.86 (the rust-version of the
+    /// crate).
+    Custom(Box<dyn CColumnOrdering>),
+}
+
+/// A column ordering that is computed outside SuperLU, and
+/// passed to it as a user-supplied permutation (MY_PERMC)
+///
+/// This is implemented for every
+/// [ColumnOrdering](crate::ordering::ColumnOrdering), which
+/// should be used instead of implementing this trait directly.
+pub trait CColumnOrdering {
+    /// Compute the column permutation for a matrix with the
+    /// compressed-column structure given by column_offsets and
+    /// row_indices. Element j of the result is the position of
+    /// column j in the permuted matrix.
+    fn c_column_perm(&self, num_rows: usize, column_offsets: &[i32], row_indices: &[i32]) -> Vec<i32>;
 }
 
 /// Wrapper for the SuperLU C library superlu_options_t. 
@@ -268,6 +311,9 @@ impl CSuperluOptions {
 	    ColumnPermPolicy::MmdAtA => self.options.ColPerm = colperm_t_MMD_ATA,
 	    ColumnPermPolicy::MmdAtPlusA => self.options.ColPerm = colperm_t_MMD_AT_PLUS_A,
 	    ColumnPermPolicy::ColAMD => self.options.ColPerm = colperm_t_COLAMD,
+	    // The permutation is computed by the caller and passed
+	    // to SuperLU as a user-supplied permutation
+	    ColumnPermPolicy::Custom(_) => self.options.ColPerm = colperm_t_MY_PERMC,
 	}
     }
 
@@ -277,7 +323,14 @@ impl CSuperluOptions {
 	self.options.ColPerm = colperm_t_MY_PERMC;
     }
 
-    /// Set the column permutation option to use a user supplied vector
+    /// Set the row permutation option to use a user supplied vector
+    ///
+    /// Note that the sequential SuperLU drivers only read a user
+    /// supplied row permutation when the factorisation reuses the
+    /// $L$ and $U$ structures of a previous factorisation
+    /// (SamePattern_SameRowPerm). To factor a matrix from scratch
+    /// with a chosen row permutation, use UserRowPerm in the
+    /// simple_driver module.
     ///
     pub fn set_user_row_perm(&mut self) {
 	self.options.RowPerm = rowperm_t_MY_PERMR;
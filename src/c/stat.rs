@@ -7,7 +7,7 @@
 
 use std::mem::MaybeUninit;
 
-use csuperlu_sys::{StatInit, StatFree, SuperLUStat_t, StatPrint};
+use csuperlu_sys::{StatInit, StatFree, SuperLUStat_t, StatPrint, PhaseType_FACT, PhaseType_SOLVE};
 
 pub struct CSuperluStat {
     stat: SuperLUStat_t,
@@ -35,6 +35,18 @@ impl CSuperluStat {
 	&mut self.stat
     }
     
+    /// Get the number of floating-point operations in the
+    /// factorisations recorded so far
+    pub fn factor_flops(&self) -> f64 {
+	unsafe { *self.stat.ops.add(PhaseType_FACT as usize) as f64 }
+    }
+
+    /// Get the number of floating-point operations in the
+    /// triangular solves recorded so far
+    pub fn solve_flops(&self) -> f64 {
+	unsafe { *self.stat.ops.add(PhaseType_SOLVE as usize) as f64 }
+    }
+
     /// Print a stats struct (using the C library print function)
     ///
     /// This function makes the assumption that the C library does not
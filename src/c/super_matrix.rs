@@ -36,6 +36,20 @@ impl CSuperMatrix {
 	}
     }
 
+    /// Wrap a SuperMatrix structure created by the SuperLU library
+    ///
+    /// # Safety
+    ///
+    /// The SuperMatrix must be valid and initialised (for example,
+    /// by one of the *Create_* functions), and its storage must be
+    /// allocated so that it can be freed by the SuperLU library.
+    ///
+    pub unsafe fn from_raw(super_matrix: SuperMatrix) -> Self {
+	Self {
+	    super_matrix,
+	}
+    }
+
     /// Get the number of rows in the matrix
     pub fn num_rows(&self) -> usize {
 	self.super_matrix.nrow as usize
@@ -0,0 +1,173 @@
+//! Tuning parameters for the factorisation
+//!
+//! SuperLU reads several parameters of the factorisation
+//! (the panel size, the relaxation parameter for supernodes, the
+//! maximum supernode size, the minimum block sizes for 2-D
+//! blocking, and the fill estimate used for the first
+//! allocation) by calling the function sp_ienv. In the C
+//! library, sp_ienv returns fixed values, and the SuperLU manual
+//! (in the section on performance-tuning parameters) suggests
+//! replacing it to tune them for a machine or a class of
+//! matrices.
+//!
+//! This module provides sp_ienv to SuperLU. It returns the value
+//! in the [TuningParams] of the solve that is running on the
+//! current thread, if the parameter is set, and otherwise the
+//! SuperLU default. The parameters are set with
+//! [SimpleDriverOptions::set_tuning_params](crate::c::options::SimpleDriverOptions::set_tuning_params),
+//! or for a single solve with
+//! [SimpleSystem::solve_with_tuning](crate::simple_driver::SimpleSystem::solve_with_tuning),
+//! and only apply while the driver is running, so solves on
+//! other threads are not affected.
+//!
+//! This module is only built with the `tuning` cargo feature,
+//! because it exports a global sp_ienv symbol. The override only
+//! works if SuperLU is linked statically: the linker then uses
+//! this sp_ienv instead of the one in the library. If csuperlu
+//! is linked against a shared libsuperlu (such as the one
+//! installed by the libsuperlu-dev package), the library calls
+//! its own sp_ienv and the tuning parameters have no effect.
+//! The override also applies to every other user of SuperLU in
+//! the process (for example, C code linked into the same
+//! binary), which gets the SuperLU defaults unless it calls
+//! SuperLU during a csuperlu solve on the same thread.
+//!
+//! The defaults are the values returned by sp_ienv in SuperLU
+//! 5.2 (SRC/sp_ienv.c), which are unchanged in SuperLU 6.0.
+//!
+
+use std::cell::Cell;
+
+use libc::c_int;
+
+/// Overrides for the parameters returned by sp_ienv. Parameters
+/// that are not set use the SuperLU defaults (shown in brackets).
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TuningParams {
+    panel_size: Option<usize>,
+    relax: Option<usize>,
+    max_super: Option<usize>,
+    min_row_block: Option<usize>,
+    min_column_block: Option<usize>,
+    fill: Option<usize>,
+}
+
+/// The values returned by sp_ienv for ispec = 1, ..., 7 in
+/// SuperLU 5.2 (SRC/sp_ienv.c)
+const SUPERLU_DEFAULTS: [c_int; 7] = [20, 10, 200, 200, 100, 30, 10];
+
+/// Get the SuperLU default for ispec (or 0 if ispec is not
+/// valid)
+fn default_value(ispec: c_int) -> c_int {
+    usize::try_from(ispec - 1)
+        .ok()
+        .and_then(|index| SUPERLU_DEFAULTS.get(index))
+        .copied()
+        .unwrap_or(0)
+}
+
+impl TuningParams {
+    /// Make a set of parameters which all use the SuperLU
+    /// defaults
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the panel size, the number of consecutive columns
+    /// that are updated together (20). A value of zero is
+    /// treated as one (the same applies to the other
+    /// parameters).
+    pub fn panel_size(mut self, value: usize) -> Self {
+        self.panel_size = Some(value.max(1));
+        self
+    }
+
+    /// Set the relaxation parameter: subtrees of the elimination
+    /// tree with at most this many columns are merged into one
+    /// supernode (10)
+    pub fn relax(mut self, value: usize) -> Self {
+        self.relax = Some(value.max(1));
+        self
+    }
+
+    /// Set the maximum number of columns in a supernode (200)
+    pub fn max_super(mut self, value: usize) -> Self {
+        self.max_super = Some(value.max(1));
+        self
+    }
+
+    /// Set the minimum number of rows in a supernode for 2-D
+    /// blocking to be used in the dense updates (200)
+    pub fn min_row_block(mut self, value: usize) -> Self {
+        self.min_row_block = Some(value.max(1));
+        self
+    }
+
+    /// Set the minimum number of columns in a supernode for 2-D
+    /// blocking to be used in the dense updates (100)
+    pub fn min_column_block(mut self, value: usize) -> Self {
+        self.min_column_block = Some(value.max(1));
+        self
+    }
+
+    /// Set the estimated ratio of the number of non-zeros in
+    /// $L$ and $U$ to the number of non-zeros in $A$, which is
+    /// used for the first allocation of the factors and by
+    /// [SimpleSystem::estimate_memory](crate::simple_driver::SimpleSystem::estimate_memory) (30)
+    pub fn fill(mut self, value: usize) -> Self {
+        self.fill = Some(value.max(1));
+        self
+    }
+
+    /// Get the value that sp_ienv returns for ispec
+    fn value(&self, ispec: c_int) -> c_int {
+        let value = match ispec {
+            1 => self.panel_size,
+            2 => self.relax,
+            3 => self.max_super,
+            4 => self.min_row_block,
+            5 => self.min_column_block,
+            6 => self.fill,
+            // The maximum supernode size in the incomplete
+            // factorisation (not tunable here)
+            _ => None,
+        };
+        value.map_or_else(
+            || default_value(ispec),
+            |value| value.min(c_int::MAX as usize) as c_int,
+        )
+    }
+
+    /// Use the parameters for the current thread, until the
+    /// returned guard is dropped
+    pub(crate) fn apply(&self) -> TuningGuard {
+        let previous = CURRENT_PARAMS.with(|params| params.replace(*self));
+        TuningGuard { previous }
+    }
+}
+
+thread_local! {
+    /// The parameters of the solve running on this thread
+    static CURRENT_PARAMS: Cell<TuningParams> = Cell::new(TuningParams::new());
+}
+
+/// Restores the previous parameters when it is dropped
+pub(crate) struct TuningGuard {
+    previous: TuningParams,
+}
+
+impl Drop for TuningGuard {
+    fn drop(&mut self) {
+        CURRENT_PARAMS.with(|params| params.set(self.previous));
+    }
+}
+
+/// The sp_ienv function called by SuperLU, which replaces the
+/// version in the C library
+#[no_mangle]
+pub extern "C" fn sp_ienv(ispec: c_int) -> c_int {
+    CURRENT_PARAMS.with(|params| params.get().value(ispec))
+}
+
+#[cfg(test)]
+mod tests;
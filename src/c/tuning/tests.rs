@@ -0,0 +1,98 @@
+use super::*;
+use crate::c::options::{ColumnPermPolicy, SimpleDriverOptions};
+use crate::c::stat::CSuperluStat;
+use crate::c::value_type::{CWork, ValueType};
+use crate::comp_col::CompColMatrix;
+use crate::dense::DenseMatrix;
+use crate::simple_driver::SimpleSystem;
+use crate::test_utils::tridiagonal;
+
+/// Get the memory needed to factorise a with the tuning
+/// parameters (lwork = -1 in *gssvx)
+fn estimate_memory(a: &CompColMatrix<f64>, tuning_params: TuningParams) -> f64 {
+    let mut options = SimpleDriverOptions::new();
+    options.set_superlu_column_perm(ColumnPermPolicy::Natural);
+    options.set_tuning_params(tuning_params);
+    let mut stat = CSuperluStat::new();
+    let (_, mem_usage) = unsafe {
+        f64::c_expert_driver(
+            options,
+            a.super_matrix(),
+            None,
+            DenseMatrix::<f64>::zeros(3, 1).into_super_matrix(),
+            DenseMatrix::<f64>::zeros(3, 1).into_super_matrix(),
+            CWork::Query,
+            &mut stat,
+        )
+    };
+    mem_usage.total_needed as f64
+}
+
+#[test]
+fn defaults_are_used_if_not_set() {
+    // The values in SuperLU 5.2 (SRC/sp_ienv.c)
+    let defaults: Vec<c_int> = (1..=7).map(|ispec| sp_ienv(ispec)).collect();
+    assert_eq!(defaults, vec![20, 10, 200, 200, 100, 30, 10]);
+    assert_eq!(sp_ienv(0), 0);
+    assert_eq!(sp_ienv(8), 0);
+}
+
+#[test]
+fn memory_estimate_uses_default_fill() {
+    let a = tridiagonal();
+    let default = estimate_memory(&a, TuningParams::new());
+    assert_eq!(default, estimate_memory(&a, TuningParams::new().fill(30)));
+    assert!(default < estimate_memory(&a, TuningParams::new().fill(60)));
+}
+
+#[test]
+fn parameters_apply_until_guard_is_dropped() {
+    let params = TuningParams::new().panel_size(8).relax(4).max_super(50).fill(5);
+    {
+        let _guard = params.apply();
+        assert_eq!(sp_ienv(1), 8);
+        assert_eq!(sp_ienv(2), 4);
+        assert_eq!(sp_ienv(3), 50);
+        assert_eq!(sp_ienv(4), 200);
+        assert_eq!(sp_ienv(6), 5);
+        {
+            let _inner = TuningParams::new().min_column_block(30).apply();
+            assert_eq!(sp_ienv(1), 20);
+            assert_eq!(sp_ienv(5), 30);
+        }
+        assert_eq!(sp_ienv(1), 8);
+    }
+    assert_eq!(sp_ienv(1), 20);
+}
+
+#[test]
+fn parameters_are_per_thread() {
+    let _guard = TuningParams::new().panel_size(3).apply();
+    let other = std::thread::spawn(|| sp_ienv(1)).join().unwrap();
+    assert_eq!(other, 20);
+    assert_eq!(sp_ienv(1), 3);
+}
+
+#[test]
+fn zero_is_treated_as_one() {
+    let params = TuningParams::new().relax(0).fill(0);
+    assert_eq!(params, TuningParams::new().relax(1).fill(1));
+}
+
+#[test]
+fn solve_with_tuning() {
+    let b = DenseMatrix::from_vectors(3, 1, vec![5.0, 6.0, 5.0]);
+    let mut stat = CSuperluStat::new();
+    // With the defaults, the whole matrix is one relaxed
+    // supernode. Limiting the supernodes to one column gives one
+    // supernode per column.
+    let params = TuningParams::new().panel_size(1).relax(1).max_super(1);
+    let solution = SimpleSystem { a: tridiagonal(), b }
+        .solve_with_tuning(&mut stat, ColumnPermPolicy::Natural, params)
+        .unwrap();
+    assert_eq!(solution.lu.l().num_super_nodes(), 3);
+    for value in solution.x.column(0) {
+        assert!((value - 1.0).abs() < 1e-12);
+    }
+    assert_eq!(sp_ienv(1), 20);
+}
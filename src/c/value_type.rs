@@ -9,7 +9,7 @@
 //! - Consider removing the c_ prefix from these functions (and from
 //!   related functions.
 
-use std::{ffi::CString, str::FromStr};
+use std::{ffi::CString, mem::MaybeUninit, str::FromStr};
 
 use num::Num;
 
@@ -23,9 +23,19 @@ use csuperlu_sys::{
     cPrint_SuperNode_Matrix, dPrint_SuperNode_Matrix, sPrint_SuperNode_Matrix,
     zPrint_SuperNode_Matrix, Stype_t_SLU_NC, Dtype_t_SLU_S, complex, doublecomplex,
     Dtype_t_SLU_D, Dtype_t_SLU_Z, Dtype_t_SLU_C, Stype_t_SLU_DN, Mtype_t_SLU_GE,
+    sgstrs, dgstrs, cgstrs, zgstrs, trans_t_NOTRANS,
+    sgscon, dgscon, cgscon, zgscon,
+    sgsequ, dgsequ, cgsequ, zgsequ, slaqgs, dlaqgs, claqgs, zlaqgs,
+    sPivotGrowth, dPivotGrowth, cPivotGrowth, zPivotGrowth,
+    sgssvx, dgssvx, cgssvx, zgssvx, sQuerySpace, dQuerySpace, cQuerySpace, zQuerySpace,
+    GlobalLU_t, mem_usage_t, yes_no_t_NO,
 };
 
 use crate::{c::stat::CSuperluStat, c::super_matrix::CSuperMatrix};
+use crate::c::free::{
+    c_destroy_comp_col_matrix, c_destroy_dense_matrix, c_destroy_super_matrix_store,
+    c_destroy_super_node_matrix,
+};
 
 use std::fmt;
 
@@ -33,6 +43,8 @@ use std::fmt;
 pub enum Error {
     CompColError,
     DenseMatrixError,
+    DimensionMismatch,
+    PermutationError,
     OutOfMemory { mem_alloc_at_failure: usize },
     UnknownError,
 }
@@ -45,6 +57,8 @@ impl fmt::Display for Error {
 	    Self::UnknownError => write!(f, "An unknown error occured"),
 	    Self::CompColError => write!(f, "An error occured creating a compressed column matrix"),
 	    Self::DenseMatrixError => write!(f, "An error occured creating a dense matrix"),
+	    Self::DimensionMismatch => write!(f, "The matrix A is not square, or the dimensions of A and B do not match"),
+	    Self::PermutationError => write!(f, "The permutation vector is not a valid permutation of the matrix rows or columns"),
 	    Self::OutOfMemory { mem_alloc_at_failure } =>
 		write!(f, "Simple driver ran out of memory ({mem_alloc_at_failure} B allocated at failure)"),
 	}
@@ -117,19 +131,117 @@ fn make_simple_perms(
 	    options.set_user_column_perm();
 	    perm
 	},
-	None => {
-	    let mut perm = Vec::<i32>::with_capacity(size);
-	    unsafe { perm.set_len(size); }
-	    perm
-	},
+	// The permutations are outputs, but they are initialised
+	// so that SuperLU never reads uninitialised memory
+	None => vec![0; size],
     };
 
-    let mut perm_r = Vec::<i32>::with_capacity(size);
-    unsafe { perm_r.set_len(size); }
+    let perm_r = vec![0; size];
 
     (perm_c, perm_r, options)
 }
 
+/// The memory that the expert driver uses for the
+/// factorisation (see [memory](crate::memory))
+pub enum CWork<'a> {
+    /// SuperLU allocates the memory (lwork = 0)
+    Internal,
+    /// SuperLU takes all of the memory from the buffer
+    /// (lwork > 0). An empty buffer gives lwork = 0, which is
+    /// the same as Internal.
+    Buffer(&'a mut [f64]),
+    /// Only estimate the memory needed, without factorising
+    /// (lwork = -1)
+    Query,
+}
+
+impl CWork<'_> {
+    /// Get the work and lwork (in bytes) arguments for *gssvx
+    fn c_work(&mut self) -> (*mut libc::c_void, i32) {
+	match self {
+	    Self::Internal => (std::ptr::null_mut(), 0),
+	    Self::Buffer(buffer) => {
+		let size = std::mem::size_of_val(*buffer).min(i32::MAX as usize);
+		(buffer.as_mut_ptr() as *mut libc::c_void, size as i32)
+	    },
+	    Self::Query => (std::ptr::null_mut(), -1),
+	}
+    }
+}
+
+/// The scalings computed by a *gsequ routine, and the equed
+/// character returned by *laqgs (see c_equilibrate)
+pub struct CEquilibration {
+    pub row_scale: Vec<f64>,
+    pub column_scale: Vec<f64>,
+    pub rowcnd: f64,
+    pub colcnd: f64,
+    pub amax: f64,
+    pub equed: u8,
+}
+
+/// Make the options for the expert driver, from the simple
+/// driver options. Equilibration is turned off, so that the
+/// expert driver does not modify $A$ or $B$ (and iterative
+/// refinement is off by default).
+fn make_expert_options(options: &SimpleDriverOptions) -> superlu_options_t {
+    let mut options = *options.get_options();
+    options.Equil = yes_no_t_NO;
+    options
+}
+
+/// Find the return type from a *gssvx routine (see
+/// CSimpleResult::from_vectors). The *gssvx routines also return
+/// info == num_cols_a + 1 if U is non-singular, but the reciprocal
+/// condition number is less than machine precision. The solution
+/// is still computed in that case, so it is returned as a solution
+/// which is marked as ill-conditioned.
+unsafe fn from_expert_vectors(
+    info: i32,
+    num_cols_a: usize,
+    x: CSuperMatrix,
+    perm_c: Vec<i32>,
+    perm_r: Vec<i32>,
+    l: CSuperMatrix,
+    u: CSuperMatrix,
+    user_work: bool,
+) -> CSimpleResult {
+    if info > 0 && info as usize == num_cols_a + 1 {
+	return CSimpleResult::Solution {
+	    x,
+	    perm_c,
+	    perm_r,
+	    l,
+	    u,
+	    ill_conditioned: true,
+	};
+    }
+    CSimpleResult::from_vectors(info, num_cols_a, x, perm_c, perm_r, l, u, user_work)
+}
+
+/// Free the factors l and u after a failed factorisation. The
+/// factors are only freed if SuperLU created them (otherwise, the
+/// store is still the null pointer from CSuperMatrix::alloc). If
+/// the arrays of the factors are in a user-supplied work buffer
+/// (user_work), only the stores are freed, and the buffer is freed
+/// by its owner.
+unsafe fn free_factors(mut l: CSuperMatrix, mut u: CSuperMatrix, user_work: bool) {
+    if !l.super_matrix().Store.is_null() {
+	if user_work {
+	    c_destroy_super_matrix_store(&l);
+	} else {
+	    c_destroy_super_node_matrix(&mut l);
+	}
+    }
+    if !u.super_matrix().Store.is_null() {
+	if user_work {
+	    c_destroy_super_matrix_store(&u);
+	} else {
+	    c_destroy_comp_col_matrix(&mut u);
+	}
+    }
+}
+
 /// The items returned by the c_simple_driver functions
 ///
 pub enum CSimpleResult {
@@ -141,6 +253,9 @@ pub enum CSimpleResult {
 	perm_r: Vec<i32>,
 	l: CSuperMatrix,
 	u: CSuperMatrix,
+	/// The expert driver found that the reciprocal condition
+	/// number of A is less than machine precision
+	ill_conditioned: bool,
     },
     /// The factorisatio completed successfully, but A
     /// was singular so no solution was returned
@@ -170,28 +285,33 @@ impl CSimpleResult {
     /// occured. In that case, (info - num_cols_a) is the number
     /// of bytes allocated when the failure occured. TODO check
     /// the superlu source code that this is not out-by-one.
-    fn from_vectors(
+    ///
+    /// The solution matrix x is freed unless the system was
+    /// solved, and the factors l and u are freed if there was an
+    /// error (see free_factors for user_work).
+    unsafe fn from_vectors(
 	info: i32,
 	num_cols_a: usize,
-	x: CSuperMatrix,
+	mut x: CSuperMatrix,
 	perm_c: Vec<i32>,
 	perm_r: Vec<i32>,
 	l: CSuperMatrix,
 	u: CSuperMatrix,
+	user_work: bool,
     ) -> Self {
-	if info < 0 {
-	    // Check for invalid (negative) info
-	    Self::Err(Error::UnknownError)
-	} else if info == 0 {
+	if info == 0 {
 	    // Success -- system solved
-	    Self::Solution {
+	    return Self::Solution {
 		x,
 		perm_c,
 		perm_r,
 		l,
 		u,
-	    }
-	} else if info as usize <= num_cols_a {
+		ill_conditioned: false,
+	    };
+	}
+	c_destroy_dense_matrix(&mut x);
+	if info > 0 && info as usize <= num_cols_a {
 	    // A is singular, factorisation successful
 	    Self::SingularFact {
 		singular_column: info as usize - 1,
@@ -200,8 +320,13 @@ impl CSimpleResult {
 		l,
 		u,
 	    }
+	} else if info < 0 {
+	    // Check for invalid (negative) info
+	    free_factors(l, u, user_work);
+	    Self::Err(Error::UnknownError)
 	} else {
-	    // Failed due to singular A -- factorisation complete
+	    // Failed due to running out of memory
+	    free_factors(l, u, user_work);
 	    let mem_alloc_at_failure = info as usize - num_cols_a;
 	    Self::Err(Error::OutOfMemory { mem_alloc_at_failure  })
 	}
@@ -210,7 +335,10 @@ impl CSimpleResult {
 
 // Valid numerical value types for the C SuperLU library
 ///
-pub trait ValueType: Num + Copy + FromStr + std::fmt::Debug {
+pub trait ValueType: Num + Copy + FromStr + std::fmt::Debug + std::fmt::Display {
+    /// Whether the value type is complex (false for the real
+    /// types)
+    const IS_COMPLEX: bool;
     
     /// Create a compressed-column matrix from raw vectors
     ///
@@ -329,9 +457,154 @@ pub trait ValueType: Num + Copy + FromStr + std::fmt::Debug {
         b: CSuperMatrix,
         stat: &mut CSuperluStat,
     ) -> CSimpleResult;
+
+    /// Solve a linear system $AX = B$ using an existing $LU$
+    /// factorisation of $A$ (using the SuperLU *gstrs routines).
+    /// The solution overwrites b.
+    ///
+    /// # Errors
+    ///
+    /// If SuperLU reports an invalid argument, an error is
+    /// returned.
+    ///
+    /// # Safety
+    ///
+    /// The matrices l and u must be the super-node and
+    /// compressed-column factors returned by a driver routine,
+    /// and perm_c and perm_r must be the permutations returned
+    /// with them. The matrix b must be a dense matrix with the
+    /// same number of rows as l.
+    ///
+    unsafe fn c_solve_factored(
+        l: &CSuperMatrix,
+        u: &CSuperMatrix,
+        perm_c: &[i32],
+        perm_r: &[i32],
+        b: &mut CSuperMatrix,
+        stat: &mut CSuperluStat,
+    ) -> Result<(), Error>;
+
+    /// Estimate the reciprocal condition number of $A$ from its
+    /// $LU$ factorisation (using the SuperLU *gscon routines).
+    /// The norm is "1" for the 1-norm or "I" for the
+    /// infinity-norm, and anorm is the same norm of $A$.
+    ///
+    /// # Errors
+    ///
+    /// If SuperLU reports an invalid argument, an error is
+    /// returned.
+    ///
+    /// # Safety
+    ///
+    /// The matrices l and u must be the super-node and
+    /// compressed-column factors returned by a driver routine.
+    ///
+    unsafe fn c_estimate_rcond(
+        norm: &str,
+        l: &CSuperMatrix,
+        u: &CSuperMatrix,
+        anorm: f64,
+        stat: &mut CSuperluStat,
+    ) -> Result<f64, Error>;
+
+    /// Compute row and column scalings for $A$ (using the SuperLU
+    /// *gsequ routines), and apply them to $A$ in place if they
+    /// are worthwhile (using the SuperLU *laqgs routines)
+    ///
+    /// # Errors
+    ///
+    /// If *gsequ returns a non-zero info (if $A$ has a row or
+    /// column that is exactly zero, or an argument is invalid),
+    /// info is returned and $A$ is not modified.
+    ///
+    /// # Safety
+    ///
+    /// The matrix a must be a compressed-column matrix.
+    ///
+    unsafe fn c_equilibrate(a: &mut CSuperMatrix) -> Result<CEquilibration, i32>;
+
+    /// Compute the reciprocal pivot growth of the first
+    /// num_columns columns of the factorisation,
+    /// $\min_j \max_i |(AP_c)_{ij}| / \max_i |U_{ij}|$
+    /// (using the SuperLU *PivotGrowth routines)
+    ///
+    /// # Safety
+    ///
+    /// The matrix a must be the compressed-column matrix that was
+    /// factored, and perm_c, l and u must be the column permutation
+    /// and the factors returned by a driver routine.
+    /// num_columns must not be greater than the number of columns.
+    ///
+    unsafe fn c_pivot_growth(
+        num_columns: usize,
+        a: &CSuperMatrix,
+        perm_c: &[i32],
+        l: &CSuperMatrix,
+        u: &CSuperMatrix,
+    ) -> f64;
+
+    /// Solve a sparse linear system using the expert driver
+    /// (*gssvx), with the same options as the simple driver (no
+    /// equilibration or iterative refinement), choosing where the
+    /// memory for the factorisation comes from (see
+    /// [memory](crate::memory)). The solution is written to x.
+    ///
+    /// The memory usage reported by SuperLU is returned with the
+    /// result. For [CWork::Query], only total_needed is set, and
+    /// the result is an out-of-memory error.
+    ///
+    /// # Safety
+    ///
+    /// As for c_simple_driver. The matrix x must be a dense
+    /// matrix of the same size as b. If a buffer is used, it must
+    /// not be freed until the factors l and u are freed (and
+    /// their arrays must not be freed by SuperLU).
+    ///
+    unsafe fn c_expert_driver(
+        options: SimpleDriverOptions,
+        a: &CSuperMatrix,
+        perm_c: Option<Vec<i32>>,
+        b: CSuperMatrix,
+        x: CSuperMatrix,
+        work: CWork,
+        stat: &mut CSuperluStat,
+    ) -> (CSimpleResult, mem_usage_t);
+
+    /// Get the memory used by an $LU$ factorisation (using the
+    /// SuperLU *QuerySpace routines)
+    ///
+    /// # Safety
+    ///
+    /// The matrices l and u must be the super-node and
+    /// compressed-column factors returned by a driver routine.
+    ///
+    unsafe fn c_query_space(l: &CSuperMatrix, u: &CSuperMatrix) -> mem_usage_t;
+
+    /// Get the absolute value (or modulus, for complex
+    /// types) as a double
+    fn magnitude(self) -> f64;
+
+    /// Convert a real number to the value type
+    fn from_real(real: f64) -> Self;
+
+    /// Convert the value to a double-precision complex number
+    /// (which is exact for all the value types)
+    fn to_complex(self) -> num::Complex<f64>;
+
+    /// Convert a double-precision complex number to the value
+    /// type, rounding to the precision of the type (values that
+    /// are too large become infinite), and discarding the
+    /// imaginary part for real types
+    fn from_complex_lossy(value: num::Complex<f64>) -> Self;
+
+    /// Get the complex conjugate (the value itself for real
+    /// types)
+    fn conj(self) -> Self;
 }
 
 impl ValueType for f32 {
+    const IS_COMPLEX: bool = false;
+
     unsafe fn c_create_comp_col_matrix(
         num_rows: usize,
         non_zero_values: &mut Vec<f32>,
@@ -410,6 +683,8 @@ impl ValueType for f32 {
         let u = CSuperMatrix::alloc();
 	let (mut perm_c, mut perm_r, options)
 	    = make_simple_perms(a.num_columns(), perm_c, options);
+	#[cfg(feature = "tuning")]
+	let _tuning = options.tuning_params().apply();
 		
         sgssv(
             options.get_options() as *const superlu_options_t as *mut superlu_options_t,
@@ -423,11 +698,206 @@ impl ValueType for f32 {
             &mut info,
         );
 
-	CSimpleResult::from_vectors(info, a.num_columns(), b, perm_c, perm_r, l, u,)
+	CSimpleResult::from_vectors(info, a.num_columns(), b, perm_c, perm_r, l, u, false)
+    }
+
+    unsafe fn c_solve_factored(
+        l: &CSuperMatrix,
+        u: &CSuperMatrix,
+        perm_c: &[i32],
+        perm_r: &[i32],
+        b: &mut CSuperMatrix,
+        stat: &mut CSuperluStat,
+    ) -> Result<(), Error> {
+        let mut info = 0i32;
+        sgstrs(
+            trans_t_NOTRANS,
+            l.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            u.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            perm_c.as_ptr() as *mut i32,
+            perm_r.as_ptr() as *mut i32,
+            b.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            stat.get_stat(),
+            &mut info,
+        );
+        if info < 0 {
+            return Err(Error::UnknownError);
+        }
+        Ok(())
+    }
+
+    unsafe fn c_estimate_rcond(
+        norm: &str,
+        l: &CSuperMatrix,
+        u: &CSuperMatrix,
+        anorm: f64,
+        stat: &mut CSuperluStat,
+    ) -> Result<f64, Error> {
+        let norm = c_string(norm);
+        let mut rcond = 0.0;
+        let mut info = 0i32;
+        sgscon(
+            norm.as_ptr() as *mut libc::c_char,
+            l.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            u.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            anorm as f32,
+            &mut rcond,
+            stat.get_stat(),
+            &mut info,
+        );
+        if info < 0 {
+            return Err(Error::UnknownError);
+        }
+        Ok(rcond as f64)
+    }
+
+    unsafe fn c_equilibrate(a: &mut CSuperMatrix) -> Result<CEquilibration, i32> {
+        let mut row_scale = vec![0.0; a.num_rows()];
+        let mut column_scale = vec![0.0; a.num_columns()];
+        let mut rowcnd = 0.0;
+        let mut colcnd = 0.0;
+        let mut amax = 0.0;
+        let mut info = 0i32;
+        sgsequ(
+            a.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            row_scale.as_mut_ptr(),
+            column_scale.as_mut_ptr(),
+            &mut rowcnd,
+            &mut colcnd,
+            &mut amax,
+            &mut info,
+        );
+        if info != 0 {
+            return Err(info);
+        }
+        let mut equed = b'N' as libc::c_char;
+        slaqgs(
+            a.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            row_scale.as_mut_ptr(),
+            column_scale.as_mut_ptr(),
+            rowcnd,
+            colcnd,
+            amax,
+            &mut equed,
+        );
+        Ok(CEquilibration {
+            row_scale: row_scale.into_iter().map(|x| x as f64).collect(),
+            column_scale: column_scale.into_iter().map(|x| x as f64).collect(),
+            rowcnd: rowcnd as f64,
+            colcnd: colcnd as f64,
+            amax: amax as f64,
+            equed: equed as u8,
+        })
+    }
+
+    unsafe fn c_pivot_growth(
+        num_columns: usize,
+        a: &CSuperMatrix,
+        perm_c: &[i32],
+        l: &CSuperMatrix,
+        u: &CSuperMatrix,
+    ) -> f64 {
+        sPivotGrowth(
+            num_columns as i32,
+            a.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            perm_c.as_ptr() as *mut i32,
+            l.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            u.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+        ) as f64
+    }
+
+    unsafe fn c_expert_driver(
+        options: SimpleDriverOptions,
+        a: &CSuperMatrix,
+        perm_c: Option<Vec<i32>>,
+        mut b: CSuperMatrix,
+        x: CSuperMatrix,
+        mut work: CWork,
+        stat: &mut CSuperluStat,
+    ) -> (CSimpleResult, mem_usage_t) {
+        let mut info = 0i32;
+        let l = CSuperMatrix::alloc();
+        let u = CSuperMatrix::alloc();
+        let (mut perm_c, mut perm_r, options) = make_simple_perms(a.num_columns(), perm_c, options);
+        #[cfg(feature = "tuning")]
+        let _tuning = options.tuning_params().apply();
+        let mut options = make_expert_options(&options);
+        let (work, lwork) = work.c_work();
+        // An empty buffer gives lwork = 0, so SuperLU allocates
+        // the factors itself
+        let user_work = lwork > 0;
+        let mut etree = vec![0; a.num_columns()];
+        let mut equed = b'N' as libc::c_char;
+        let mut row_scale = vec![0.0; a.num_rows()];
+        let mut column_scale = vec![0.0; a.num_columns()];
+        let mut ferr = vec![0.0; b.num_columns()];
+        let mut berr = vec![0.0; b.num_columns()];
+        let mut recip_pivot_growth = 0.0;
+        let mut rcond = 0.0;
+        let mut glu = MaybeUninit::<GlobalLU_t>::zeroed().assume_init();
+        let mut mem_usage = mem_usage_t { for_lu: 0.0, total_needed: 0.0 };
+        sgssvx(
+            &mut options,
+            a.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            perm_c.as_mut_ptr(),
+            perm_r.as_mut_ptr(),
+            etree.as_mut_ptr(),
+            &mut equed,
+            row_scale.as_mut_ptr(),
+            column_scale.as_mut_ptr(),
+            l.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            u.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            work,
+            lwork,
+            b.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            x.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            &mut recip_pivot_growth,
+            &mut rcond,
+            ferr.as_mut_ptr(),
+            berr.as_mut_ptr(),
+            &mut glu,
+            &mut mem_usage,
+            stat.get_stat(),
+            &mut info,
+        );
+        c_destroy_dense_matrix(&mut b);
+        let result = from_expert_vectors(info, a.num_columns(), x, perm_c, perm_r, l, u, user_work);
+        (result, mem_usage)
+    }
+
+    unsafe fn c_query_space(l: &CSuperMatrix, u: &CSuperMatrix) -> mem_usage_t {
+        let mut mem_usage = mem_usage_t { for_lu: 0.0, total_needed: 0.0 };
+        sQuerySpace(
+            l.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            u.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            &mut mem_usage,
+        );
+        mem_usage
+    }
+
+    fn magnitude(self) -> f64 {
+        self.abs() as f64
+    }
+
+    fn from_real(real: f64) -> Self {
+        real as f32
+    }
+    fn to_complex(self) -> num::Complex<f64> {
+        num::Complex::new(self as f64, 0.0)
+    }
+
+    fn from_complex_lossy(value: num::Complex<f64>) -> Self {
+        value.re as f32
+    }
+
+    fn conj(self) -> Self {
+        self
     }
 }
 
 impl ValueType for f64 {
+    const IS_COMPLEX: bool = false;
+
     unsafe fn c_create_comp_col_matrix(
         num_rows: usize,
         non_zero_values: &mut Vec<f64>,
@@ -507,6 +977,8 @@ impl ValueType for f64 {
         let u = CSuperMatrix::alloc();
 	let (mut perm_c, mut perm_r, options)
 	    = make_simple_perms(a.num_columns(), perm_c, options);
+	#[cfg(feature = "tuning")]
+	let _tuning = options.tuning_params().apply();
 
         dgssv(
             options.get_options() as *const superlu_options_t as *mut superlu_options_t,
@@ -520,11 +992,206 @@ impl ValueType for f64 {
             &mut info,
         );
 
-	CSimpleResult::from_vectors(info, a.num_columns(), b, perm_c, perm_r, l, u,)
+	CSimpleResult::from_vectors(info, a.num_columns(), b, perm_c, perm_r, l, u, false)
+    }
+
+    unsafe fn c_solve_factored(
+        l: &CSuperMatrix,
+        u: &CSuperMatrix,
+        perm_c: &[i32],
+        perm_r: &[i32],
+        b: &mut CSuperMatrix,
+        stat: &mut CSuperluStat,
+    ) -> Result<(), Error> {
+        let mut info = 0i32;
+        dgstrs(
+            trans_t_NOTRANS,
+            l.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            u.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            perm_c.as_ptr() as *mut i32,
+            perm_r.as_ptr() as *mut i32,
+            b.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            stat.get_stat(),
+            &mut info,
+        );
+        if info < 0 {
+            return Err(Error::UnknownError);
+        }
+        Ok(())
+    }
+
+    unsafe fn c_estimate_rcond(
+        norm: &str,
+        l: &CSuperMatrix,
+        u: &CSuperMatrix,
+        anorm: f64,
+        stat: &mut CSuperluStat,
+    ) -> Result<f64, Error> {
+        let norm = c_string(norm);
+        let mut rcond = 0.0;
+        let mut info = 0i32;
+        dgscon(
+            norm.as_ptr() as *mut libc::c_char,
+            l.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            u.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            anorm,
+            &mut rcond,
+            stat.get_stat(),
+            &mut info,
+        );
+        if info < 0 {
+            return Err(Error::UnknownError);
+        }
+        Ok(rcond)
+    }
+
+    unsafe fn c_equilibrate(a: &mut CSuperMatrix) -> Result<CEquilibration, i32> {
+        let mut row_scale = vec![0.0; a.num_rows()];
+        let mut column_scale = vec![0.0; a.num_columns()];
+        let mut rowcnd = 0.0;
+        let mut colcnd = 0.0;
+        let mut amax = 0.0;
+        let mut info = 0i32;
+        dgsequ(
+            a.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            row_scale.as_mut_ptr(),
+            column_scale.as_mut_ptr(),
+            &mut rowcnd,
+            &mut colcnd,
+            &mut amax,
+            &mut info,
+        );
+        if info != 0 {
+            return Err(info);
+        }
+        let mut equed = b'N' as libc::c_char;
+        dlaqgs(
+            a.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            row_scale.as_mut_ptr(),
+            column_scale.as_mut_ptr(),
+            rowcnd,
+            colcnd,
+            amax,
+            &mut equed,
+        );
+        Ok(CEquilibration {
+            row_scale,
+            column_scale,
+            rowcnd,
+            colcnd,
+            amax,
+            equed: equed as u8,
+        })
+    }
+
+    unsafe fn c_pivot_growth(
+        num_columns: usize,
+        a: &CSuperMatrix,
+        perm_c: &[i32],
+        l: &CSuperMatrix,
+        u: &CSuperMatrix,
+    ) -> f64 {
+        dPivotGrowth(
+            num_columns as i32,
+            a.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            perm_c.as_ptr() as *mut i32,
+            l.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            u.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+        )
+    }
+
+    unsafe fn c_expert_driver(
+        options: SimpleDriverOptions,
+        a: &CSuperMatrix,
+        perm_c: Option<Vec<i32>>,
+        mut b: CSuperMatrix,
+        x: CSuperMatrix,
+        mut work: CWork,
+        stat: &mut CSuperluStat,
+    ) -> (CSimpleResult, mem_usage_t) {
+        let mut info = 0i32;
+        let l = CSuperMatrix::alloc();
+        let u = CSuperMatrix::alloc();
+        let (mut perm_c, mut perm_r, options) = make_simple_perms(a.num_columns(), perm_c, options);
+        #[cfg(feature = "tuning")]
+        let _tuning = options.tuning_params().apply();
+        let mut options = make_expert_options(&options);
+        let (work, lwork) = work.c_work();
+        // An empty buffer gives lwork = 0, so SuperLU allocates
+        // the factors itself
+        let user_work = lwork > 0;
+        let mut etree = vec![0; a.num_columns()];
+        let mut equed = b'N' as libc::c_char;
+        let mut row_scale = vec![0.0; a.num_rows()];
+        let mut column_scale = vec![0.0; a.num_columns()];
+        let mut ferr = vec![0.0; b.num_columns()];
+        let mut berr = vec![0.0; b.num_columns()];
+        let mut recip_pivot_growth = 0.0;
+        let mut rcond = 0.0;
+        let mut glu = MaybeUninit::<GlobalLU_t>::zeroed().assume_init();
+        let mut mem_usage = mem_usage_t { for_lu: 0.0, total_needed: 0.0 };
+        dgssvx(
+            &mut options,
+            a.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            perm_c.as_mut_ptr(),
+            perm_r.as_mut_ptr(),
+            etree.as_mut_ptr(),
+            &mut equed,
+            row_scale.as_mut_ptr(),
+            column_scale.as_mut_ptr(),
+            l.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            u.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            work,
+            lwork,
+            b.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            x.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            &mut recip_pivot_growth,
+            &mut rcond,
+            ferr.as_mut_ptr(),
+            berr.as_mut_ptr(),
+            &mut glu,
+            &mut mem_usage,
+            stat.get_stat(),
+            &mut info,
+        );
+        c_destroy_dense_matrix(&mut b);
+        let result = from_expert_vectors(info, a.num_columns(), x, perm_c, perm_r, l, u, user_work);
+        (result, mem_usage)
+    }
+
+    unsafe fn c_query_space(l: &CSuperMatrix, u: &CSuperMatrix) -> mem_usage_t {
+        let mut mem_usage = mem_usage_t { for_lu: 0.0, total_needed: 0.0 };
+        dQuerySpace(
+            l.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            u.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            &mut mem_usage,
+        );
+        mem_usage
+    }
+
+    fn magnitude(self) -> f64 {
+        self.abs()
+    }
+
+    fn from_real(real: f64) -> Self {
+        real
+    }
+    fn to_complex(self) -> num::Complex<f64> {
+        num::Complex::new(self, 0.0)
+    }
+
+    fn from_complex_lossy(value: num::Complex<f64>) -> Self {
+        value.re
+    }
+
+    fn conj(self) -> Self {
+        self
     }
 }
 
 impl ValueType for num::Complex<f32> {
+    const IS_COMPLEX: bool = true;
+
     unsafe fn c_create_comp_col_matrix(
         num_rows: usize,
         non_zero_values: &mut Vec<num::Complex<f32>>,
@@ -603,6 +1270,8 @@ impl ValueType for num::Complex<f32> {
         let u = CSuperMatrix::alloc();
 	let (mut perm_c, mut perm_r, options)
 	    = make_simple_perms(a.num_columns(), perm_c, options);
+	#[cfg(feature = "tuning")]
+	let _tuning = options.tuning_params().apply();
 
 	cgssv(
             options.get_options() as *const superlu_options_t as *mut superlu_options_t,
@@ -616,11 +1285,206 @@ impl ValueType for num::Complex<f32> {
             &mut info,
         );
 
-	CSimpleResult::from_vectors(info, a.num_columns(), b, perm_c, perm_r, l, u,)
+	CSimpleResult::from_vectors(info, a.num_columns(), b, perm_c, perm_r, l, u, false)
+    }
+
+    unsafe fn c_solve_factored(
+        l: &CSuperMatrix,
+        u: &CSuperMatrix,
+        perm_c: &[i32],
+        perm_r: &[i32],
+        b: &mut CSuperMatrix,
+        stat: &mut CSuperluStat,
+    ) -> Result<(), Error> {
+        let mut info = 0i32;
+        cgstrs(
+            trans_t_NOTRANS,
+            l.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            u.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            perm_c.as_ptr() as *mut i32,
+            perm_r.as_ptr() as *mut i32,
+            b.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            stat.get_stat(),
+            &mut info,
+        );
+        if info < 0 {
+            return Err(Error::UnknownError);
+        }
+        Ok(())
+    }
+
+    unsafe fn c_estimate_rcond(
+        norm: &str,
+        l: &CSuperMatrix,
+        u: &CSuperMatrix,
+        anorm: f64,
+        stat: &mut CSuperluStat,
+    ) -> Result<f64, Error> {
+        let norm = c_string(norm);
+        let mut rcond = 0.0;
+        let mut info = 0i32;
+        cgscon(
+            norm.as_ptr() as *mut libc::c_char,
+            l.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            u.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            anorm as f32,
+            &mut rcond,
+            stat.get_stat(),
+            &mut info,
+        );
+        if info < 0 {
+            return Err(Error::UnknownError);
+        }
+        Ok(rcond as f64)
+    }
+
+    unsafe fn c_equilibrate(a: &mut CSuperMatrix) -> Result<CEquilibration, i32> {
+        let mut row_scale = vec![0.0; a.num_rows()];
+        let mut column_scale = vec![0.0; a.num_columns()];
+        let mut rowcnd = 0.0;
+        let mut colcnd = 0.0;
+        let mut amax = 0.0;
+        let mut info = 0i32;
+        cgsequ(
+            a.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            row_scale.as_mut_ptr(),
+            column_scale.as_mut_ptr(),
+            &mut rowcnd,
+            &mut colcnd,
+            &mut amax,
+            &mut info,
+        );
+        if info != 0 {
+            return Err(info);
+        }
+        let mut equed = b'N' as libc::c_char;
+        claqgs(
+            a.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            row_scale.as_mut_ptr(),
+            column_scale.as_mut_ptr(),
+            rowcnd,
+            colcnd,
+            amax,
+            &mut equed,
+        );
+        Ok(CEquilibration {
+            row_scale: row_scale.into_iter().map(|x| x as f64).collect(),
+            column_scale: column_scale.into_iter().map(|x| x as f64).collect(),
+            rowcnd: rowcnd as f64,
+            colcnd: colcnd as f64,
+            amax: amax as f64,
+            equed: equed as u8,
+        })
+    }
+
+    unsafe fn c_pivot_growth(
+        num_columns: usize,
+        a: &CSuperMatrix,
+        perm_c: &[i32],
+        l: &CSuperMatrix,
+        u: &CSuperMatrix,
+    ) -> f64 {
+        cPivotGrowth(
+            num_columns as i32,
+            a.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            perm_c.as_ptr() as *mut i32,
+            l.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            u.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+        ) as f64
+    }
+
+    unsafe fn c_expert_driver(
+        options: SimpleDriverOptions,
+        a: &CSuperMatrix,
+        perm_c: Option<Vec<i32>>,
+        mut b: CSuperMatrix,
+        x: CSuperMatrix,
+        mut work: CWork,
+        stat: &mut CSuperluStat,
+    ) -> (CSimpleResult, mem_usage_t) {
+        let mut info = 0i32;
+        let l = CSuperMatrix::alloc();
+        let u = CSuperMatrix::alloc();
+        let (mut perm_c, mut perm_r, options) = make_simple_perms(a.num_columns(), perm_c, options);
+        #[cfg(feature = "tuning")]
+        let _tuning = options.tuning_params().apply();
+        let mut options = make_expert_options(&options);
+        let (work, lwork) = work.c_work();
+        // An empty buffer gives lwork = 0, so SuperLU allocates
+        // the factors itself
+        let user_work = lwork > 0;
+        let mut etree = vec![0; a.num_columns()];
+        let mut equed = b'N' as libc::c_char;
+        let mut row_scale = vec![0.0; a.num_rows()];
+        let mut column_scale = vec![0.0; a.num_columns()];
+        let mut ferr = vec![0.0; b.num_columns()];
+        let mut berr = vec![0.0; b.num_columns()];
+        let mut recip_pivot_growth = 0.0;
+        let mut rcond = 0.0;
+        let mut glu = MaybeUninit::<GlobalLU_t>::zeroed().assume_init();
+        let mut mem_usage = mem_usage_t { for_lu: 0.0, total_needed: 0.0 };
+        cgssvx(
+            &mut options,
+            a.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            perm_c.as_mut_ptr(),
+            perm_r.as_mut_ptr(),
+            etree.as_mut_ptr(),
+            &mut equed,
+            row_scale.as_mut_ptr(),
+            column_scale.as_mut_ptr(),
+            l.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            u.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            work,
+            lwork,
+            b.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            x.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            &mut recip_pivot_growth,
+            &mut rcond,
+            ferr.as_mut_ptr(),
+            berr.as_mut_ptr(),
+            &mut glu,
+            &mut mem_usage,
+            stat.get_stat(),
+            &mut info,
+        );
+        c_destroy_dense_matrix(&mut b);
+        let result = from_expert_vectors(info, a.num_columns(), x, perm_c, perm_r, l, u, user_work);
+        (result, mem_usage)
+    }
+
+    unsafe fn c_query_space(l: &CSuperMatrix, u: &CSuperMatrix) -> mem_usage_t {
+        let mut mem_usage = mem_usage_t { for_lu: 0.0, total_needed: 0.0 };
+        cQuerySpace(
+            l.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            u.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            &mut mem_usage,
+        );
+        mem_usage
+    }
+
+    fn magnitude(self) -> f64 {
+        self.norm() as f64
+    }
+
+    fn from_real(real: f64) -> Self {
+        num::Complex::new(real as f32, 0.0)
+    }
+    fn to_complex(self) -> num::Complex<f64> {
+        num::Complex::new(self.re as f64, self.im as f64)
+    }
+
+    fn from_complex_lossy(value: num::Complex<f64>) -> Self {
+        num::Complex::new(value.re as f32, value.im as f32)
+    }
+
+    fn conj(self) -> Self {
+        num::Complex::new(self.re, -self.im)
     }
 }
 
 impl ValueType for num::Complex<f64> {
+    const IS_COMPLEX: bool = true;
+
     unsafe fn c_create_comp_col_matrix(
         num_rows: usize,
         non_zero_values: &mut Vec<num::Complex<f64>>,
@@ -699,6 +1563,8 @@ impl ValueType for num::Complex<f64> {
         let u = CSuperMatrix::alloc();
 	let (mut perm_c, mut perm_r, options)
 	    = make_simple_perms(a.num_columns(), perm_c, options);
+	#[cfg(feature = "tuning")]
+	let _tuning = options.tuning_params().apply();
 
 	zgssv(
             options.get_options() as *const superlu_options_t as *mut superlu_options_t,
@@ -712,6 +1578,199 @@ impl ValueType for num::Complex<f64> {
             &mut info,
         );
 
-	CSimpleResult::from_vectors(info, a.num_columns(), b, perm_c, perm_r, l, u,)
+	CSimpleResult::from_vectors(info, a.num_columns(), b, perm_c, perm_r, l, u, false)
+    }
+
+    unsafe fn c_solve_factored(
+        l: &CSuperMatrix,
+        u: &CSuperMatrix,
+        perm_c: &[i32],
+        perm_r: &[i32],
+        b: &mut CSuperMatrix,
+        stat: &mut CSuperluStat,
+    ) -> Result<(), Error> {
+        let mut info = 0i32;
+        zgstrs(
+            trans_t_NOTRANS,
+            l.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            u.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            perm_c.as_ptr() as *mut i32,
+            perm_r.as_ptr() as *mut i32,
+            b.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            stat.get_stat(),
+            &mut info,
+        );
+        if info < 0 {
+            return Err(Error::UnknownError);
+        }
+        Ok(())
+    }
+
+    unsafe fn c_estimate_rcond(
+        norm: &str,
+        l: &CSuperMatrix,
+        u: &CSuperMatrix,
+        anorm: f64,
+        stat: &mut CSuperluStat,
+    ) -> Result<f64, Error> {
+        let norm = c_string(norm);
+        let mut rcond = 0.0;
+        let mut info = 0i32;
+        zgscon(
+            norm.as_ptr() as *mut libc::c_char,
+            l.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            u.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            anorm,
+            &mut rcond,
+            stat.get_stat(),
+            &mut info,
+        );
+        if info < 0 {
+            return Err(Error::UnknownError);
+        }
+        Ok(rcond)
+    }
+
+    unsafe fn c_equilibrate(a: &mut CSuperMatrix) -> Result<CEquilibration, i32> {
+        let mut row_scale = vec![0.0; a.num_rows()];
+        let mut column_scale = vec![0.0; a.num_columns()];
+        let mut rowcnd = 0.0;
+        let mut colcnd = 0.0;
+        let mut amax = 0.0;
+        let mut info = 0i32;
+        zgsequ(
+            a.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            row_scale.as_mut_ptr(),
+            column_scale.as_mut_ptr(),
+            &mut rowcnd,
+            &mut colcnd,
+            &mut amax,
+            &mut info,
+        );
+        if info != 0 {
+            return Err(info);
+        }
+        let mut equed = b'N' as libc::c_char;
+        zlaqgs(
+            a.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            row_scale.as_mut_ptr(),
+            column_scale.as_mut_ptr(),
+            rowcnd,
+            colcnd,
+            amax,
+            &mut equed,
+        );
+        Ok(CEquilibration {
+            row_scale,
+            column_scale,
+            rowcnd,
+            colcnd,
+            amax,
+            equed: equed as u8,
+        })
+    }
+
+    unsafe fn c_pivot_growth(
+        num_columns: usize,
+        a: &CSuperMatrix,
+        perm_c: &[i32],
+        l: &CSuperMatrix,
+        u: &CSuperMatrix,
+    ) -> f64 {
+        zPivotGrowth(
+            num_columns as i32,
+            a.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            perm_c.as_ptr() as *mut i32,
+            l.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            u.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+        )
+    }
+
+    unsafe fn c_expert_driver(
+        options: SimpleDriverOptions,
+        a: &CSuperMatrix,
+        perm_c: Option<Vec<i32>>,
+        mut b: CSuperMatrix,
+        x: CSuperMatrix,
+        mut work: CWork,
+        stat: &mut CSuperluStat,
+    ) -> (CSimpleResult, mem_usage_t) {
+        let mut info = 0i32;
+        let l = CSuperMatrix::alloc();
+        let u = CSuperMatrix::alloc();
+        let (mut perm_c, mut perm_r, options) = make_simple_perms(a.num_columns(), perm_c, options);
+        #[cfg(feature = "tuning")]
+        let _tuning = options.tuning_params().apply();
+        let mut options = make_expert_options(&options);
+        let (work, lwork) = work.c_work();
+        // An empty buffer gives lwork = 0, so SuperLU allocates
+        // the factors itself
+        let user_work = lwork > 0;
+        let mut etree = vec![0; a.num_columns()];
+        let mut equed = b'N' as libc::c_char;
+        let mut row_scale = vec![0.0; a.num_rows()];
+        let mut column_scale = vec![0.0; a.num_columns()];
+        let mut ferr = vec![0.0; b.num_columns()];
+        let mut berr = vec![0.0; b.num_columns()];
+        let mut recip_pivot_growth = 0.0;
+        let mut rcond = 0.0;
+        let mut glu = MaybeUninit::<GlobalLU_t>::zeroed().assume_init();
+        let mut mem_usage = mem_usage_t { for_lu: 0.0, total_needed: 0.0 };
+        zgssvx(
+            &mut options,
+            a.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            perm_c.as_mut_ptr(),
+            perm_r.as_mut_ptr(),
+            etree.as_mut_ptr(),
+            &mut equed,
+            row_scale.as_mut_ptr(),
+            column_scale.as_mut_ptr(),
+            l.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            u.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            work,
+            lwork,
+            b.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            x.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            &mut recip_pivot_growth,
+            &mut rcond,
+            ferr.as_mut_ptr(),
+            berr.as_mut_ptr(),
+            &mut glu,
+            &mut mem_usage,
+            stat.get_stat(),
+            &mut info,
+        );
+        c_destroy_dense_matrix(&mut b);
+        let result = from_expert_vectors(info, a.num_columns(), x, perm_c, perm_r, l, u, user_work);
+        (result, mem_usage)
+    }
+
+    unsafe fn c_query_space(l: &CSuperMatrix, u: &CSuperMatrix) -> mem_usage_t {
+        let mut mem_usage = mem_usage_t { for_lu: 0.0, total_needed: 0.0 };
+        zQuerySpace(
+            l.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            u.super_matrix() as *const SuperMatrix as *mut SuperMatrix,
+            &mut mem_usage,
+        );
+        mem_usage
+    }
+
+    fn magnitude(self) -> f64 {
+        self.norm()
+    }
+
+    fn from_real(real: f64) -> Self {
+        num::Complex::new(real, 0.0)
+    }
+    fn to_complex(self) -> num::Complex<f64> {
+        self
+    }
+
+    fn from_complex_lossy(value: num::Complex<f64>) -> Self {
+        value
+    }
+
+    fn conj(self) -> Self {
+        num::Complex::new(self.re, -self.im)
     }
 }
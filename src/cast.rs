@@ -0,0 +1,183 @@
+//! Convert matrices between value types
+//!
+//! [CompColMatrix], [DenseMatrix] and [SparseMat] can be converted
+//! to any other [ValueType] with `cast::<Q>()`. Each value is
+//! converted through a double-precision complex number:
+//!
+//! * Real values become complex values with a zero imaginary
+//!   part, and single-precision values are converted to double
+//!   precision exactly.
+//! * Double-precision values are rounded to single precision.
+//!   Values that are too large for single precision would become
+//!   infinite, so [CastError::Overflow] is returned instead.
+//! * Complex values can only be converted to a real type if
+//!   their imaginary part is zero. Otherwise,
+//!   [CastError::ImaginaryPart] is returned.
+//!
+//! Values that are already infinite (or NaN) are not treated as
+//! overflow. `cast_lossy::<Q>()` performs the same conversion without any
+//! checks: values that are too large become infinite, and the
+//! imaginary parts are discarded when converting to a real type.
+//!
+
+use std::fmt;
+
+use crate::c::value_type::ValueType;
+use crate::comp_col::CompColMatrix;
+use crate::dense::DenseMatrix;
+use crate::sparse_matrix::SparseMat;
+
+/// Errors that can occur when converting a matrix to a different
+/// value type. The position is the row and column of a value
+/// that could not be converted.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CastError {
+    /// The value is finite, but too large for the new type
+    Overflow { row: usize, col: usize },
+    /// The value has a non-zero imaginary part, and the new type
+    /// is real
+    ImaginaryPart { row: usize, col: usize },
+}
+
+impl std::error::Error for CastError {}
+
+impl fmt::Display for CastError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Overflow { row, col } =>
+                write!(f, "Cannot cast: the value at ({row}, {col}) is too large for the new type"),
+            Self::ImaginaryPart { row, col } =>
+                write!(f, "Cannot cast: the value at ({row}, {col}) has a non-zero imaginary part"),
+        }
+    }
+}
+
+/// Convert a value to another value type, without any checks
+pub fn cast_value_lossy<P: ValueType, Q: ValueType>(value: P) -> Q {
+    Q::from_complex_lossy(value.to_complex())
+}
+
+/// Convert a value to another value type, checking that it is
+/// not too large for the new type, and that it does not have a
+/// non-zero imaginary part if the new type is real. The row and
+/// column are used in the error.
+pub fn cast_value<P: ValueType, Q: ValueType>(value: P, row: usize, col: usize) -> Result<Q, CastError> {
+    let value = value.to_complex();
+    if !Q::IS_COMPLEX && value.im != 0.0 {
+        return Err(CastError::ImaginaryPart { row, col });
+    }
+    let cast = Q::from_complex_lossy(value).to_complex();
+    let overflow = |before: f64, after: f64| before.is_finite() && after.is_infinite();
+    if overflow(value.re, cast.re) || overflow(value.im, cast.im) {
+        return Err(CastError::Overflow { row, col });
+    }
+    Ok(Q::from_complex_lossy(value))
+}
+
+impl<P: ValueType> CompColMatrix<P> {
+    /// Convert the matrix to another value type (see
+    /// [cast](crate::cast)). The pattern is unchanged.
+    ///
+    /// # Errors
+    ///
+    /// If a value is too large for the new type, or has a
+    /// non-zero imaginary part and the new type is real.
+    ///
+    pub fn cast<Q: ValueType>(&self) -> Result<CompColMatrix<Q>, CastError> {
+        let column_offsets = self.column_offsets();
+        let row_indices = self.row_indices();
+        let mut non_zero_values = Vec::with_capacity(row_indices.len());
+        for col in 0..self.num_columns() {
+            let column = column_offsets[col] as usize..column_offsets[col + 1] as usize;
+            for (&row, &value) in row_indices[column.clone()].iter().zip(&self.non_zero_values()[column]) {
+                non_zero_values.push(cast_value(value, row as usize, col)?);
+            }
+        }
+        Ok(self.with_values(non_zero_values))
+    }
+
+    /// Convert the matrix to another value type without checking
+    /// for overflow or discarded imaginary parts (see
+    /// [cast](crate::cast)). The pattern is unchanged.
+    pub fn cast_lossy<Q: ValueType>(&self) -> CompColMatrix<Q> {
+        let non_zero_values = self.non_zero_values().iter().map(|&value| cast_value_lossy(value)).collect();
+        self.with_values(non_zero_values)
+    }
+
+    /// Make a matrix with the same pattern and new values
+    fn with_values<Q: ValueType>(&self, non_zero_values: Vec<Q>) -> CompColMatrix<Q> {
+        CompColMatrix::from_vectors(
+            self.num_rows(),
+            non_zero_values,
+            self.row_indices().to_vec(),
+            self.column_offsets().to_vec(),
+        )
+    }
+}
+
+impl<P: ValueType> DenseMatrix<P> {
+    /// Convert the matrix to another value type (see
+    /// [cast](crate::cast))
+    ///
+    /// # Errors
+    ///
+    /// If a value is too large for the new type, or has a
+    /// non-zero imaginary part and the new type is real.
+    ///
+    pub fn cast<Q: ValueType>(&self) -> Result<DenseMatrix<Q>, CastError> {
+        let mut values = Vec::with_capacity(self.num_rows() * self.num_columns());
+        for col in 0..self.num_columns() {
+            for (row, &value) in self.column(col).iter().enumerate() {
+                values.push(cast_value(value, row, col)?);
+            }
+        }
+        Ok(DenseMatrix::from_vectors(self.num_rows(), self.num_columns(), values))
+    }
+
+    /// Convert the matrix to another value type without checking
+    /// for overflow or discarded imaginary parts (see
+    /// [cast](crate::cast))
+    pub fn cast_lossy<Q: ValueType>(&self) -> DenseMatrix<Q> {
+        let mut values = Vec::with_capacity(self.num_rows() * self.num_columns());
+        for col in 0..self.num_columns() {
+            values.extend(self.column(col).iter().map(|&value| cast_value_lossy::<P, Q>(value)));
+        }
+        DenseMatrix::from_vectors(self.num_rows(), self.num_columns(), values)
+    }
+}
+
+impl<P: ValueType> SparseMat<P> {
+    /// Convert the matrix to another value type (see
+    /// [cast](crate::cast))
+    ///
+    /// # Errors
+    ///
+    /// If a value is too large for the new type, or has a
+    /// non-zero imaginary part and the new type is real. The
+    /// values are not visited in any particular order, so if
+    /// there is more than one such value, any of them may be
+    /// reported.
+    ///
+    pub fn cast<Q: ValueType>(&self) -> Result<SparseMat<Q>, CastError> {
+        let mut matrix = SparseMat::new(self.num_rows(), self.num_cols());
+        for (&(row, col), &value) in self.non_zero_vals() {
+            matrix.insert(row, col, cast_value(value, row, col)?);
+        }
+        Ok(matrix)
+    }
+
+    /// Convert the matrix to another value type without checking
+    /// for overflow or discarded imaginary parts (see
+    /// [cast](crate::cast)). Values that become zero (for
+    /// example, by underflow) are not stored.
+    pub fn cast_lossy<Q: ValueType>(&self) -> SparseMat<Q> {
+        let mut matrix = SparseMat::new(self.num_rows(), self.num_cols());
+        for (&(row, col), &value) in self.non_zero_vals() {
+            matrix.insert(row, col, cast_value_lossy(value));
+        }
+        matrix
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,75 @@
+use super::*;
+use num::Complex;
+
+/// The matrix
+///
+///  1  0 -3
+/// -2  4  0
+///  0  0  5
+fn test_matrix() -> CompColMatrix<f64> {
+    CompColMatrix::from_vectors(
+        3,
+        vec![1.0, -2.0, 4.0, -3.0, 5.0],
+        vec![0, 1, 1, 0, 2],
+        vec![0, 2, 3, 5],
+    )
+}
+
+#[test]
+fn real_to_complex() {
+    let a = test_matrix().cast::<Complex<f64>>().unwrap();
+    assert_eq!(a.row_indices(), &[0, 1, 1, 0, 2]);
+    assert_eq!(a.column_offsets(), &[0, 2, 3, 5]);
+    assert_eq!(a.value(1, 0), Complex::new(-2.0, 0.0));
+    assert_eq!(a.value(2, 2), Complex::new(5.0, 0.0));
+}
+
+#[test]
+fn double_to_single_and_back() {
+    let a = test_matrix().cast::<f32>().unwrap();
+    assert_eq!(a.value(0, 2), -3.0f32);
+    let a = a.cast::<Complex<f64>>().unwrap().cast::<f64>().unwrap();
+    assert_eq!(a.non_zero_values(), test_matrix().non_zero_values());
+}
+
+#[test]
+fn overflow_is_reported() {
+    let a = CompColMatrix::from_vectors(2, vec![1.0, 1e300], vec![0, 1], vec![0, 1, 2]);
+    assert_eq!(a.cast::<f32>().unwrap_err(), CastError::Overflow { row: 1, col: 1 });
+    assert_eq!(a.cast::<Complex<f32>>().unwrap_err(), CastError::Overflow { row: 1, col: 1 });
+    assert!(a.cast_lossy::<f32>().value(1, 1).is_infinite());
+    // Values that are already infinite are not an overflow
+    let b = DenseMatrix::from_vectors(2, 1, vec![f64::INFINITY, 1.0]);
+    assert!(b.cast::<f32>().unwrap().column(0)[0].is_infinite());
+}
+
+#[test]
+fn imaginary_part_is_reported() {
+    let b = DenseMatrix::from_vectors(2, 2, vec![
+        Complex::new(1.0, 0.0),
+        Complex::new(2.0, 0.0),
+        Complex::new(3.0, 0.0),
+        Complex::new(4.0, 0.5),
+    ]);
+    assert_eq!(b.cast::<f64>().err(), Some(CastError::ImaginaryPart { row: 1, col: 1 }));
+    let real = b.cast_lossy::<f64>();
+    assert_eq!(real.column(1), &[3.0, 4.0]);
+    let single = b.cast::<Complex<f32>>().unwrap();
+    assert_eq!(single.column(1)[1], Complex::new(4.0f32, 0.5));
+}
+
+#[test]
+fn sparse_mat_cast() {
+    let mut a = SparseMat::new(3, 2);
+    a.insert(0, 0, 1.5f64);
+    a.insert(2, 1, 1e-60);
+    let complex = a.cast::<Complex<f64>>().unwrap();
+    assert_eq!(complex.num_rows(), 3);
+    assert_eq!(complex.num_cols(), 2);
+    assert_eq!(complex.get(0, 0), Complex::new(1.5, 0.0));
+    // 1e-60 underflows to zero in single precision, and is no
+    // longer stored
+    let single = a.cast::<f32>().unwrap();
+    assert_eq!(single.num_non_zeros(), 1);
+    assert_eq!(single.get(0, 0), 1.5);
+}
@@ -17,9 +17,24 @@ use crate::harwell_boeing::HarwellBoeingMatrix;
 use crate::c::super_matrix::CSuperMatrix;
 use csuperlu_sys::NCformat;
 use crate::c::value_type::ValueType;
+use crate::display::{DisplayView, MatrixDisplay, MatrixElements};
+use crate::equilibration::{Equilibration, EquilibrationError};
+use crate::ordering::SparsityPattern;
+use std::fmt;
 use std::fs;
+use std::io;
 use std::ops::Mul;
-use std::process;
+
+/// Convert a row index or a number of non-zeros to a SuperLU
+/// (32-bit) index
+///
+/// # Panics
+///
+/// If the value does not fit in an i32.
+///
+pub(crate) fn to_index(value: usize) -> i32 {
+    i32::try_from(value).expect("Too many non-zeros for a SuperLU (32-bit) index")
+}
 
 /// Compressed-column matrix
 ///
@@ -44,11 +59,13 @@ impl<P: ValueType> CompColMatrix<P> {
     /// stored in Harwell-Boeing format. The function will
     /// attempt to parse the non-zero values in the precision
     /// P
-    pub fn from_harwell_boeing(file_path: String) -> Self {
-        let file = fs::File::open(&file_path).unwrap_or_else(|err| {
-            println!("Problem opening file '{file_path}': {err}");
-            process::exit(1);
-        });
+    ///
+    /// # Errors
+    ///
+    /// If the file cannot be opened, the error is returned.
+    ///
+    pub fn from_harwell_boeing(file_path: String) -> io::Result<Self> {
+        let file = fs::File::open(&file_path)?;
 
         let matrix = HarwellBoeingMatrix::<P>::from_file(file);
 
@@ -59,7 +76,7 @@ impl<P: ValueType> CompColMatrix<P> {
         let (column_offsets, row_indices, non_zero_values) = matrix.to_vectors();
 
         // Make the left-hand side matrix
-        Self::from_vectors(num_rows, non_zero_values, row_indices, column_offsets)
+        Ok(Self::from_vectors(num_rows, non_zero_values, row_indices, column_offsets))
     }
 
     /// Specify a compressed column matrix from input vectors.
@@ -96,7 +113,13 @@ impl<P: ValueType> CompColMatrix<P> {
         }
     }
 
-    pub fn value(&mut self, row: usize, col: usize) -> P {
+    /// Get the value of the element at (row, column), which is
+    /// zero if the element is not stored
+    ///
+    /// The row indices in each column do not need to be sorted
+    /// (they are not sorted in the $U$ factor returned by SuperLU).
+    ///
+    pub fn value(&self, row: usize, col: usize) -> P {
         let super_matrix = self.super_matrix();
         assert!(row < super_matrix.num_rows(), "Row index out of range");
         assert!(
@@ -106,10 +129,109 @@ impl<P: ValueType> CompColMatrix<P> {
         let col_start = self.column_offsets()[col] as usize;
         let col_end = self.column_offsets()[col + 1] as usize;
         let row_indices = &self.row_indices()[col_start..col_end];
-        match row_indices.binary_search(&(row as i32)) {
-            Ok(row_index) => self.non_zero_values()[col_start + row_index],
-            Err(_) => P::zero(),
+        match row_indices.iter().position(|&r| r as usize == row) {
+            Some(row_index) => self.non_zero_values()[col_start + row_index],
+            None => P::zero(),
+        }
+    }
+
+    /// Iterate over the (row, value) pairs stored in a column
+    ///
+    /// The pairs are returned in the order they are stored, so
+    /// the rows are not necessarily sorted.
+    ///
+    pub fn column(&self, col: usize) -> impl Iterator<Item = (usize, P)> + '_ {
+        assert!(col < self.num_columns(), "Column index out of range");
+        let start = self.column_offsets()[col] as usize;
+        let end = self.column_offsets()[col + 1] as usize;
+        self.row_indices()[start..end]
+            .iter()
+            .zip(&self.non_zero_values()[start..end])
+            .map(|(&row, &value)| (row as usize, value))
+    }
+
+    /// Compute the matrix-vector product $Ax$
+    ///
+    /// Each column of the matrix is multiplied by the matching
+    /// entry of $x$ and added to the result. The length of $x$
+    /// must equal the number of columns.
+    ///
+    pub fn multiply_vector(&self, x: &[P]) -> Vec<P> {
+        assert!(
+            self.num_columns() == x.len(),
+            "Cannot multiply; incompatible dimensions"
+        );
+        let mut b = vec![P::zero(); self.num_rows()];
+        for (col, &x_value) in x.iter().enumerate() {
+            for (row, value) in self.column(col) {
+                b[row] = b[row] + value * x_value;
+            }
         }
+        b
+    }
+
+    /// Get the 1-norm of the matrix (the maximum absolute
+    /// column sum)
+    pub fn norm_one(&self) -> f64 {
+        self.column_offsets()
+            .windows(2)
+            .map(|offsets| {
+                self.non_zero_values()[offsets[0] as usize..offsets[1] as usize]
+                    .iter()
+                    .map(|value| value.magnitude())
+                    .sum()
+            })
+            .fold(0.0, f64::max)
+    }
+
+    /// Get the infinity-norm of the matrix (the maximum absolute
+    /// row sum)
+    pub fn norm_inf(&self) -> f64 {
+        let mut row_sums = vec![0.0; self.num_rows()];
+        for (&row, value) in self.row_indices().iter().zip(self.non_zero_values()) {
+            row_sums[row as usize] += value.magnitude();
+        }
+        row_sums.into_iter().fold(0.0, f64::max)
+    }
+
+    /// Get the largest absolute value of the elements in the
+    /// matrix (this is not a consistent matrix norm)
+    pub fn norm_max(&self) -> f64 {
+        self.non_zero_values()
+            .iter()
+            .map(|value| value.magnitude())
+            .fold(0.0, f64::max)
+    }
+
+    /// Get the Frobenius norm of the matrix (the square root of
+    /// the sum of the squares of the absolute values)
+    pub fn norm_frobenius(&self) -> f64 {
+        self.non_zero_values()
+            .iter()
+            .map(|value| value.magnitude().powi(2))
+            .sum::<f64>()
+            .sqrt()
+    }
+
+    /// Equilibrate the matrix in place, scaling its rows and
+    /// columns so that the largest element in each has magnitude
+    /// close to one (see [equilibration](crate::equilibration))
+    ///
+    /// The scalings are only applied if they are worthwhile. The
+    /// returned [Equilibration] contains the scale factors, and
+    /// can be used to scale the right-hand side and unscale the
+    /// solution.
+    ///
+    /// # Errors
+    ///
+    /// If the matrix has a row or column that is exactly zero,
+    /// an error is returned and the matrix is not modified.
+    ///
+    pub fn equilibrate(&mut self) -> Result<Equilibration, EquilibrationError> {
+        let num_rows = self.num_rows();
+        unsafe { P::c_equilibrate(&mut self.super_matrix) }
+            .map(Equilibration::from_c_equilibration)
+            .map_err(|info| EquilibrationError::from_c_info(info, num_rows))
     }
 
     pub fn num_rows(&self) -> usize {
@@ -144,38 +266,69 @@ impl<P: ValueType> CompColMatrix<P> {
         }
     }
 
+    /// Get the pattern of the stored elements (without the
+    /// values)
+    pub fn pattern(&self) -> SparsityPattern<'_> {
+        SparsityPattern::new(self.num_rows(), self.column_offsets(), self.row_indices())
+    }
+
     pub fn super_matrix<'a>(&'a self) -> &'a CSuperMatrix {
         &self.super_matrix
     }
 
-    /// Call the SuperLU C library print function for this type
+    /// Get a configurable view of the matrix for printing
+    /// (see [display](crate::display))
+    pub fn display(&self) -> MatrixDisplay<'_, P> {
+        MatrixDisplay::new(self, DisplayView::Sparse)
+    }
+
+    /// Print the matrix to stdout, preceded by a label
     pub fn print(&self, what: &str) {
-        unsafe {
-            P::c_print_comp_col_matrix(what, &self.super_matrix);
-        }
+        println!("{what} = {self}");
+    }
+}
+
+impl<P: ValueType> MatrixElements<P> for CompColMatrix<P> {
+    fn num_rows(&self) -> usize {
+        self.num_rows()
+    }
+    fn num_columns(&self) -> usize {
+        self.num_columns()
+    }
+    fn num_stored(&self) -> usize {
+        self.non_zero_values().len()
+    }
+    fn stored_elements(&self) -> Box<dyn Iterator<Item = (usize, usize, P)> + '_> {
+        let column_offsets = self.column_offsets();
+        Box::new((0..self.num_columns()).flat_map(move |col| {
+            let start = column_offsets[col] as usize;
+            let end = column_offsets[col + 1] as usize;
+            self.row_indices()[start..end]
+                .iter()
+                .zip(&self.non_zero_values()[start..end])
+                .map(move |(&row, &value)| (row as usize, col, value))
+        }))
+    }
+    fn element(&self, row: usize, col: usize) -> P {
+        self.value(row, col)
+    }
+}
+
+impl<P: ValueType> fmt::Display for CompColMatrix<P> {
+    /// Display the stored elements of the matrix, or the
+    /// dense matrix if the alternate format `{:#}` is used
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.display().alternate(f.alternate()), f)
     }
 }
 
 impl<'a, P: ValueType> Mul<&Vec<P>> for &'a mut CompColMatrix<P> {
     type Output = Vec<P>;
 
-    /// Naive matrix multiplication which loops over all
-    /// each full row of the sparse matrix and adds up the
-    /// results.
+    /// Matrix-vector multiplication (see
+    /// [CompColMatrix::multiply_vector])
     fn mul(self, x: &Vec<P>) -> Vec<P> {
-        assert!(
-            self.num_columns() == x.len(),
-            "Cannot multiply; incompatible dimensions"
-        );
-        let mut b = Vec::<P>::new();
-        for row in 0..self.num_rows() {
-            let mut value = P::zero();
-            for column in 0..self.num_columns() {
-                value = value + (self.value(row, column) * x[row]);
-            }
-            b.push(value);
-        }
-        b
+        self.multiply_vector(x)
     }
 }
 
@@ -186,3 +339,6 @@ impl<P: ValueType> Drop for CompColMatrix<P> {
 	}
     }
 }
+
+#[cfg(test)]
+mod tests;
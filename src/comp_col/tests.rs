@@ -0,0 +1,60 @@
+use super::*;
+
+/// The matrix
+///
+///  1  0 -3
+/// -2  4  0
+///  0  0  5
+fn test_matrix() -> CompColMatrix<f64> {
+    CompColMatrix::from_vectors(
+        3,
+        vec![1.0, -2.0, 4.0, -3.0, 5.0],
+        vec![0, 1, 1, 0, 2],
+        vec![0, 2, 3, 5],
+    )
+}
+
+#[test]
+fn matrix_norms() {
+    let a = test_matrix();
+    assert_eq!(a.norm_one(), 8.0);
+    assert_eq!(a.norm_inf(), 6.0);
+    assert_eq!(a.norm_max(), 5.0);
+    assert!((a.norm_frobenius() - 55.0f64.sqrt()).abs() < 1e-12);
+}
+
+#[test]
+fn complex_matrix_norms() {
+    let a = CompColMatrix::from_vectors(
+        2,
+        vec![num::Complex::new(3.0, 4.0), num::Complex::new(0.0, -1.0)],
+        vec![0, 0],
+        vec![0, 1, 2],
+    );
+    assert_eq!(a.norm_one(), 5.0);
+    assert_eq!(a.norm_inf(), 6.0);
+    assert_eq!(a.norm_max(), 5.0);
+    assert!((a.norm_frobenius() - 26.0f64.sqrt()).abs() < 1e-12);
+}
+
+#[test]
+fn matrix_vector_product() {
+    let mut a = test_matrix();
+    let b = &mut a * &vec![1.0, 2.0, 3.0];
+    assert_eq!(b, vec![-8.0, 6.0, 15.0]);
+}
+
+#[test]
+fn multiply_vector_by_reference() {
+    let a = test_matrix();
+    assert_eq!(a.multiply_vector(&[1.0, 2.0, 3.0]), vec![-8.0, 6.0, 15.0]);
+    assert_eq!(a.multiply_vector(&[0.0, 0.0, 0.0]), vec![0.0; 3]);
+}
+
+#[test]
+fn column_iterator() {
+    let a = test_matrix();
+    assert_eq!(a.column(0).collect::<Vec<_>>(), vec![(0, 1.0), (1, -2.0)]);
+    assert_eq!(a.column(1).collect::<Vec<_>>(), vec![(1, 4.0)]);
+    assert_eq!(a.column(2).collect::<Vec<_>>(), vec![(0, -3.0), (2, 5.0)]);
+}
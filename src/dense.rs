@@ -1,10 +1,21 @@
 //! Functions to create dense matrices.
 //!
+//! Dense matrices are stored in column-major order. Element
+//! $(i, j)$ is stored at offset $i + j \times \text{lda}$ in the
+//! underlying storage, where $\text{lda} \ge n_\text{rows}$ is the
+//! leading dimension of the matrix (the distance between the
+//! starts of consecutive columns). Matrices created in this
+//! module always have $\text{lda} = n_\text{rows}$, but matrices
+//! returned by SuperLU may not.
+//!
 
 use crate::c::free::c_destroy_dense_matrix;
 use crate::c::super_matrix::CSuperMatrix;
 use crate::c::value_type::ValueType;
+use crate::display::{DisplayView, MatrixDisplay, MatrixElements};
 use csuperlu_sys::DNformat;
+use std::fmt;
+use std::ops::{Index, IndexMut};
 
 pub struct DenseMatrix<P: ValueType> {
     super_matrix: CSuperMatrix,
@@ -31,6 +42,45 @@ impl<P: ValueType> DenseMatrix<P> {
         }
     }
 
+    /// Create a dense matrix of the given size, with all
+    /// elements equal to zero.
+    pub fn zeros(num_rows: usize, num_columns: usize) -> Self {
+        Self::from_vectors(num_rows, num_columns, vec![P::zero(); num_rows * num_columns])
+    }
+
+    /// Create a square identity matrix of the given size.
+    pub fn identity(size: usize) -> Self {
+        let mut x = Self::zeros(size, size);
+        for n in 0..size {
+            x[(n, n)] = P::one();
+        }
+        x
+    }
+
+    /// Specify a dense matrix from a vector of values in
+    /// row-major order (i.e. the first row, followed by the
+    /// second row, etc.). The values are transposed into the
+    /// column-major order used by SuperLU.
+    ///
+    /// # Panics
+    ///
+    /// If the length of the vector is not equal to
+    /// num_rows * num_columns.
+    ///
+    pub fn from_row_major(num_rows: usize, num_columns: usize, x: Vec<P>) -> Self {
+        assert!(
+            x.len() == num_rows * num_columns,
+            "Number of values does not match the matrix dimensions"
+        );
+        let mut column_major = Vec::with_capacity(x.len());
+        for col in 0..num_columns {
+            for row in 0..num_rows {
+                column_major.push(x[row * num_columns + col]);
+            }
+        }
+        Self::from_vectors(num_rows, num_columns, column_major)
+    }
+
     /// Create a DenseMatrix from a SuperMatrix
     ///
     /// # Safety
@@ -79,21 +129,154 @@ impl<P: ValueType> DenseMatrix<P> {
         self.super_matrix.num_columns()
     }
 
-    pub fn column_major_values(&mut self) -> &[P] {
+    /// Get the leading dimension of the matrix (the offset between
+    /// the starts of consecutive columns in the underlying storage)
+    pub fn leading_dimension(&self) -> usize {
+        unsafe { self.super_matrix.store::<DNformat>().lda as usize }
+    }
+
+    /// Get the number of values in the underlying storage
+    /// (including any padding between columns, but not after
+    /// the last column)
+    fn storage_len(&self) -> usize {
+        match self.num_columns() {
+            0 => 0,
+            num_columns => self.leading_dimension() * (num_columns - 1) + self.num_rows(),
+        }
+    }
+
+    /// Get the underlying storage of the dense matrix, in
+    /// column-major order
+    ///
+    /// If the leading dimension is larger than the number of
+    /// rows, the storage contains padding between the columns.
+    /// Use [to_vec](Self::to_vec) to obtain the values without
+    /// padding, or [column](Self::column) to access one column.
+    pub fn column_major_values(&self) -> &[P] {
+        unsafe {
+            let c_dnformat = self.super_matrix.store::<DNformat>();
+            std::slice::from_raw_parts(c_dnformat.nzval as *mut P, self.storage_len())
+        }
+    }
+
+    /// Get the underlying storage of the dense matrix as a
+    /// mutable slice (see [column_major_values](Self::column_major_values))
+    pub fn column_major_values_mut(&mut self) -> &mut [P] {
         unsafe {
             let c_dnformat = self.super_matrix.store::<DNformat>();
-            let size = self.num_rows() * self.num_columns();
-            std::slice::from_raw_parts(c_dnformat.nzval as *mut P, size as usize)
+            std::slice::from_raw_parts_mut(c_dnformat.nzval as *mut P, self.storage_len())
+        }
+    }
+
+    /// Get a column of the matrix as a slice
+    ///
+    /// # Panics
+    ///
+    /// If the column index is out of range.
+    pub fn column(&self, col: usize) -> &[P] {
+        assert!(col < self.num_columns(), "Column index out of range");
+        let start = col * self.leading_dimension();
+        &self.column_major_values()[start..start + self.num_rows()]
+    }
+
+    /// Get a column of the matrix as a mutable slice
+    ///
+    /// # Panics
+    ///
+    /// If the column index is out of range.
+    pub fn column_mut(&mut self, col: usize) -> &mut [P] {
+        assert!(col < self.num_columns(), "Column index out of range");
+        let start = col * self.leading_dimension();
+        let num_rows = self.num_rows();
+        &mut self.column_major_values_mut()[start..start + num_rows]
+    }
+
+    /// Overwrite a column of the matrix with new values
+    ///
+    /// This can be used to refill a right-hand side matrix
+    /// with new values, without allocating a new matrix.
+    ///
+    /// # Panics
+    ///
+    /// If the column index is out of range, or if the
+    /// number of values is not equal to the number of rows.
+    pub fn set_column(&mut self, col: usize, values: &[P]) {
+        assert!(
+            values.len() == self.num_rows(),
+            "Number of values does not match the number of rows"
+        );
+        self.column_mut(col).copy_from_slice(values);
+    }
+
+    /// Copy the values in the matrix into a vector, in
+    /// column-major order (without any padding between columns)
+    pub fn to_vec(&self) -> Vec<P> {
+        let mut values = Vec::with_capacity(self.num_rows() * self.num_columns());
+        for col in 0..self.num_columns() {
+            values.extend_from_slice(self.column(col));
         }
+        values
     }
+
     pub fn super_matrix<'a>(&'a self) -> &'a CSuperMatrix {
         &self.super_matrix
     }
     
+    /// Get a configurable view of the matrix for printing
+    /// (see [display](crate::display))
+    pub fn display(&self) -> MatrixDisplay<'_, P> {
+        MatrixDisplay::new(self, DisplayView::Dense)
+    }
+
+    /// Print the matrix to stdout, preceded by a label
     pub fn print(&self, what: &str) {
-        unsafe {
-            P::c_print_dense_matrix(what, &self.super_matrix);
-        }
+        println!("{what} = {self}");
+    }
+}
+
+impl<P: ValueType> MatrixElements<P> for DenseMatrix<P> {
+    fn num_rows(&self) -> usize {
+        self.num_rows()
+    }
+    fn num_columns(&self) -> usize {
+        self.num_columns()
+    }
+    fn num_stored(&self) -> usize {
+        self.num_rows() * self.num_columns()
+    }
+    fn stored_elements(&self) -> Box<dyn Iterator<Item = (usize, usize, P)> + '_> {
+        Box::new((0..self.num_columns()).flat_map(move |col| {
+            self.column(col).iter().enumerate().map(move |(row, &value)| (row, col, value))
+        }))
+    }
+    fn element(&self, row: usize, col: usize) -> P {
+        self[(row, col)]
+    }
+}
+
+impl<P: ValueType> fmt::Display for DenseMatrix<P> {
+    /// Display the matrix as a grid of values, or list the
+    /// elements if the alternate format `{:#}` is used
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.display().alternate(f.alternate()), f)
+    }
+}
+
+impl<P: ValueType> Index<(usize, usize)> for DenseMatrix<P> {
+    type Output = P;
+
+    /// Get the element at (row, column)
+    fn index(&self, (row, col): (usize, usize)) -> &P {
+        assert!(row < self.num_rows(), "Row index out of range");
+        &self.column(col)[row]
+    }
+}
+
+impl<P: ValueType> IndexMut<(usize, usize)> for DenseMatrix<P> {
+    /// Get a mutable reference to the element at (row, column)
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut P {
+        assert!(row < self.num_rows(), "Row index out of range");
+        &mut self.column_mut(col)[row]
     }
 }
 
@@ -104,3 +287,6 @@ impl<P: ValueType> Drop for DenseMatrix<P> {
 	}
     }
 }
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,84 @@
+use super::*;
+
+#[test]
+fn zeros_and_identity() {
+    let a = DenseMatrix::<f64>::zeros(3, 2);
+    assert_eq!(a.num_rows(), 3);
+    assert_eq!(a.num_columns(), 2);
+    assert_eq!(a.leading_dimension(), 3);
+    assert_eq!(a.to_vec(), vec![0.0; 6]);
+
+    let i = DenseMatrix::<f32>::identity(3);
+    for row in 0..3 {
+        for col in 0..3 {
+            assert_eq!(i[(row, col)], if row == col { 1.0 } else { 0.0 });
+        }
+    }
+}
+
+#[test]
+fn row_major_construction() {
+    let a = DenseMatrix::from_row_major(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    assert_eq!(a.to_vec(), vec![1.0, 4.0, 2.0, 5.0, 3.0, 6.0]);
+    assert_eq!(a[(0, 2)], 3.0);
+    assert_eq!(a[(1, 0)], 4.0);
+    assert_eq!(a.column(1), &[2.0, 5.0]);
+}
+
+#[test]
+#[should_panic]
+fn invalid_row_major_construction() {
+    let _ = DenseMatrix::from_row_major(2, 3, vec![1.0, 2.0]);
+}
+
+#[test]
+fn index_mut_and_columns() {
+    let mut a = DenseMatrix::<f64>::zeros(2, 2);
+    a[(1, 0)] = 5.0;
+    a.column_mut(1)[0] = 7.0;
+    assert_eq!(a.column_major_values(), &[0.0, 5.0, 7.0, 0.0]);
+    a.set_column(0, &[1.0, 2.0]);
+    assert_eq!(a.to_vec(), vec![1.0, 2.0, 7.0, 0.0]);
+}
+
+#[test]
+#[should_panic]
+fn out_of_range_index() {
+    let a = DenseMatrix::<f64>::zeros(2, 2);
+    let _ = a[(2, 0)];
+}
+
+#[test]
+#[should_panic]
+fn set_column_wrong_length() {
+    let mut a = DenseMatrix::<f64>::zeros(2, 2);
+    a.set_column(0, &[1.0, 2.0, 3.0]);
+}
+
+#[test]
+fn leading_dimension_is_respected() {
+    // Build a 2x2 matrix stored with a leading dimension of 3
+    let mut values = vec![1.0, 2.0, -1.0, 3.0, 4.0, -1.0];
+    let super_matrix = unsafe {
+        let x = CSuperMatrix::alloc();
+        csuperlu_sys::dCreate_Dense_Matrix(
+            x.super_matrix() as *const csuperlu_sys::SuperMatrix as *mut csuperlu_sys::SuperMatrix,
+            2,
+            2,
+            values.as_mut_ptr(),
+            3,
+            csuperlu_sys::Stype_t_SLU_DN,
+            csuperlu_sys::Dtype_t_SLU_D,
+            csuperlu_sys::Mtype_t_SLU_GE,
+        );
+        std::mem::forget(values);
+        x
+    };
+    let mut a = unsafe { DenseMatrix::<f64>::from_super_matrix(super_matrix) };
+    assert_eq!(a.leading_dimension(), 3);
+    assert_eq!(a.column(1), &[3.0, 4.0]);
+    assert_eq!(a[(1, 1)], 4.0);
+    assert_eq!(a.to_vec(), vec![1.0, 2.0, 3.0, 4.0]);
+    a.set_column(0, &[5.0, 6.0]);
+    assert_eq!(a.column_major_values(), &[5.0, 6.0, -1.0, 3.0, 4.0]);
+}
@@ -0,0 +1,212 @@
+//! Display matrices without using the SuperLU print functions
+//!
+//! The SuperLU print functions (for example, `dPrint_CompCol_Matrix`)
+//! write directly to stdout, so their output cannot be captured or
+//! redirected. This module contains native Rust formatting for the
+//! matrix types in this crate, which is used by their
+//! [Display](std::fmt::Display) implementations.
+//!
+//! Matrices can be shown in two views:
+//!
+//! * A sparse view, which lists the stored elements as
+//!   `(row, column) = value`, in column-major order.
+//! * A dense view, which shows the matrix as a grid of values,
+//!   including the zeros.
+//!
+//! The `{}` format uses the natural view for the matrix type
+//! (sparse for sparse matrices, dense for dense matrices), and
+//! the alternate format `{:#}` uses the other view. Precision
+//! is taken from the format string (e.g. `{:.3}`). Large matrices
+//! are truncated. For more control, use the `display()` method
+//! on the matrix, which returns a [MatrixDisplay] that can be
+//! configured before formatting:
+//!
+//! ```ignore
+//! println!("{}", a.display().dense().precision(2).max_rows(10));
+//! ```
+//!
+
+use std::fmt;
+
+use crate::c::value_type::ValueType;
+
+/// Read access to the elements of a matrix, used to display it
+pub(crate) trait MatrixElements<P: ValueType> {
+    /// Get the number of rows in the matrix
+    fn num_rows(&self) -> usize;
+    /// Get the number of columns in the matrix
+    fn num_columns(&self) -> usize;
+    /// Get the number of stored elements in the matrix
+    fn num_stored(&self) -> usize;
+    /// Get the stored elements (row, column, value) in
+    /// column-major order
+    fn stored_elements(&self) -> Box<dyn Iterator<Item = (usize, usize, P)> + '_>;
+    /// Get the value of the element at (row, column)
+    fn element(&self, row: usize, col: usize) -> P;
+}
+
+impl<P: ValueType, M: MatrixElements<P>> MatrixElements<P> for &M {
+    fn num_rows(&self) -> usize {
+        (**self).num_rows()
+    }
+    fn num_columns(&self) -> usize {
+        (**self).num_columns()
+    }
+    fn num_stored(&self) -> usize {
+        (**self).num_stored()
+    }
+    fn stored_elements(&self) -> Box<dyn Iterator<Item = (usize, usize, P)> + '_> {
+        (**self).stored_elements()
+    }
+    fn element(&self, row: usize, col: usize) -> P {
+        (**self).element(row, col)
+    }
+}
+
+/// The way a matrix is laid out when it is displayed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DisplayView {
+    /// List the stored elements as `(row, column) = value`
+    Sparse,
+    /// Show the matrix as a grid of values, including zeros
+    Dense,
+}
+
+/// A configurable view of a matrix, which implements Display
+///
+/// Obtain a MatrixDisplay by calling `display()` on a matrix.
+///
+pub struct MatrixDisplay<'a, P: ValueType> {
+    matrix: Box<dyn MatrixElements<P> + 'a>,
+    view: DisplayView,
+    precision: Option<usize>,
+    max_rows: usize,
+    max_columns: usize,
+    max_elements: usize,
+}
+
+impl<'a, P: ValueType> MatrixDisplay<'a, P> {
+    pub(crate) fn new(matrix: impl MatrixElements<P> + 'a, view: DisplayView) -> Self {
+        Self {
+            matrix: Box::new(matrix),
+            view,
+            precision: None,
+            max_rows: 20,
+            max_columns: 10,
+            max_elements: 50,
+        }
+    }
+
+    /// Show the matrix as a grid of values
+    pub fn dense(mut self) -> Self {
+        self.view = DisplayView::Dense;
+        self
+    }
+
+    /// List the stored elements of the matrix
+    pub fn sparse(mut self) -> Self {
+        self.view = DisplayView::Sparse;
+        self
+    }
+
+    /// Set the number of digits after the decimal point. This
+    /// overrides any precision specified in the format string.
+    pub fn precision(mut self, precision: usize) -> Self {
+        self.precision = Some(precision);
+        self
+    }
+
+    /// Set the maximum number of rows shown in the dense view
+    pub fn max_rows(mut self, max_rows: usize) -> Self {
+        self.max_rows = max_rows;
+        self
+    }
+
+    /// Set the maximum number of columns shown in the dense view
+    pub fn max_columns(mut self, max_columns: usize) -> Self {
+        self.max_columns = max_columns;
+        self
+    }
+
+    /// Set the maximum number of elements listed in the sparse view
+    pub fn max_elements(mut self, max_elements: usize) -> Self {
+        self.max_elements = max_elements;
+        self
+    }
+
+    /// Set the view used for the `{}` format, and switch to the
+    /// other view for the alternate format `{:#}`
+    pub(crate) fn alternate(mut self, alternate: bool) -> Self {
+        if alternate {
+            self.view = match self.view {
+                DisplayView::Sparse => DisplayView::Dense,
+                DisplayView::Dense => DisplayView::Sparse,
+            };
+        }
+        self
+    }
+
+    fn format_value(&self, value: P, precision: Option<usize>) -> String {
+        match precision {
+            Some(precision) => format!("{value:.precision$}"),
+            None => format!("{value}"),
+        }
+    }
+
+    fn fmt_sparse(&self, f: &mut fmt::Formatter, precision: Option<usize>) -> fmt::Result {
+        let num_stored = self.matrix.num_stored();
+        writeln!(f, "{} x {} matrix, {} non-zero values",
+                 self.matrix.num_rows(), self.matrix.num_columns(), num_stored)?;
+        for (row, col, value) in self.matrix.stored_elements().take(self.max_elements) {
+            writeln!(f, "({}, {}) = {}", row, col, self.format_value(value, precision))?;
+        }
+        if num_stored > self.max_elements {
+            writeln!(f, "... ({} more)", num_stored - self.max_elements)?;
+        }
+        Ok(())
+    }
+
+    fn fmt_dense(&self, f: &mut fmt::Formatter, precision: Option<usize>) -> fmt::Result {
+        let num_rows = self.matrix.num_rows();
+        let num_columns = self.matrix.num_columns();
+        writeln!(f, "{num_rows} x {num_columns} matrix")?;
+        let shown_rows = num_rows.min(self.max_rows);
+        let shown_columns = num_columns.min(self.max_columns);
+        let mut cells = Vec::with_capacity(shown_rows * shown_columns);
+        for row in 0..shown_rows {
+            for col in 0..shown_columns {
+                cells.push(self.format_value(self.matrix.element(row, col), precision));
+            }
+        }
+        let width = cells.iter().map(|cell| cell.chars().count()).max().unwrap_or(0);
+        for row in 0..shown_rows {
+            let line = cells[row * shown_columns..][..shown_columns]
+                .iter()
+                .map(|cell| format!("{cell:>width$}"))
+                .collect::<Vec<_>>()
+                .join(" ");
+            if shown_columns < num_columns {
+                writeln!(f, "{line} ...")?;
+            } else {
+                writeln!(f, "{line}")?;
+            }
+        }
+        if shown_rows < num_rows {
+            writeln!(f, "... ({} more rows)", num_rows - shown_rows)?;
+        }
+        Ok(())
+    }
+}
+
+impl<P: ValueType> fmt::Display for MatrixDisplay<'_, P> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let precision = self.precision.or(f.precision());
+        match self.view {
+            DisplayView::Sparse => self.fmt_sparse(f, precision),
+            DisplayView::Dense => self.fmt_dense(f, precision),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,72 @@
+use crate::dense::DenseMatrix;
+use crate::test_utils::{comp_col_3x3, lu_decomp_3x3};
+
+#[test]
+fn comp_col_sparse_view() {
+    let a = comp_col_3x3();
+    let expected = "3 x 3 matrix, 5 non-zero values\n\
+                    (0, 0) = 1\n(2, 0) = 4\n(1, 1) = 3\n(0, 2) = 2\n(2, 2) = 5\n";
+    assert_eq!(format!("{a}"), expected);
+}
+
+#[test]
+fn comp_col_dense_view() {
+    let a = comp_col_3x3();
+    let expected = "3 x 3 matrix\n\
+                    1.0 0.0 2.0\n\
+                    0.0 3.0 0.0\n\
+                    4.0 0.0 5.0\n";
+    assert_eq!(format!("{a:#.1}"), expected);
+    assert_eq!(format!("{}", a.display().dense().precision(1)), expected);
+}
+
+#[test]
+fn dense_view_alignment() {
+    let b = DenseMatrix::from_row_major(2, 2, vec![1.0, -20.5, 300.0, 4.0]);
+    let expected = "2 x 2 matrix\n    1 -20.5\n  300     4\n";
+    assert_eq!(format!("{b}"), expected);
+    let expected = "2 x 2 matrix, 4 non-zero values\n\
+                    (0, 0) = 1.00\n(1, 0) = 300.00\n(0, 1) = -20.50\n(1, 1) = 4.00\n";
+    assert_eq!(format!("{b:#.2}"), expected);
+}
+
+#[test]
+fn truncation() {
+    let b = DenseMatrix::<f64>::identity(4);
+    let expected = "4 x 4 matrix\n1 0 ...\n0 1 ...\n0 0 ...\n... (1 more rows)\n";
+    assert_eq!(format!("{}", b.display().max_rows(3).max_columns(2)), expected);
+
+    let a = comp_col_3x3();
+    let expected = "3 x 3 matrix, 5 non-zero values\n(0, 0) = 1\n(2, 0) = 4\n... (3 more)\n";
+    assert_eq!(format!("{}", a.display().max_elements(2)), expected);
+}
+
+#[test]
+fn super_node_matrix() {
+    let lu = lu_decomp_3x3();
+    let l = lu.l();
+    assert_eq!(l.num_super_nodes(), 2);
+    assert_eq!(l.super_node_columns(1), 1..3);
+    assert_eq!(l.super_node_of_column(2), 1);
+    assert_eq!(l.column(2), (&[1, 2][..], &[0.0, -3.0][..]));
+    assert_eq!(l.value(2, 0), 4.0);
+    assert_eq!(l.value(1, 0), 0.0);
+    assert_eq!(format!("{}", l.display().max_elements(1)),
+               "3 x 3 matrix, 6 non-zero values\n(0, 0) = 1\n... (5 more)\n");
+}
+
+#[test]
+fn lu_factors() {
+    let lu = lu_decomp_3x3();
+    let l = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [4.0, 0.0, 1.0]];
+    let u = [[1.0, 0.0, 2.0], [0.0, 3.0, 0.0], [0.0, 0.0, -3.0]];
+    for row in 0..3 {
+        for col in 0..3 {
+            assert_eq!(lu.l_value(row, col), l[row][col]);
+            assert_eq!(lu.u_value(row, col), u[row][col]);
+        }
+    }
+    let expected = "L = 3 x 3 matrix\n1 0 0\n0 1 0\n4 0 1\n\
+                    U = 3 x 3 matrix\n 1  0  2\n 0  3  0\n 0  0 -3\n";
+    assert_eq!(format!("{lu:#}"), expected);
+}
@@ -0,0 +1,189 @@
+//! Row and column equilibration of a compressed-column matrix
+//!
+//! A badly scaled matrix $A$ (for example, a circuit matrix with
+//! conductances of very different sizes) can be replaced by the
+//! equilibrated matrix $D_rAD_c$, where $D_r$ and $D_c$ are
+//! diagonal scaling matrices chosen so that the largest element
+//! in each row and column has magnitude close to one. The
+//! scalings are computed and applied using the SuperLU *gsequ
+//! and *laqgs routines, by
+//! [CompColMatrix::equilibrate](crate::comp_col::CompColMatrix::equilibrate).
+//!
+//! The system $AX = B$ is then solved as
+//! $(D_rAD_c)Y = D_rB$, and the solution is $X = D_cY$. The
+//! [Equilibration] returned when the matrix is scaled has
+//! helpers for both steps, which can be used with any driver:
+//!
+//! ```ignore
+//! let equilibration = a.equilibrate()?;
+//! equilibration.scale_rhs(&mut b);
+//! let mut x = SimpleSystem { a, b }.solve(&mut stat, policy)?.x;
+//! equilibration.unscale_solution(&mut x);
+//! ```
+//!
+//! SuperLU only applies a scaling if it is worthwhile (if the
+//! ratio of the smallest to the largest scaling factor is below
+//! 0.1, or the matrix elements are close to underflow or
+//! overflow), so the matrix may be scaled by rows, by columns,
+//! by both, or not at all. The [Equilibrated] marker records
+//! which scalings were applied.
+//!
+
+use std::fmt;
+
+use crate::c::value_type::{CEquilibration, Error, ValueType};
+use crate::dense::DenseMatrix;
+
+/// The scalings that were applied to the matrix
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Equilibrated {
+    /// No scaling was applied
+    None,
+    /// The rows were scaled ($D_rA$)
+    Row,
+    /// The columns were scaled ($AD_c$)
+    Column,
+    /// The rows and the columns were scaled ($D_rAD_c$)
+    Both,
+}
+
+impl Equilibrated {
+    /// Get the marker from the SuperLU equed character
+    pub(crate) fn from_c_equed(equed: u8) -> Self {
+        match equed {
+            b'R' => Self::Row,
+            b'C' => Self::Column,
+            b'B' => Self::Both,
+            _ => Self::None,
+        }
+    }
+
+    /// Check whether the rows were scaled
+    pub fn rows_scaled(&self) -> bool {
+        matches!(self, Self::Row | Self::Both)
+    }
+
+    /// Check whether the columns were scaled
+    pub fn columns_scaled(&self) -> bool {
+        matches!(self, Self::Column | Self::Both)
+    }
+}
+
+/// Errors that can occur when equilibrating a matrix
+#[derive(Debug)]
+pub enum EquilibrationError {
+    /// The row is exactly zero, so the matrix is singular
+    ZeroRow { row: usize },
+    /// The column is exactly zero, so the matrix is singular
+    ZeroColumn { col: usize },
+    /// SuperLU reported an invalid argument
+    Other(Error),
+}
+
+impl EquilibrationError {
+    /// Get the error from the non-zero info value returned by
+    /// *gsequ
+    pub(crate) fn from_c_info(info: i32, num_rows: usize) -> Self {
+        if info < 0 {
+            return Self::Other(Error::UnknownError);
+        }
+        let info = info as usize;
+        if info > num_rows {
+            Self::ZeroColumn { col: info - num_rows - 1 }
+        } else {
+            Self::ZeroRow { row: info - 1 }
+        }
+    }
+}
+
+impl std::error::Error for EquilibrationError {}
+
+impl fmt::Display for EquilibrationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::ZeroRow { row } => write!(f, "Cannot equilibrate: row {row} is exactly zero"),
+            Self::ZeroColumn { col } => write!(f, "Cannot equilibrate: column {col} is exactly zero"),
+            Self::Other(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+/// The scalings computed (and possibly applied) by
+/// [CompColMatrix::equilibrate](crate::comp_col::CompColMatrix::equilibrate)
+#[derive(Debug, Clone)]
+pub struct Equilibration {
+    /// The row scale factors (the diagonal of $D_r$)
+    pub row_scale: Vec<f64>,
+    /// The column scale factors (the diagonal of $D_c$)
+    pub column_scale: Vec<f64>,
+    /// The ratio of the smallest to the largest row scale factor
+    pub rowcnd: f64,
+    /// The ratio of the smallest to the largest column scale
+    /// factor
+    pub colcnd: f64,
+    /// The largest absolute value of the elements in the matrix
+    /// (before scaling)
+    pub amax: f64,
+    /// The scalings that were applied to the matrix
+    pub equilibrated: Equilibrated,
+}
+
+impl Equilibration {
+    /// Make the scalings from the values returned by
+    /// c_equilibrate
+    pub(crate) fn from_c_equilibration(equilibration: CEquilibration) -> Self {
+        let CEquilibration { row_scale, column_scale, rowcnd, colcnd, amax, equed } = equilibration;
+        Self {
+            row_scale,
+            column_scale,
+            rowcnd,
+            colcnd,
+            amax,
+            equilibrated: Equilibrated::from_c_equed(equed),
+        }
+    }
+
+    /// Scale the right-hand side $B$ by $D_r$ (if the rows of
+    /// the matrix were scaled)
+    ///
+    /// # Panics
+    ///
+    /// If the number of rows of b does not match the matrix.
+    ///
+    pub fn scale_rhs<P: ValueType>(&self, b: &mut DenseMatrix<P>) {
+        if self.equilibrated.rows_scaled() {
+            scale_rows(b, &self.row_scale);
+        }
+    }
+
+    /// Unscale the solution $Y$ of the equilibrated system by
+    /// $D_c$ (if the columns of the matrix were scaled), so that
+    /// it becomes the solution $X$ of the original system
+    ///
+    /// # Panics
+    ///
+    /// If the number of rows of x does not match the number of
+    /// columns of the matrix.
+    ///
+    pub fn unscale_solution<P: ValueType>(&self, x: &mut DenseMatrix<P>) {
+        if self.equilibrated.columns_scaled() {
+            scale_rows(x, &self.column_scale);
+        }
+    }
+}
+
+/// Multiply each row of a dense matrix by the scale factor
+fn scale_rows<P: ValueType>(x: &mut DenseMatrix<P>, scale: &[f64]) {
+    assert!(
+        x.num_rows() == scale.len(),
+        "Number of rows does not match the number of scale factors"
+    );
+    for col in 0..x.num_columns() {
+        for (value, &factor) in x.column_mut(col).iter_mut().zip(scale) {
+            *value = *value * P::from_real(factor);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,75 @@
+use super::*;
+use crate::c::options::ColumnPermPolicy;
+use crate::c::stat::CSuperluStat;
+use crate::comp_col::CompColMatrix;
+use crate::simple_driver::SimpleSystem;
+
+/// A badly scaled matrix (rows scaled by 1e6 and 1e-6)
+///
+///  2e6  1e6  0
+///  1    3    1
+///  0   1e-6 4e-6
+fn badly_scaled() -> CompColMatrix<f64> {
+    CompColMatrix::from_vectors(
+        3,
+        vec![2e6, 1.0, 1e6, 3.0, 1e-6, 1.0, 4e-6],
+        vec![0, 1, 0, 1, 2, 1, 2],
+        vec![0, 2, 5, 7],
+    )
+}
+
+#[test]
+fn equilibrate_scales_rows() {
+    let mut a = badly_scaled();
+    let equilibration = a.equilibrate().unwrap();
+    assert!(equilibration.equilibrated.rows_scaled());
+    assert_eq!(equilibration.amax, 2e6);
+    assert!(equilibration.rowcnd < 0.1);
+    // The largest element in each row of the scaled matrix is one
+    let mut row_max = [0.0f64; 3];
+    for (&row, value) in a.row_indices().iter().zip(a.non_zero_values()) {
+        row_max[row as usize] = row_max[row as usize].max(value.abs());
+    }
+    for max in row_max {
+        assert!(max <= 1.0 + 1e-12 && max > 0.1);
+    }
+}
+
+#[test]
+fn equilibrated_solve_matches_original() {
+    let x_expected = vec![1.0, -2.0, 3.0];
+    let b_values = vec![0.0, -2.0, 1e-5];
+    let mut stat = CSuperluStat::new();
+
+    let mut a = badly_scaled();
+    let equilibration = a.equilibrate().unwrap();
+    let mut b = DenseMatrix::from_vectors(3, 1, b_values);
+    equilibration.scale_rhs(&mut b);
+    let mut x = SimpleSystem { a, b }
+        .solve(&mut stat, ColumnPermPolicy::Natural)
+        .unwrap()
+        .x;
+    equilibration.unscale_solution(&mut x);
+    for (value, expected) in x.column(0).iter().zip(&x_expected) {
+        assert!((value - expected).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn well_scaled_matrix_is_not_scaled() {
+    let mut a = CompColMatrix::from_vectors(2, vec![1.0, 0.5, 0.5, 1.0], vec![0, 1, 0, 1], vec![0, 2, 4]);
+    let equilibration = a.equilibrate().unwrap();
+    assert_eq!(equilibration.equilibrated, Equilibrated::None);
+    assert_eq!(a.non_zero_values(), &[1.0, 0.5, 0.5, 1.0]);
+    let mut b = DenseMatrix::from_vectors(2, 1, vec![3.0, 4.0]);
+    equilibration.scale_rhs(&mut b);
+    assert_eq!(b.column(0), &[3.0, 4.0]);
+}
+
+#[test]
+fn zero_row_and_column_are_reported() {
+    let mut a = CompColMatrix::from_vectors(2, vec![1.0, 2.0], vec![0, 0], vec![0, 1, 2]);
+    assert!(matches!(a.equilibrate(), Err(EquilibrationError::ZeroRow { row: 1 })));
+    let mut a = CompColMatrix::from_vectors(2, vec![1.0, 2.0], vec![0, 1], vec![0, 2, 2]);
+    assert!(matches!(a.equilibrate(), Err(EquilibrationError::ZeroColumn { col: 1 })));
+}
@@ -0,0 +1,272 @@
+//! Reuse an $LU$ factorisation to solve further systems
+//!
+//! The drivers compute the factorisation $P_rAP_c = LU$ while
+//! solving $AX = B$. A [Factorization] keeps the factors and the
+//! permutations together, so that they can be used to solve for
+//! new right-hand sides (using the SuperLU *gstrs routines)
+//! without factoring $A$ again.
+//!
+//! The factorisation can also be used to compute selected
+//! entries of $A^{-1}$, including its diagonal, without forming
+//! the (usually dense) inverse. Column $j$ of $A^{-1}$ is the
+//! solution of $Ax = e_j$, so the entries are found by solving
+//! for the unit vectors of the columns that are needed. The
+//! unit vectors are solved in batches, so that only a
+//! $n \times n_\text{batch}$ dense matrix is stored at once.
+//!
+//! The reciprocal condition number of $A$ can be estimated from
+//! the factors (using the SuperLU *gscon routines). The norm of
+//! $A$ that the estimate needs is computed when the factorisation
+//! is created. A small reciprocal condition number (close to the
+//! machine precision) means that solutions computed using the
+//! factorisation may be inaccurate.
+//!
+
+use std::collections::BTreeMap;
+
+use crate::c::options::ColumnPermPolicy;
+use crate::c::stat::CSuperluStat;
+use crate::c::value_type::{Error, ValueType};
+use crate::comp_col::CompColMatrix;
+use crate::dense::DenseMatrix;
+use crate::lu_decomp::LUDecomp;
+use crate::simple_driver::{ColumnPerm, RowPerm, SimpleError, SimpleSolution, SimpleSystem};
+
+/// The default number of right-hand sides solved at once when
+/// computing entries of the inverse
+pub const DEFAULT_BATCH_SIZE: usize = 64;
+
+/// The matrix norm used for the condition number
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Norm {
+    /// The 1-norm (maximum absolute column sum)
+    One,
+    /// The infinity-norm (maximum absolute row sum)
+    Infinity,
+}
+
+impl Norm {
+    fn c_norm(&self) -> &'static str {
+        match self {
+            Self::One => "1",
+            Self::Infinity => "I",
+        }
+    }
+}
+
+/// An $LU$ factorisation $P_rAP_c = LU$ of a square matrix $A$
+pub struct Factorization<P: ValueType> {
+    /// The factors $L$ and $U$
+    pub lu: LUDecomp<P>,
+    /// The column permutation $P_c$
+    pub column_perm: ColumnPerm,
+    /// The row permutation $P_r$
+    pub row_perm: RowPerm,
+    norm_one: f64,
+    norm_inf: f64,
+}
+
+impl<P: ValueType> From<SimpleSolution<P>> for Factorization<P> {
+    /// Keep the factorisation from a solution (the matrix $A$ and
+    /// the solution $X$ are dropped)
+    fn from(solution: SimpleSolution<P>) -> Self {
+        let SimpleSolution { a, lu, column_perm, row_perm, .. } = solution;
+        Self::from_parts(&a, lu, column_perm, row_perm)
+    }
+}
+
+impl<P: ValueType> Factorization<P> {
+    /// Factor a square matrix using the simple driver
+    ///
+    /// The simple driver always solves a system while factoring,
+    /// so a single zero right-hand side is used. The matrix is
+    /// returned along with the factorisation.
+    ///
+    /// # Errors
+    ///
+    /// Errors from the simple driver (for example, if $A$ is
+    /// singular) are returned.
+    ///
+    pub fn new(
+        a: CompColMatrix<P>,
+        stat: &mut CSuperluStat,
+        column_perm_policy: ColumnPermPolicy,
+    ) -> Result<(CompColMatrix<P>, Self), SimpleError<P>> {
+        let b = DenseMatrix::zeros(a.num_rows(), 1);
+        let solution = SimpleSystem { a, b }.solve(stat, column_perm_policy)?;
+        let SimpleSolution { a, lu, column_perm, row_perm, .. } = solution;
+        let factorization = Self::from_parts(&a, lu, column_perm, row_perm);
+        Ok((a, factorization))
+    }
+
+    pub(crate) fn from_parts(a: &CompColMatrix<P>, lu: LUDecomp<P>, column_perm: ColumnPerm, row_perm: RowPerm) -> Self {
+        Self {
+            lu,
+            column_perm,
+            row_perm,
+            norm_one: a.norm_one(),
+            norm_inf: a.norm_inf(),
+        }
+    }
+
+    /// Get the number of rows (and columns) in $A$
+    pub fn size(&self) -> usize {
+        self.lu.num_rows()
+    }
+
+    /// Solve $AX = B$ using the factorisation, returning $X$
+    ///
+    /// # Errors
+    ///
+    /// If SuperLU reports an invalid argument, an error is
+    /// returned.
+    ///
+    /// # Panics
+    ///
+    /// If the number of rows of b does not match $A$.
+    ///
+    pub fn solve(&self, b: DenseMatrix<P>, stat: &mut CSuperluStat) -> Result<DenseMatrix<P>, Error> {
+        assert!(
+            b.num_rows() == self.size(),
+            "Number of rows in the right-hand side does not match the matrix"
+        );
+        unsafe {
+            let mut b_super_matrix = b.into_super_matrix();
+            let result = P::c_solve_factored(
+                self.lu.l().super_matrix(),
+                self.lu.u().super_matrix(),
+                self.column_perm.as_slice(),
+                self.row_perm.as_slice(),
+                &mut b_super_matrix,
+                stat,
+            );
+            let x = DenseMatrix::from_super_matrix(b_super_matrix);
+            result.map(|_| x)
+        }
+    }
+
+    /// Estimate the reciprocal condition number of $A$ in the
+    /// given norm, $1 / (\lVert A \rVert \lVert A^{-1} \rVert)$
+    ///
+    /// The estimate is between zero and one. Zero means that $A$
+    /// is singular to working precision, and values close to the
+    /// machine precision of P mean that $A$ is ill-conditioned.
+    ///
+    /// # Errors
+    ///
+    /// If SuperLU reports an invalid argument, an error is
+    /// returned.
+    ///
+    pub fn rcond(&self, norm: Norm, stat: &mut CSuperluStat) -> Result<f64, Error> {
+        let anorm = match norm {
+            Norm::One => self.norm_one,
+            Norm::Infinity => self.norm_inf,
+        };
+        unsafe {
+            P::c_estimate_rcond(
+                norm.c_norm(),
+                self.lu.l().super_matrix(),
+                self.lu.u().super_matrix(),
+                anorm,
+                stat,
+            )
+        }
+    }
+
+    /// Compute the reciprocal pivot growth of the factorisation
+    /// (see [LUDecomp::reciprocal_pivot_growth]). The matrix a
+    /// must be the matrix that was factored.
+    ///
+    /// # Panics
+    ///
+    /// If the size of a does not match the factorisation.
+    ///
+    pub fn reciprocal_pivot_growth(&self, a: &CompColMatrix<P>) -> f64 {
+        self.lu.reciprocal_pivot_growth(a, &self.column_perm, self.size())
+    }
+
+    /// Solve for the columns of $A^{-1}$ with the given indices,
+    /// in batches, calling f(column, values) for each column
+    fn for_each_inverse_column(
+        &self,
+        columns: &[usize],
+        batch_size: usize,
+        stat: &mut CSuperluStat,
+        mut f: impl FnMut(usize, &[P]),
+    ) -> Result<(), Error> {
+        let size = self.size();
+        for batch in columns.chunks(batch_size.max(1)) {
+            let mut b = DenseMatrix::zeros(size, batch.len());
+            for (k, &col) in batch.iter().enumerate() {
+                b[(col, k)] = P::one();
+            }
+            let x = self.solve(b, stat)?;
+            for (k, &col) in batch.iter().enumerate() {
+                f(col, x.column(k));
+            }
+        }
+        Ok(())
+    }
+
+    /// Compute selected entries (row, column) of $A^{-1}$
+    ///
+    /// The values are returned in the same order as the entries.
+    /// One column of $A^{-1}$ is computed for each distinct column
+    /// index in the entries, and the columns are solved
+    /// batch_size at a time.
+    ///
+    /// # Errors
+    ///
+    /// If SuperLU reports an invalid argument, an error is
+    /// returned.
+    ///
+    /// # Panics
+    ///
+    /// If any row or column index is out of range.
+    ///
+    pub fn inverse_entries(
+        &self,
+        entries: &[(usize, usize)],
+        batch_size: usize,
+        stat: &mut CSuperluStat,
+    ) -> Result<Vec<P>, Error> {
+        let size = self.size();
+        // Map each needed column to the entries in that column
+        let mut columns = BTreeMap::<usize, Vec<usize>>::new();
+        for (n, &(row, col)) in entries.iter().enumerate() {
+            assert!(row < size && col < size, "Entry ({row}, {col}) out of range");
+            columns.entry(col).or_default().push(n);
+        }
+        let column_indices: Vec<usize> = columns.keys().copied().collect();
+        let mut values = vec![P::zero(); entries.len()];
+        self.for_each_inverse_column(&column_indices, batch_size, stat, |col, x| {
+            for &n in &columns[&col] {
+                values[n] = x[entries[n].0];
+            }
+        })?;
+        Ok(values)
+    }
+
+    /// Compute the diagonal of $A^{-1}$
+    ///
+    /// Every column of $A^{-1}$ is computed (batch_size columns at
+    /// a time), but only the diagonal entries are stored.
+    ///
+    /// # Errors
+    ///
+    /// If SuperLU reports an invalid argument, an error is
+    /// returned.
+    ///
+    pub fn inverse_diagonal(&self, batch_size: usize, stat: &mut CSuperluStat) -> Result<Vec<P>, Error> {
+        let size = self.size();
+        let columns: Vec<usize> = (0..size).collect();
+        let mut diagonal = vec![P::zero(); size];
+        self.for_each_inverse_column(&columns, batch_size, stat, |col, x| {
+            diagonal[col] = x[col];
+        })?;
+        Ok(diagonal)
+    }
+}
+
+#[cfg(test)]
+mod tests;
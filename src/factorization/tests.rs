@@ -0,0 +1,156 @@
+use super::*;
+use crate::test_utils::comp_col;
+
+/// A 6x6 unsymmetric sparse test matrix, as dense rows
+fn dense_rows() -> Vec<Vec<f64>> {
+    vec![
+        vec![4.0, 0.0, 1.0, 0.0, 0.0, 2.0],
+        vec![1.0, 5.0, 0.0, 0.0, 1.0, 0.0],
+        vec![0.0, 2.0, 6.0, 1.0, 0.0, 0.0],
+        vec![0.0, 0.0, 1.0, 3.0, 0.0, 1.0],
+        vec![3.0, 0.0, 0.0, 1.0, 7.0, 0.0],
+        vec![0.0, 1.0, 0.0, 0.0, 2.0, 5.0],
+    ]
+}
+
+/// Invert a dense matrix using Gauss-Jordan elimination with
+/// partial pivoting
+fn dense_inverse(rows: &[Vec<f64>]) -> Vec<Vec<f64>> {
+    let n = rows.len();
+    let mut a: Vec<Vec<f64>> = rows.to_vec();
+    let mut inv: Vec<Vec<f64>> = (0..n)
+        .map(|i| (0..n).map(|j| if i == j { 1.0 } else { 0.0 }).collect())
+        .collect();
+    for k in 0..n {
+        let p = (k..n).max_by(|&i, &j| a[i][k].abs().total_cmp(&a[j][k].abs())).unwrap();
+        a.swap(k, p);
+        inv.swap(k, p);
+        let pivot = a[k][k];
+        for j in 0..n {
+            a[k][j] /= pivot;
+            inv[k][j] /= pivot;
+        }
+        for i in (0..n).filter(|&i| i != k) {
+            let factor = a[i][k];
+            for j in 0..n {
+                a[i][j] -= factor * a[k][j];
+                inv[i][j] -= factor * inv[k][j];
+            }
+        }
+    }
+    inv
+}
+
+#[test]
+fn inverse_diagonal_matches_dense_inverse() {
+    let rows = dense_rows();
+    let inv = dense_inverse(&rows);
+    let mut stat = CSuperluStat::new();
+    let (_, factorization) = Factorization::new(comp_col(&rows), &mut stat, ColumnPermPolicy::ColAMD).unwrap();
+    for batch_size in [1, 4, DEFAULT_BATCH_SIZE] {
+        let diagonal = factorization.inverse_diagonal(batch_size, &mut stat).unwrap();
+        for (n, value) in diagonal.iter().enumerate() {
+            assert!((value - inv[n][n]).abs() < 1e-12);
+        }
+    }
+}
+
+#[test]
+fn inverse_entries_match_dense_inverse() {
+    let rows = dense_rows();
+    let inv = dense_inverse(&rows);
+    let mut stat = CSuperluStat::new();
+    let (_, factorization) = Factorization::new(comp_col(&rows), &mut stat, ColumnPermPolicy::Natural).unwrap();
+    let entries = [(0, 5), (3, 2), (5, 0), (0, 5), (2, 2), (4, 1)];
+    let values = factorization.inverse_entries(&entries, 2, &mut stat).unwrap();
+    assert_eq!(values.len(), entries.len());
+    for (&(row, col), value) in entries.iter().zip(values) {
+        assert!((value - inv[row][col]).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn solve_reuses_factorization() {
+    let rows = dense_rows();
+    let mut stat = CSuperluStat::new();
+    let b = DenseMatrix::from_vectors(6, 1, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    let solution = SimpleSystem { a: comp_col(&rows), b }
+        .solve(&mut stat, ColumnPermPolicy::ColAMD)
+        .unwrap();
+    let x_simple = solution.x.to_vec();
+    let factorization = Factorization::from(solution);
+    let b = DenseMatrix::from_vectors(6, 2, [1.0, 2.0, 3.0, 4.0, 5.0, 6.0].repeat(2));
+    let x = factorization.solve(b, &mut stat).unwrap();
+    for col in 0..2 {
+        for (value, expected) in x.column(col).iter().zip(&x_simple) {
+            assert!((value - expected).abs() < 1e-12);
+        }
+    }
+}
+
+#[test]
+fn rcond_matches_dense_inverse() {
+    let rows = dense_rows();
+    let inv = dense_inverse(&rows);
+    let a = comp_col(&rows);
+    let inv_norm_one = (0..6).map(|j| (0..6).map(|i| inv[i][j].abs()).sum::<f64>()).fold(0.0, f64::max);
+    let inv_norm_inf = (0..6).map(|i| inv[i].iter().map(|v| v.abs()).sum::<f64>()).fold(0.0, f64::max);
+    let expected_one = 1.0 / (a.norm_one() * inv_norm_one);
+    let expected_inf = 1.0 / (a.norm_inf() * inv_norm_inf);
+    let mut stat = CSuperluStat::new();
+    let (_, factorization) = Factorization::new(a, &mut stat, ColumnPermPolicy::ColAMD).unwrap();
+    // The estimate of the norm of the inverse is a lower bound,
+    // which is usually exact for small matrices
+    let rcond_one = factorization.rcond(Norm::One, &mut stat).unwrap();
+    let rcond_inf = factorization.rcond(Norm::Infinity, &mut stat).unwrap();
+    assert!(rcond_one >= expected_one * (1.0 - 1e-12) && rcond_one < 3.0 * expected_one);
+    assert!(rcond_inf >= expected_inf * (1.0 - 1e-12) && rcond_inf < 3.0 * expected_inf);
+}
+
+#[test]
+fn rcond_detects_ill_conditioning() {
+    let epsilon = 1e-10;
+    let rows = vec![vec![1.0, 1.0], vec![1.0, 1.0 + epsilon]];
+    let mut stat = CSuperluStat::new();
+    let (_, factorization) = Factorization::new(comp_col(&rows), &mut stat, ColumnPermPolicy::Natural).unwrap();
+    assert!(factorization.rcond(Norm::One, &mut stat).unwrap() < 1e-9);
+}
+
+#[test]
+fn reciprocal_pivot_growth_matches_factors() {
+    let rows = dense_rows();
+    let mut stat = CSuperluStat::new();
+    let (a, factorization) = Factorization::new(comp_col(&rows), &mut stat, ColumnPermPolicy::ColAMD).unwrap();
+    let perm_c = factorization.column_perm.as_slice();
+    let mut expected = f64::MAX;
+    for col in 0..6 {
+        // Column perm_c[col] of AP_c is column col of A
+        let max_a = rows.iter().map(|row| row[col].abs()).fold(0.0, f64::max);
+        let max_u = factorization
+            .lu
+            .u_column(perm_c[col] as usize)
+            .map(|(_, value)| value.abs())
+            .fold(0.0, f64::max);
+        expected = expected.min(max_a / max_u);
+    }
+    let rpg = factorization.reciprocal_pivot_growth(&a);
+    assert!((rpg - expected).abs() < 1e-12 * expected);
+    assert!(rpg > 0.0 && rpg <= 1.0);
+}
+
+#[test]
+fn reciprocal_pivot_growth_of_singular_matrix() {
+    let rows = vec![
+        vec![2.0, 1.0, 0.0],
+        vec![4.0, 2.0, 0.0],
+        vec![0.0, 0.0, 1.0],
+    ];
+    let mut stat = CSuperluStat::new();
+    let result = Factorization::new(comp_col(&rows), &mut stat, ColumnPermPolicy::Natural);
+    let Err(err) = result else {
+        panic!("Expected the matrix to be singular");
+    };
+    assert!(matches!(err, SimpleError::Singular { singular_column: 1, .. }));
+    let rpg = err.reciprocal_pivot_growth().unwrap();
+    assert!(rpg > 0.0 && rpg.is_finite());
+}
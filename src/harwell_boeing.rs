@@ -292,11 +292,6 @@ impl<P: Num + FromStr> HarwellBoeingMatrix<P> {
         let row_indices = parse_vector(&mut lines, num_row_index_lines, 5, parse_offset);
         let non_zero_values = parse_vector(&mut lines, num_values_lines, 15, parse_float);
 
-        println!("{:?}", header);
-        println!("non zero values = {}", non_zero_values.len());
-        println!("row indices = {}", row_indices.len());
-        println!("col pointers = {}", column_offsets.len());
-
         Self {
             header,
             column_offsets,
@@ -0,0 +1,84 @@
+//! Conversions to and from other Rust linear algebra crates
+//!
+//! Each submodule in this module is enabled by a cargo feature
+//! with the same name as the crate it converts to and from. For
+//! example, to convert between `sprs::CsMat` and
+//! [CompColMatrix](crate::comp_col::CompColMatrix), enable the
+//! `sprs` feature.
+//!
+//! SuperLU stores row indices and column offsets as 32-bit
+//! signed integers, whereas most Rust crates use `usize`. All
+//! conversions into csuperlu types check that every index fits
+//! into an `i32`, and return a [ConversionError] if it does not.
+//!
+
+use std::fmt;
+
+use crate::c::value_type::ValueType;
+use crate::simple_driver::SimpleError;
+
+#[cfg(feature = "sprs")]
+pub mod sprs;
+
+#[cfg(feature = "nalgebra")]
+pub mod nalgebra;
+
+#[cfg(feature = "ndarray")]
+pub mod ndarray;
+
+/// Errors that can occur when converting matrices between
+/// csuperlu and another crate.
+#[derive(Debug, PartialEq)]
+pub enum ConversionError {
+    /// An index (row index, column index or column offset) is
+    /// too large to be stored in the 32-bit integers used by
+    /// SuperLU.
+    IndexOverflow { index: usize },
+    /// The matrix dimensions are not compatible with the
+    /// requested conversion or operation.
+    DimensionMismatch,
+    /// The matrix does not have a valid compressed structure
+    /// (for example, the row indices in a column are not sorted).
+    InvalidStructure(String),
+}
+
+impl std::error::Error for ConversionError {}
+
+impl fmt::Display for ConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::IndexOverflow { index } =>
+                write!(f, "Index {index} does not fit into a SuperLU (32-bit) index"),
+            Self::DimensionMismatch => write!(f, "Incompatible matrix dimensions"),
+            Self::InvalidStructure(reason) => write!(f, "Invalid matrix structure: {reason}"),
+        }
+    }
+}
+
+/// Errors returned by the solve helpers, which convert the input
+/// matrices and then call the simple driver.
+#[derive(Debug)]
+pub enum SolveError<P: ValueType> {
+    /// The input matrices could not be converted
+    Conversion(ConversionError),
+    /// The simple driver returned an error
+    Solver(SimpleError<P>),
+}
+
+impl<P: ValueType> From<ConversionError> for SolveError<P> {
+    fn from(err: ConversionError) -> Self {
+        Self::Conversion(err)
+    }
+}
+
+impl<P: ValueType> From<SimpleError<P>> for SolveError<P> {
+    fn from(err: SimpleError<P>) -> Self {
+        Self::Solver(err)
+    }
+}
+
+/// Convert an index into a SuperLU index, checking that it fits
+#[cfg(any(feature = "sprs", feature = "nalgebra"))]
+pub(crate) fn checked_index(index: usize) -> Result<i32, ConversionError> {
+    i32::try_from(index).map_err(|_| ConversionError::IndexOverflow { index })
+}
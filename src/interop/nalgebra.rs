@@ -0,0 +1,193 @@
+//! Conversions between csuperlu and [nalgebra](https://nalgebra.org)
+//!
+//! This module is enabled by the `nalgebra` cargo feature. It
+//! provides conversions between the sparse matrix types of
+//! `nalgebra_sparse` (`CscMatrix` and `CsrMatrix`) and
+//! [CompColMatrix], and between the dense `nalgebra` types
+//! (`DMatrix` and `DVector`) and [DenseMatrix]. The conversions
+//! are available for all four [ValueType]s, including the complex
+//! types (`nalgebra` uses the same `num::Complex` type as csuperlu).
+//!
+//! The functions [solve_vector] and [solve_matrix] solve $AX = B$
+//! for a `nalgebra_sparse` matrix $A$ using the simple driver, and
+//! return the solution as a `nalgebra` vector or matrix.
+//!
+
+use ::nalgebra::{DMatrix, DVector, Scalar};
+use ::nalgebra_sparse::{CscMatrix, CsrMatrix};
+
+use crate::c::options::ColumnPermPolicy;
+use crate::c::stat::CSuperluStat;
+use crate::c::value_type::ValueType;
+use crate::comp_col::CompColMatrix;
+use crate::dense::DenseMatrix;
+use crate::interop::{checked_index, ConversionError, SolveError};
+use crate::simple_driver::{SimpleSolution, SimpleSystem};
+
+/// Convert a `nalgebra_sparse` index array into a SuperLU index array
+fn to_superlu_indices(indices: &[usize]) -> Result<Vec<i32>, ConversionError> {
+    indices.iter().map(|&index| checked_index(index)).collect()
+}
+
+impl<P: ValueType + Scalar> TryFrom<CscMatrix<P>> for CompColMatrix<P> {
+    type Error = ConversionError;
+
+    /// Convert an owned CSC matrix into a compressed-column
+    /// matrix. The non-zero values are moved without copying.
+    fn try_from(a: CscMatrix<P>) -> Result<Self, Self::Error> {
+        let num_rows = a.nrows();
+        checked_index(num_rows)?;
+        let (col_offsets, row_indices, values) = a.disassemble();
+        let column_offsets = to_superlu_indices(&col_offsets)?;
+        let row_indices = to_superlu_indices(&row_indices)?;
+        Ok(CompColMatrix::from_vectors(num_rows, values, row_indices, column_offsets))
+    }
+}
+
+impl<P: ValueType + Scalar> TryFrom<&CscMatrix<P>> for CompColMatrix<P> {
+    type Error = ConversionError;
+
+    /// Copy a CSC matrix into a compressed-column matrix
+    fn try_from(a: &CscMatrix<P>) -> Result<Self, Self::Error> {
+        let num_rows = a.nrows();
+        checked_index(num_rows)?;
+        let column_offsets = to_superlu_indices(a.col_offsets())?;
+        let row_indices = to_superlu_indices(a.row_indices())?;
+        let values = a.values().to_vec();
+        Ok(CompColMatrix::from_vectors(num_rows, values, row_indices, column_offsets))
+    }
+}
+
+impl<P: ValueType + Scalar> TryFrom<&CsrMatrix<P>> for CompColMatrix<P> {
+    type Error = ConversionError;
+
+    /// Convert a CSR matrix into a compressed-column matrix
+    /// (the matrix is transposed into CSC storage first).
+    fn try_from(a: &CsrMatrix<P>) -> Result<Self, Self::Error> {
+        Self::try_from(CscMatrix::from(a))
+    }
+}
+
+impl<P: ValueType + Scalar> TryFrom<&CompColMatrix<P>> for CscMatrix<P> {
+    type Error = ConversionError;
+
+    /// Copy a compressed-column matrix into a CSC matrix
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the compressed-column matrix does
+    /// not satisfy the requirements of `CscMatrix` (for example,
+    /// if the row indices in a column are not sorted).
+    fn try_from(a: &CompColMatrix<P>) -> Result<Self, Self::Error> {
+        let col_offsets = a.column_offsets().iter().map(|&x| x as usize).collect();
+        let row_indices = a.row_indices().iter().map(|&x| x as usize).collect();
+        let values = a.non_zero_values().to_vec();
+        CscMatrix::try_from_csc_data(a.num_rows(), a.num_columns(), col_offsets, row_indices, values)
+            .map_err(|err| ConversionError::InvalidStructure(err.to_string()))
+    }
+}
+
+impl<P: ValueType + Scalar> TryFrom<&CompColMatrix<P>> for CsrMatrix<P> {
+    type Error = ConversionError;
+
+    /// Copy a compressed-column matrix into a CSR matrix
+    fn try_from(a: &CompColMatrix<P>) -> Result<Self, Self::Error> {
+        Ok(CsrMatrix::from(&CscMatrix::try_from(a)?))
+    }
+}
+
+impl<P: ValueType + Scalar> From<DMatrix<P>> for DenseMatrix<P> {
+    /// Convert a `DMatrix` into a dense matrix. Both types are
+    /// stored in column-major order, so the values are moved
+    /// without copying.
+    fn from(a: DMatrix<P>) -> Self {
+        let (num_rows, num_columns) = a.shape();
+        DenseMatrix::from_vectors(num_rows, num_columns, a.data.into())
+    }
+}
+
+impl<P: ValueType + Scalar> From<&DMatrix<P>> for DenseMatrix<P> {
+    /// Copy a `DMatrix` into a dense matrix
+    fn from(a: &DMatrix<P>) -> Self {
+        let (num_rows, num_columns) = a.shape();
+        DenseMatrix::from_vectors(num_rows, num_columns, a.as_slice().to_vec())
+    }
+}
+
+impl<P: ValueType + Scalar> From<DVector<P>> for DenseMatrix<P> {
+    /// Convert a `DVector` into a dense matrix with one column
+    fn from(b: DVector<P>) -> Self {
+        let num_rows = b.len();
+        DenseMatrix::from_vectors(num_rows, 1, b.data.into())
+    }
+}
+
+impl<P: ValueType + Scalar> From<&DenseMatrix<P>> for DMatrix<P> {
+    /// Copy a dense matrix into a `DMatrix`
+    fn from(a: &DenseMatrix<P>) -> Self {
+        let values = a.to_vec();
+        DMatrix::from_vec(a.num_rows(), a.num_columns(), values)
+    }
+}
+
+impl<P: ValueType + Scalar> TryFrom<&DenseMatrix<P>> for DVector<P> {
+    type Error = ConversionError;
+
+    /// Copy a dense matrix with exactly one column into a `DVector`
+    fn try_from(a: &DenseMatrix<P>) -> Result<Self, Self::Error> {
+        if a.num_columns() != 1 {
+            return Err(ConversionError::DimensionMismatch);
+        }
+        Ok(DVector::from_vec(a.to_vec()))
+    }
+}
+
+/// Solve $AX = B$ using the simple driver, returning $X$ as a `DMatrix`
+///
+/// The matrix $A$ is copied into a [CompColMatrix], and the
+/// right-hand sides $B$ are moved into a [DenseMatrix] without
+/// copying. Use [SimpleSystem] directly if you need access to the
+/// $LU$ factorisation or the permutations.
+///
+/// # Errors
+///
+/// A [SolveError::Conversion] is returned if $A$ is not square,
+/// or if the number of rows of $B$ does not match $A$. Errors from
+/// the simple driver are returned as [SolveError::Solver].
+///
+pub fn solve_matrix<P: ValueType + Scalar>(
+    a: &CscMatrix<P>,
+    b: DMatrix<P>,
+    stat: &mut CSuperluStat,
+    column_perm_policy: ColumnPermPolicy,
+) -> Result<DMatrix<P>, SolveError<P>> {
+    if a.nrows() != a.ncols() || a.nrows() != b.nrows() {
+        return Err(ConversionError::DimensionMismatch.into());
+    }
+    let a = CompColMatrix::try_from(a)?;
+    let b = DenseMatrix::from(b);
+    let SimpleSolution { x, .. } = SimpleSystem { a, b }.solve(stat, column_perm_policy)?;
+    Ok(DMatrix::from(&x))
+}
+
+/// Solve $Ax = b$ using the simple driver, returning $x$ as a `DVector`
+///
+/// This is the same as [solve_matrix], for a single right-hand side.
+///
+pub fn solve_vector<P: ValueType + Scalar>(
+    a: &CscMatrix<P>,
+    b: DVector<P>,
+    stat: &mut CSuperluStat,
+    column_perm_policy: ColumnPermPolicy,
+) -> Result<DVector<P>, SolveError<P>> {
+    if a.nrows() != a.ncols() || a.nrows() != b.len() {
+        return Err(ConversionError::DimensionMismatch.into());
+    }
+    let a = CompColMatrix::try_from(a)?;
+    let b = DenseMatrix::from(b);
+    let SimpleSolution { x, .. } = SimpleSystem { a, b }.solve(stat, column_perm_policy)?;
+    Ok(DVector::try_from(&x)?)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,99 @@
+use super::*;
+use num::Complex;
+use crate::test_utils::{self, X_TRUE};
+
+/// The 5x5 matrix from the SuperLU user guide, in CSC storage
+fn user_guide_matrix() -> CscMatrix<f64> {
+    let a = test_utils::user_guide_matrix();
+    let to_usize = |indices: &[i32]| indices.iter().map(|&index| index as usize).collect();
+    CscMatrix::try_from_csc_data(
+        a.num_rows(),
+        a.num_columns(),
+        to_usize(a.column_offsets()),
+        to_usize(a.row_indices()),
+        a.non_zero_values().to_vec(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn csc_round_trip() {
+    let csc = user_guide_matrix();
+    let a = CompColMatrix::try_from(&csc).unwrap();
+    assert_eq!(a.num_rows(), 5);
+    assert_eq!(a.column_offsets(), &[0, 3, 6, 8, 10, 12]);
+    assert_eq!(a.row_indices(), &[0, 1, 4, 1, 2, 4, 0, 2, 0, 3, 3, 4]);
+    assert_eq!(CscMatrix::try_from(&a).unwrap(), csc);
+
+    let b = CompColMatrix::try_from(csc.clone()).unwrap();
+    assert_eq!(b.non_zero_values(), csc.values());
+}
+
+#[test]
+fn csr_round_trip() {
+    let csc = user_guide_matrix();
+    let csr = CsrMatrix::from(&csc);
+    let a = CompColMatrix::try_from(&csr).unwrap();
+    assert_eq!(a.column_offsets(), &[0, 3, 6, 8, 10, 12]);
+    assert_eq!(a.non_zero_values(), csc.values());
+    assert_eq!(CsrMatrix::try_from(&a).unwrap(), csr);
+}
+
+#[test]
+fn complex_csc_conversion() {
+    let values = vec![Complex::new(1.0f32, 2.0), Complex::new(-3.0, 0.5)];
+    let csc = CscMatrix::try_from_csc_data(2, 2, vec![0, 1, 2], vec![1, 0], values.clone()).unwrap();
+    let a = CompColMatrix::try_from(&csc).unwrap();
+    assert_eq!(a.non_zero_values(), &values[..]);
+    assert_eq!(a.row_indices(), &[1, 0]);
+    assert_eq!(CscMatrix::try_from(&a).unwrap(), csc);
+}
+
+#[test]
+fn dense_round_trip() {
+    let m = DMatrix::from_row_slice(2, 3, &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    let a = DenseMatrix::from(&m);
+    assert_eq!(a.num_rows(), 2);
+    assert_eq!(a.num_columns(), 3);
+    assert_eq!(a.column_major_values(), &[1.0, 4.0, 2.0, 5.0, 3.0, 6.0]);
+    assert_eq!(DMatrix::from(&a), m);
+    assert_eq!(DMatrix::from(&DenseMatrix::from(m.clone())), m);
+}
+
+#[test]
+fn vector_round_trip() {
+    let v = DVector::from_vec(vec![Complex::new(1.0, -1.0), Complex::new(0.0, 2.0)]);
+    let a = DenseMatrix::from(v.clone());
+    assert_eq!(a.num_columns(), 1);
+    assert_eq!(DVector::try_from(&a).unwrap(), v);
+
+    let m = DenseMatrix::from(DMatrix::<f64>::zeros(2, 2));
+    assert_eq!(DVector::try_from(&m), Err(ConversionError::DimensionMismatch));
+}
+
+#[test]
+fn solve_rejects_mismatched_rhs() {
+    let mut stat = CSuperluStat::new();
+    let b = DVector::from_vec(vec![1.0; 4]);
+    let result = solve_vector(&user_guide_matrix(), b, &mut stat, ColumnPermPolicy::Natural);
+    assert!(matches!(result, Err(SolveError::Conversion(ConversionError::DimensionMismatch))));
+}
+
+#[test]
+fn solve_vector_and_matrix() {
+    let a = user_guide_matrix();
+    let mut stat = CSuperluStat::new();
+    let x = solve_vector(&a, DVector::from_element(5, 1.0), &mut stat, ColumnPermPolicy::Natural).unwrap();
+    for (value, expected) in x.iter().zip(X_TRUE) {
+        assert!((value - expected).abs() < 1e-14);
+    }
+
+    // The second right-hand side is twice the first
+    let b = DMatrix::from_fn(5, 2, |_, col| (col + 1) as f64);
+    let x = solve_matrix(&a, b, &mut stat, ColumnPermPolicy::ColAMD).unwrap();
+    assert_eq!(x.shape(), (5, 2));
+    for (row, expected) in X_TRUE.iter().enumerate() {
+        assert!((x[(row, 0)] - expected).abs() < 1e-14);
+        assert!((x[(row, 1)] - 2.0 * expected).abs() < 1e-14);
+    }
+}
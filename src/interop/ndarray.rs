@@ -0,0 +1,115 @@
+//! Conversions between csuperlu and [ndarray](https://docs.rs/ndarray)
+//!
+//! This module is enabled by the `ndarray` cargo feature. It
+//! converts between `ndarray` arrays and [DenseMatrix], for use
+//! as right-hand sides and solutions of $AX = B$.
+//!
+//! A [DenseMatrix] is always stored in column-major order.
+//! Arrays in column-major ("Fortran") layout are copied directly
+//! (or moved, for owned arrays), and arrays in any other layout
+//! (including the default row-major layout) are transposed into
+//! column-major order as they are copied.
+//!
+
+use ::ndarray::{Array1, Array2, ArrayView1, ArrayView2, ArrayViewMut2, ShapeBuilder};
+
+use crate::c::value_type::ValueType;
+use crate::dense::DenseMatrix;
+use crate::interop::ConversionError;
+
+impl<P: ValueType> From<ArrayView2<'_, P>> for DenseMatrix<P> {
+    /// Copy a two-dimensional array view (in any memory layout)
+    /// into a dense matrix
+    fn from(a: ArrayView2<'_, P>) -> Self {
+        let (num_rows, num_columns) = a.dim();
+        // The transpose of a column-major array is a row-major
+        // array, which is contiguous if a is contiguous
+        let a_t = a.t();
+        let values = match a_t.as_slice() {
+            Some(values) => values.to_vec(),
+            None => a_t.iter().copied().collect(),
+        };
+        DenseMatrix::from_vectors(num_rows, num_columns, values)
+    }
+}
+
+impl<P: ValueType> From<Array2<P>> for DenseMatrix<P> {
+    /// Convert a two-dimensional array into a dense matrix. If the
+    /// array is contiguous and in column-major layout, the values
+    /// are moved without copying; otherwise they are copied.
+    fn from(a: Array2<P>) -> Self {
+        let (num_rows, num_columns) = a.dim();
+        if !a.t().is_standard_layout() {
+            return Self::from(a.view());
+        }
+        match a.into_raw_vec_and_offset() {
+            (values, Some(0)) if values.len() == num_rows * num_columns => {
+                DenseMatrix::from_vectors(num_rows, num_columns, values)
+            }
+            (values, offset) => {
+                let offset = offset.unwrap_or(0);
+                let values = values[offset..][..num_rows * num_columns].to_vec();
+                DenseMatrix::from_vectors(num_rows, num_columns, values)
+            }
+        }
+    }
+}
+
+impl<P: ValueType> From<ArrayView1<'_, P>> for DenseMatrix<P> {
+    /// Copy a one-dimensional array view into a dense matrix
+    /// with one column
+    fn from(b: ArrayView1<'_, P>) -> Self {
+        DenseMatrix::from_vectors(b.len(), 1, b.iter().copied().collect())
+    }
+}
+
+impl<P: ValueType> From<&DenseMatrix<P>> for Array2<P> {
+    /// Copy a dense matrix into a two-dimensional array (in
+    /// column-major layout)
+    fn from(x: &DenseMatrix<P>) -> Self {
+        let shape = (x.num_rows(), x.num_columns()).f();
+        Array2::from_shape_vec(shape, x.to_vec())
+            .expect("Dense matrix values do not match its dimensions")
+    }
+}
+
+impl<P: ValueType> TryFrom<&DenseMatrix<P>> for Array1<P> {
+    type Error = ConversionError;
+
+    /// Copy a dense matrix with exactly one column into a
+    /// one-dimensional array
+    fn try_from(x: &DenseMatrix<P>) -> Result<Self, Self::Error> {
+        if x.num_columns() != 1 {
+            return Err(ConversionError::DimensionMismatch);
+        }
+        Ok(Array1::from(x.to_vec()))
+    }
+}
+
+/// Copy a dense matrix into a caller-supplied array view
+///
+/// Use this function to write the solution $X$ returned by a
+/// solver into existing storage (in any memory layout), without
+/// allocating a new array.
+///
+/// # Errors
+///
+/// If the shape of the array view does not match the dimensions
+/// of the dense matrix, an error is returned and the view is not
+/// modified.
+///
+pub fn write_to_array<P: ValueType>(
+    x: &DenseMatrix<P>,
+    mut out: ArrayViewMut2<'_, P>,
+) -> Result<(), ConversionError> {
+    if out.dim() != (x.num_rows(), x.num_columns()) {
+        return Err(ConversionError::DimensionMismatch);
+    }
+    for ((row, column), value) in out.indexed_iter_mut() {
+        *value = x[(row, column)];
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,54 @@
+use super::*;
+use ::ndarray::{array, Array2, ShapeBuilder};
+use num::Complex;
+
+#[test]
+fn row_major_view_to_dense() {
+    let a = array![[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]];
+    let b = DenseMatrix::from(a.view());
+    assert_eq!(b.num_rows(), 2);
+    assert_eq!(b.num_columns(), 3);
+    assert_eq!(b.column_major_values(), &[1.0, 4.0, 2.0, 5.0, 3.0, 6.0]);
+}
+
+#[test]
+fn column_major_array_to_dense() {
+    let a = Array2::from_shape_vec((2, 3).f(), vec![1.0, 4.0, 2.0, 5.0, 3.0, 6.0]).unwrap();
+    let b = DenseMatrix::from(a.view());
+    assert_eq!(b.column_major_values(), &[1.0, 4.0, 2.0, 5.0, 3.0, 6.0]);
+    let c = DenseMatrix::from(a);
+    assert_eq!(c.column_major_values(), &[1.0, 4.0, 2.0, 5.0, 3.0, 6.0]);
+}
+
+#[test]
+fn strided_view_to_dense() {
+    let a = array![[1.0f32, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]];
+    let b = DenseMatrix::from(a.slice(::ndarray::s![..;2, 1..]));
+    assert_eq!(b.num_rows(), 2);
+    assert_eq!(b.num_columns(), 2);
+    assert_eq!(b.column_major_values(), &[2.0, 8.0, 3.0, 9.0]);
+}
+
+#[test]
+fn dense_to_array() {
+    let x = DenseMatrix::from_vectors(2, 2, vec![Complex::new(1.0, 1.0), Complex::new(2.0, 0.0),
+                                                 Complex::new(3.0, 0.0), Complex::new(4.0, -1.0)]);
+    let a = Array2::from(&x);
+    assert_eq!(a[[0, 1]], Complex::new(3.0, 0.0));
+    assert_eq!(a[[1, 0]], Complex::new(2.0, 0.0));
+    assert_eq!(Array1::try_from(&x), Err(ConversionError::DimensionMismatch));
+
+    let v = DenseMatrix::from(array![1.0, 2.0, 3.0].view());
+    assert_eq!(Array1::try_from(&v).unwrap(), array![1.0, 2.0, 3.0]);
+}
+
+#[test]
+fn write_to_existing_array() {
+    let x = DenseMatrix::from_vectors(2, 2, vec![1.0, 2.0, 3.0, 4.0]);
+    let mut out = Array2::zeros((2, 2));
+    write_to_array(&x, out.view_mut()).unwrap();
+    assert_eq!(out, array![[1.0, 3.0], [2.0, 4.0]]);
+
+    let mut wrong = Array2::zeros((3, 2));
+    assert_eq!(write_to_array(&x, wrong.view_mut()), Err(ConversionError::DimensionMismatch));
+}
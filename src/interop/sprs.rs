@@ -0,0 +1,211 @@
+//! Conversions between csuperlu and [sprs](https://docs.rs/sprs)
+//!
+//! This module is enabled by the `sprs` cargo feature. It
+//! provides conversions between `sprs::CsMat` (in either CSC or
+//! CSR storage) and the csuperlu matrix types, and a [solve]
+//! function which solves $AX = B$ for a `sprs` matrix $A$ using
+//! the simple driver.
+//!
+//! SuperLU only accepts compressed-column matrices, so CSR
+//! matrices are converted to CSC storage as part of the
+//! conversion. Converting an owned CSC matrix moves the
+//! non-zero values into the [CompColMatrix] without copying
+//! them. The index arrays are always copied, because SuperLU
+//! uses `i32` indices whereas `sprs` uses `usize` by default.
+//!
+
+use std::ops::Deref;
+
+use ::sprs::{CsMatBase, CsMatI, SpIndex, TriMat};
+
+use crate::c::options::ColumnPermPolicy;
+use crate::c::stat::CSuperluStat;
+use crate::c::value_type::ValueType;
+use crate::comp_col::CompColMatrix;
+use crate::dense::DenseMatrix;
+use crate::interop::{checked_index, ConversionError, SolveError};
+use crate::simple_driver::{SimpleSolution, SimpleSystem};
+use crate::sparse_matrix::SparseMat;
+
+/// Convert a `sprs` index array into a SuperLU index array,
+/// subtracting the offset of the first element (non-zero for
+/// `sprs` views into the middle of a matrix)
+fn to_superlu_indices<I: SpIndex>(indices: &[I], offset: usize) -> Result<Vec<i32>, ConversionError> {
+    indices
+        .iter()
+        .map(|index| checked_index(index.index() - offset))
+        .collect()
+}
+
+/// Convert a SuperLU index array into a `sprs` index array
+fn from_superlu_indices<I: SpIndex>(indices: &[i32]) -> Result<Vec<I>, ConversionError> {
+    indices
+        .iter()
+        .map(|&index| {
+            <I as num::NumCast>::from(index)
+                .ok_or(ConversionError::IndexOverflow { index: index as usize })
+        })
+        .collect()
+}
+
+impl<P, I, Iptr> TryFrom<CsMatI<P, I, Iptr>> for CompColMatrix<P>
+where
+    P: ValueType + Default,
+    I: SpIndex,
+    Iptr: SpIndex,
+{
+    type Error = ConversionError;
+
+    /// Convert an owned `sprs` matrix into a compressed-column
+    /// matrix. If the matrix is in CSC storage, the non-zero
+    /// values are moved without copying. A CSR matrix is first
+    /// converted to CSC storage.
+    fn try_from(a: CsMatI<P, I, Iptr>) -> Result<Self, Self::Error> {
+        let a = a.into_csc();
+        let num_rows = a.rows();
+        checked_index(num_rows)?;
+        checked_index(a.cols())?;
+        let (indptr, indices, data) = a.into_raw_storage();
+        let offset = indptr.first().map_or(0, |first| first.index());
+        let column_offsets = to_superlu_indices(&indptr, offset)?;
+        let row_indices = to_superlu_indices(&indices, 0)?;
+        Ok(CompColMatrix::from_vectors(num_rows, data, row_indices, column_offsets))
+    }
+}
+
+impl<P, I, Iptr, IptrStorage, IndStorage, DataStorage>
+    TryFrom<&CsMatBase<P, I, IptrStorage, IndStorage, DataStorage, Iptr>> for CompColMatrix<P>
+where
+    P: ValueType + Default,
+    I: SpIndex,
+    Iptr: SpIndex,
+    IptrStorage: Deref<Target = [Iptr]>,
+    IndStorage: Deref<Target = [I]>,
+    DataStorage: Deref<Target = [P]>,
+{
+    type Error = ConversionError;
+
+    /// Copy a `sprs` matrix (or matrix view) into a
+    /// compressed-column matrix.
+    fn try_from(
+        a: &CsMatBase<P, I, IptrStorage, IndStorage, DataStorage, Iptr>,
+    ) -> Result<Self, Self::Error> {
+        if !a.is_csc() {
+            return Self::try_from(a.to_csc());
+        }
+        let num_rows = a.rows();
+        checked_index(num_rows)?;
+        checked_index(a.cols())?;
+        let column_offsets = to_superlu_indices(&a.proper_indptr(), 0)?;
+        let row_indices = to_superlu_indices(a.indices(), 0)?;
+        let non_zero_values = a.data().to_vec();
+        Ok(CompColMatrix::from_vectors(num_rows, non_zero_values, row_indices, column_offsets))
+    }
+}
+
+impl<P, I> TryFrom<&CompColMatrix<P>> for CsMatI<P, I>
+where
+    P: ValueType + Default,
+    I: SpIndex,
+{
+    type Error = ConversionError;
+
+    /// Copy a compressed-column matrix into a `sprs` matrix
+    /// in CSC storage.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the indices do not fit into the
+    /// index type I, or if the row indices in each column of the
+    /// compressed-column matrix are not sorted (`sprs` requires
+    /// sorted indices).
+    fn try_from(a: &CompColMatrix<P>) -> Result<Self, Self::Error> {
+        let indptr = from_superlu_indices(a.column_offsets())?;
+        let indices = from_superlu_indices(a.row_indices())?;
+        let data = a.non_zero_values().to_vec();
+        CsMatI::try_new_csc((a.num_rows(), a.num_columns()), indptr, indices, data)
+            .map_err(|(_, _, _, err)| ConversionError::InvalidStructure(err.to_string()))
+    }
+}
+
+impl<P, I, Iptr, IptrStorage, IndStorage, DataStorage>
+    From<&CsMatBase<P, I, IptrStorage, IndStorage, DataStorage, Iptr>> for SparseMat<P>
+where
+    P: ValueType,
+    I: SpIndex,
+    Iptr: SpIndex,
+    IptrStorage: Deref<Target = [Iptr]>,
+    IndStorage: Deref<Target = [I]>,
+    DataStorage: Deref<Target = [P]>,
+{
+    /// Copy a `sprs` matrix (in either storage order) into a
+    /// sparse matrix. Explicitly stored zeros are dropped.
+    fn from(a: &CsMatBase<P, I, IptrStorage, IndStorage, DataStorage, Iptr>) -> Self {
+        let mut sparse_mat = SparseMat::new(a.rows(), a.cols());
+        for (&value, (row, col)) in a.iter() {
+            sparse_mat.insert(row.index(), col.index(), value);
+        }
+        sparse_mat
+    }
+}
+
+impl<P> From<&SparseMat<P>> for CsMatI<P, usize>
+where
+    P: ValueType + Default,
+{
+    /// Copy a sparse matrix into a `sprs` matrix in CSC storage
+    fn from(a: &SparseMat<P>) -> Self {
+        let mut triplets = TriMat::with_capacity((a.num_rows(), a.num_cols()), a.num_non_zeros());
+        for (&(row, col), &value) in a.non_zero_vals() {
+            triplets.add_triplet(row, col, value);
+        }
+        triplets.to_csc()
+    }
+}
+
+/// Solve $AX = B$ for a `sprs` matrix $A$ using the simple driver
+///
+/// The right-hand side $B$ is passed as a column-major vector of
+/// length $n \times n_\text{rhs}$, where $n$ is the number of rows
+/// of $A$; the number of right-hand sides is inferred from its
+/// length. The solution $X$ is returned in the same layout.
+///
+/// The matrix $A$ is copied into a [CompColMatrix] (see the
+/// conversions in this module). Use [SimpleSystem] directly if
+/// you need access to the $LU$ factorisation or the permutations.
+///
+/// # Errors
+///
+/// A [SolveError::Conversion] is returned if $A$ is not square,
+/// if the length of b is not a multiple of the number of rows of
+/// $A$, or if $A$ cannot be converted. Errors from the simple
+/// driver (for example, if $A$ is singular) are returned as
+/// [SolveError::Solver].
+///
+pub fn solve<P, I, Iptr, IptrStorage, IndStorage, DataStorage>(
+    a: &CsMatBase<P, I, IptrStorage, IndStorage, DataStorage, Iptr>,
+    b: Vec<P>,
+    stat: &mut CSuperluStat,
+    column_perm_policy: ColumnPermPolicy,
+) -> Result<Vec<P>, SolveError<P>>
+where
+    P: ValueType + Default,
+    I: SpIndex,
+    Iptr: SpIndex,
+    IptrStorage: Deref<Target = [Iptr]>,
+    IndStorage: Deref<Target = [I]>,
+    DataStorage: Deref<Target = [P]>,
+{
+    let num_rows = a.rows();
+    if num_rows != a.cols() || num_rows == 0 || b.len() % num_rows != 0 {
+        return Err(ConversionError::DimensionMismatch.into());
+    }
+    let num_rhs = b.len() / num_rows;
+    let a = CompColMatrix::try_from(a)?;
+    let b = DenseMatrix::from_vectors(num_rows, num_rhs, b);
+    let SimpleSolution { x, .. } = SimpleSystem { a, b }.solve(stat, column_perm_policy)?;
+    Ok(x.to_vec())
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,96 @@
+use super::*;
+use ::sprs::CsMat;
+use crate::test_utils::{self, X_TRUE};
+
+/// The 5x5 matrix from the SuperLU user guide, in CSC storage
+fn user_guide_matrix() -> CsMat<f64> {
+    let a = test_utils::user_guide_matrix();
+    let to_usize = |indices: &[i32]| indices.iter().map(|&index| index as usize).collect();
+    CsMat::new_csc(
+        (a.num_rows(), a.num_columns()),
+        to_usize(a.column_offsets()),
+        to_usize(a.row_indices()),
+        a.non_zero_values().to_vec(),
+    )
+}
+
+#[test]
+fn csc_to_comp_col() {
+    let a = CompColMatrix::try_from(user_guide_matrix()).unwrap();
+    assert_eq!(a.num_rows(), 5);
+    assert_eq!(a.num_columns(), 5);
+    assert_eq!(a.column_offsets(), &[0, 3, 6, 8, 10, 12]);
+    assert_eq!(a.row_indices(), &[0, 1, 4, 1, 2, 4, 0, 2, 0, 3, 3, 4]);
+    assert_eq!(a.non_zero_values()[7], 16.0);
+}
+
+#[test]
+fn csr_to_comp_col() {
+    let csc = user_guide_matrix();
+    let csr = csc.to_csr();
+    let a = CompColMatrix::try_from(&csr).unwrap();
+    assert_eq!(a.column_offsets(), &[0, 3, 6, 8, 10, 12]);
+    assert_eq!(a.row_indices(), &[0, 1, 4, 1, 2, 4, 0, 2, 0, 3, 3, 4]);
+    assert_eq!(a.non_zero_values(), csc.data());
+}
+
+#[test]
+fn view_to_comp_col() {
+    let csc = user_guide_matrix();
+    let middle = csc.slice_outer(2..4);
+    let a = CompColMatrix::try_from(&middle).unwrap();
+    assert_eq!(a.num_rows(), 5);
+    assert_eq!(a.num_columns(), 2);
+    assert_eq!(a.column_offsets(), &[0, 2, 4]);
+    assert_eq!(a.row_indices(), &[0, 2, 0, 3]);
+}
+
+#[test]
+fn comp_col_round_trip() {
+    let csc = user_guide_matrix();
+    let a = CompColMatrix::try_from(&csc).unwrap();
+    let b: CsMat<f64> = CsMatI::try_from(&a).unwrap();
+    assert_eq!(b, csc);
+    let c: CsMatI<f64, i32> = CsMatI::try_from(&a).unwrap();
+    assert_eq!(c.indices(), &[0, 1, 4, 1, 2, 4, 0, 2, 0, 3, 3, 4]);
+}
+
+#[test]
+fn unsorted_comp_col_is_rejected() {
+    let a = CompColMatrix::from_vectors(2, vec![1.0, 2.0], vec![1, 0], vec![0, 2, 2]);
+    let b: Result<CsMat<f64>, _> = CsMatI::try_from(&a);
+    assert!(matches!(b, Err(ConversionError::InvalidStructure(_))));
+}
+
+#[test]
+fn sparse_mat_round_trip() {
+    let csc = user_guide_matrix();
+    let a = SparseMat::from(&csc);
+    assert_eq!(a.num_non_zeros(), 12);
+    assert_eq!(a.get(3, 4), 21.0);
+    assert_eq!(a.get(4, 0), 12.0);
+    let b = CsMat::from(&a);
+    assert_eq!(b, csc);
+    assert_eq!(SparseMat::from(&csc.to_csr()), a);
+}
+
+#[test]
+fn solve_rejects_mismatched_rhs() {
+    let mut stat = CSuperluStat::new();
+    let result = solve(&user_guide_matrix(), vec![1.0; 7], &mut stat, ColumnPermPolicy::Natural);
+    assert!(matches!(result, Err(SolveError::Conversion(ConversionError::DimensionMismatch))));
+}
+
+#[test]
+fn solve_csc_and_csr() {
+    let csc = user_guide_matrix();
+    let mut stat = CSuperluStat::new();
+    for x in [
+        solve(&csc, vec![1.0; 5], &mut stat, ColumnPermPolicy::Natural).unwrap(),
+        solve(&csc.to_csr(), vec![1.0; 5], &mut stat, ColumnPermPolicy::ColAMD).unwrap(),
+    ] {
+        for (value, expected) in x.iter().zip(X_TRUE) {
+            assert!((value - expected).abs() < 1e-14);
+        }
+    }
+}
@@ -29,15 +29,34 @@
 //!
 
 //#![warn(missing_docs)]
+pub mod arithmetic;
+pub mod block_matrix;
+pub mod cast;
 pub mod comp_col;
 pub mod dense;
+pub mod display;
+pub mod equilibration;
+pub mod factorization;
 pub mod harwell_boeing;
 pub mod lu_decomp;
+pub mod memory;
+pub mod mixed_precision;
+pub mod ordering;
+pub mod properties;
+pub mod schur;
 pub mod simple_driver;
+pub mod singularity;
+pub mod spy;
+pub mod submatrix;
 pub mod super_node;
+pub mod symbolic;
+pub mod transversal;
 pub mod utils;
 pub mod c;
 pub mod sparse_matrix;
+pub mod interop;
+#[cfg(test)]
+mod test_utils;
     
 //pub use error::Error;
 
@@ -74,7 +93,7 @@ mod tests {
         let column_offsets = vec![0, 3, 6, 8, 10, 12];
 
         // Make the left-hand side matrix
-        let mut a =
+        let a =
             CompColMatrix::from_vectors(num_rows, non_zero_values, row_indices, column_offsets);
 
         // Check non-zero matrix values
@@ -141,7 +160,7 @@ mod tests {
 	let mut stat = CSuperluStat::new();
 
 	let SimpleSolution {
-	    mut x, ..
+	    x, ..
 	} = SimpleSystem {
 	    a,
 	    b,
@@ -1,15 +1,49 @@
+//! The $LU$ decomposition computed by SuperLU
+//!
+//! SuperLU stores the $L$ factor as a [SuperNodeMatrix], which
+//! also contains the parts of $U$ that lie inside the diagonal
+//! blocks of the super-nodes. The rest of $U$ is stored as a
+//! [CompColMatrix]. The functions in this module hide that
+//! split, so that $L$ (with its unit diagonal) and $U$ can be
+//! accessed as ordinary matrices.
+//!
+//! The factors are for the permuted matrix $P_rAP_c$, so row and
+//! column indices refer to positions after permutation.
+//!
+//! If the factorisation used a [WorkBuffer], the arrays of $L$
+//! and $U$ are stored in the buffer, which is owned by the
+//! decomposition (see [memory](crate::memory)).
+//!
+
+use std::fmt;
+use std::mem::ManuallyDrop;
+
 use crate::comp_col::CompColMatrix;
+use crate::display::{DisplayView, MatrixDisplay, MatrixElements};
 use crate::super_node::SuperNodeMatrix;
 use crate::c::value_type::ValueType;
+use crate::simple_driver::ColumnPerm;
+use crate::memory::WorkBuffer;
+use crate::c::free::c_destroy_super_matrix_store;
 
 #[derive(Debug)]
 pub struct LUDecomp<P: ValueType> {
-    l: SuperNodeMatrix<P>,
-    u: CompColMatrix<P>,
+    // The factors are dropped manually, because their arrays
+    // are not allocated by SuperLU if there is a work buffer
+    l: ManuallyDrop<SuperNodeMatrix<P>>,
+    u: ManuallyDrop<CompColMatrix<P>>,
+    work: Option<WorkBuffer>,
 }
 
 impl<P: ValueType> LUDecomp<P> {
     pub fn from_matrices(l: SuperNodeMatrix<P>, u: CompColMatrix<P>) -> Self {
+        Self::from_parts(l, u, None)
+    }
+
+    /// Make the decomposition from the factors returned by a
+    /// driver, and the work buffer that the driver stored them in
+    /// (if any)
+    pub(crate) fn from_parts(l: SuperNodeMatrix<P>, u: CompColMatrix<P>, work: Option<WorkBuffer>) -> Self {
         let l_c_super_matrix = l.super_matrix();
         let u_c_super_matrix = u.super_matrix();
         assert!(
@@ -20,25 +54,217 @@ impl<P: ValueType> LUDecomp<P> {
             l_c_super_matrix.num_columns() == u_c_super_matrix.num_columns(),
             "Number of columns in L and U must match"
         );
-        Self { l, u }
+        Self {
+            l: ManuallyDrop::new(l),
+            u: ManuallyDrop::new(u),
+            work,
+        }
     }
-    pub fn print(&mut self) {
-        self.l.print("L");
-        self.u.print("U");
+
+    /// Get the super-node matrix storing $L$ (and the diagonal
+    /// blocks of $U$)
+    pub fn l(&self) -> &SuperNodeMatrix<P> {
+        &self.l
     }
-    pub fn value(&mut self, row: usize, col: usize) -> P {
-        let l_c_super_matrix = self.l.super_matrix();
-        let _u_c_super_matrix = self.u.super_matrix();
-        assert!(
-            row < l_c_super_matrix.num_rows(),
-            "Row index out of range"
-        );
+
+    /// Get the compressed-column matrix storing the parts of $U$
+    /// outside the super-node diagonal blocks
+    pub fn u(&self) -> &CompColMatrix<P> {
+        &self.u
+    }
+
+    /// Get the number of rows in the factors
+    pub fn num_rows(&self) -> usize {
+        self.l.num_rows()
+    }
+
+    /// Get the number of columns in the factors
+    pub fn num_columns(&self) -> usize {
+        self.l.num_columns()
+    }
+
+    /// Get the stored elements of $L$ in column j, excluding the
+    /// unit diagonal
+    pub(crate) fn l_column(&self, col: usize) -> impl Iterator<Item = (usize, P)> + '_ {
+        let (row_indices, values) = self.l.column(col);
+        row_indices
+            .iter()
+            .zip(values)
+            .map(|(&row, &value)| (row as usize, value))
+            .filter(move |&(row, _)| row > col)
+    }
+
+    /// Get the stored elements of $U$ in column j (from both
+    /// the super-node matrix and the compressed-column matrix)
+    pub(crate) fn u_column(&self, col: usize) -> impl Iterator<Item = (usize, P)> + '_ {
+        let (row_indices, values) = self.l.column(col);
+        let start = self.u.column_offsets()[col] as usize;
+        let end = self.u.column_offsets()[col + 1] as usize;
+        let u_part = self.u.row_indices()[start..end]
+            .iter()
+            .zip(&self.u.non_zero_values()[start..end]);
+        u_part
+            .chain(row_indices.iter().zip(values))
+            .map(|(&row, &value)| (row as usize, value))
+            .filter(move |&(row, _)| row <= col)
+    }
+
+    /// Get the value of $L$ at (row, column). The diagonal of
+    /// $L$ is one.
+    ///
+    /// # Panics
+    ///
+    /// If the row or column index is out of range.
+    pub fn l_value(&self, row: usize, col: usize) -> P {
+        assert!(row < self.num_rows(), "Row index out of range");
+        assert!(col < self.num_columns(), "Column index out of range");
+        if row == col {
+            return P::one();
+        }
+        self.l_column(col)
+            .find(|&(r, _)| r == row)
+            .map_or(P::zero(), |(_, value)| value)
+    }
+
+    /// Get the value of $U$ at (row, column)
+    ///
+    /// # Panics
+    ///
+    /// If the row or column index is out of range.
+    pub fn u_value(&self, row: usize, col: usize) -> P {
+        assert!(row < self.num_rows(), "Row index out of range");
+        assert!(col < self.num_columns(), "Column index out of range");
+        self.u_column(col)
+            .find(|&(r, _)| r == row)
+            .map_or(P::zero(), |(_, value)| value)
+    }
+
+    /// Get a configurable view of $L$ for printing (see
+    /// [display](crate::display)). The unit diagonal is shown.
+    pub fn display_l(&self) -> MatrixDisplay<'_, P> {
+        MatrixDisplay::new(LFactor(self), DisplayView::Sparse)
+    }
+
+    /// Get a configurable view of $U$ for printing (see
+    /// [display](crate::display))
+    pub fn display_u(&self) -> MatrixDisplay<'_, P> {
+        MatrixDisplay::new(UFactor(self), DisplayView::Sparse)
+    }
+
+    /// Compute the reciprocal pivot growth of the first
+    /// num_columns columns of the factorisation
+    ///
+    /// The reciprocal pivot growth is the minimum over the columns
+    /// of $\max_i |(AP_c)_{ij}| / \max_i |U_{ij}|$, where $A$ is
+    /// the matrix that was factored and $P_c$ is the column
+    /// permutation. A value much less than one means that the
+    /// factorisation is unstable, and the solution may be
+    /// inaccurate.
+    ///
+    /// # Panics
+    ///
+    /// If the size of a does not match the factors, or num_columns
+    /// is greater than the number of columns.
+    ///
+    pub fn reciprocal_pivot_growth(
+        &self,
+        a: &CompColMatrix<P>,
+        column_perm: &ColumnPerm,
+        num_columns: usize,
+    ) -> f64 {
         assert!(
-            col < l_c_super_matrix.num_columns(),
-            "Column index out of range"
+            a.num_rows() == self.num_rows() && a.num_columns() == self.num_columns(),
+            "Size of the matrix does not match the factors"
         );
-        // let _l_c_scformat = unsafe { &mut *(l_c_super_matrix.store::<SCformat>()) };
-        // let _u_c_ncformat = unsafe { &mut *(u_c_super_matrix.store::<NCformat>()) };
-        todo!("Finish this off later");
+        assert!(num_columns <= self.num_columns(), "Number of columns out of range");
+        unsafe {
+            P::c_pivot_growth(
+                num_columns,
+                a.super_matrix(),
+                column_perm.as_slice(),
+                self.l.super_matrix(),
+                self.u.super_matrix(),
+            )
+        }
+    }
+
+    /// Print $L$ and $U$ to stdout
+    pub fn print(&self) {
+        print!("{self}");
+    }
+}
+
+impl<P: ValueType> Drop for LUDecomp<P> {
+    fn drop(&mut self) {
+        unsafe {
+            if self.work.is_some() {
+                // Only the SuperMatrix stores are allocated by
+                // SuperLU; the buffer is freed when it is dropped
+                c_destroy_super_matrix_store(self.l.super_matrix());
+                c_destroy_super_matrix_store(self.u.super_matrix());
+            } else {
+                ManuallyDrop::drop(&mut self.l);
+                ManuallyDrop::drop(&mut self.u);
+            }
+        }
+    }
+}
+
+/// The $L$ factor of an LUDecomp, for display
+struct LFactor<'a, P: ValueType>(&'a LUDecomp<P>);
+
+/// The $U$ factor of an LUDecomp, for display
+struct UFactor<'a, P: ValueType>(&'a LUDecomp<P>);
+
+impl<P: ValueType> MatrixElements<P> for LFactor<'_, P> {
+    fn num_rows(&self) -> usize {
+        self.0.num_rows()
+    }
+    fn num_columns(&self) -> usize {
+        self.0.num_columns()
+    }
+    fn num_stored(&self) -> usize {
+        (0..self.0.num_columns()).map(|col| self.0.l_column(col).count() + 1).sum()
+    }
+    fn stored_elements(&self) -> Box<dyn Iterator<Item = (usize, usize, P)> + '_> {
+        Box::new((0..self.0.num_columns()).flat_map(move |col| {
+            std::iter::once((col, P::one()))
+                .chain(self.0.l_column(col))
+                .map(move |(row, value)| (row, col, value))
+        }))
+    }
+    fn element(&self, row: usize, col: usize) -> P {
+        self.0.l_value(row, col)
+    }
+}
+
+impl<P: ValueType> MatrixElements<P> for UFactor<'_, P> {
+    fn num_rows(&self) -> usize {
+        self.0.num_rows()
+    }
+    fn num_columns(&self) -> usize {
+        self.0.num_columns()
+    }
+    fn num_stored(&self) -> usize {
+        (0..self.0.num_columns()).map(|col| self.0.u_column(col).count()).sum()
+    }
+    fn stored_elements(&self) -> Box<dyn Iterator<Item = (usize, usize, P)> + '_> {
+        Box::new((0..self.0.num_columns()).flat_map(move |col| {
+            self.0.u_column(col).map(move |(row, value)| (row, col, value))
+        }))
+    }
+    fn element(&self, row: usize, col: usize) -> P {
+        self.0.u_value(row, col)
+    }
+}
+
+impl<P: ValueType> fmt::Display for LUDecomp<P> {
+    /// Display $L$ followed by $U$. The alternate format `{:#}`
+    /// shows the factors as dense matrices.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "L = ")?;
+        fmt::Display::fmt(&self.display_l().alternate(f.alternate()), f)?;
+        write!(f, "U = ")?;
+        fmt::Display::fmt(&self.display_u().alternate(f.alternate()), f)
     }
 }
@@ -0,0 +1,89 @@
+//! Memory usage of the $LU$ factorisation
+//!
+//! By default, SuperLU allocates the memory for $L$ and $U$ itself
+//! (with malloc), starting from an estimate based on the number
+//! of non-zeros in $A$ and expanding the arrays when they fill up.
+//! If an allocation fails, the driver returns
+//! [Error::OutOfMemory](crate::c::value_type::Error::OutOfMemory).
+//! After a factorisation, [LUDecomp::mem_usage] reports how much
+//! memory was used (using the SuperLU *QuerySpace routines).
+//!
+//! To bound the memory used by a factorisation, a [WorkBuffer]
+//! can be allocated in Rust and passed to
+//! [SimpleSystem::solve_with_work](crate::simple_driver::SimpleSystem::solve_with_work).
+//! SuperLU then takes all the memory for $L$, $U$ and its
+//! temporary arrays from the buffer (lwork > 0 in the *gssvx
+//! routines), and returns
+//! [Error::OutOfMemory](crate::c::value_type::Error::OutOfMemory)
+//! instead of allocating more if the buffer is too small. The
+//! buffer is owned by the [LUDecomp], and is freed with it.
+//!
+//! The memory needed can be estimated before factorising, with
+//! [SimpleSystem::estimate_memory](crate::simple_driver::SimpleSystem::estimate_memory)
+//! (lwork = -1). This only runs the column ordering, and uses the
+//! same initial estimate that SuperLU uses to allocate its
+//! arrays, so the factorisation may need more (or less) memory
+//! than estimated. The exact amount is known after factorising.
+//!
+
+use csuperlu_sys::mem_usage_t;
+
+use crate::c::value_type::ValueType;
+use crate::lu_decomp::LUDecomp;
+
+/// The memory used by a factorisation, in bytes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MemUsage {
+    /// The memory used for $L$ and $U$
+    pub for_lu: f64,
+    /// The total memory needed by the factorisation (including
+    /// the temporary arrays)
+    pub total_needed: f64,
+}
+
+impl MemUsage {
+    pub(crate) fn from_c_mem_usage(mem_usage: &mem_usage_t) -> Self {
+        Self {
+            for_lu: mem_usage.for_lu as f64,
+            total_needed: mem_usage.total_needed as f64,
+        }
+    }
+}
+
+/// A buffer allocated in Rust, which SuperLU uses for all the
+/// memory in a factorisation (see [memory](crate::memory))
+#[derive(Debug)]
+pub struct WorkBuffer {
+    // Stored as f64 so that the buffer is aligned for all the
+    // value types
+    buffer: Vec<f64>,
+}
+
+impl WorkBuffer {
+    /// Allocate a buffer of (at least) size bytes
+    pub fn new(size: usize) -> Self {
+        let len = size.div_ceil(std::mem::size_of::<f64>());
+        Self { buffer: vec![0.0; len] }
+    }
+
+    /// Get the size of the buffer in bytes
+    pub fn size(&self) -> usize {
+        self.buffer.len() * std::mem::size_of::<f64>()
+    }
+
+    pub(crate) fn as_mut_slice(&mut self) -> &mut [f64] {
+        &mut self.buffer
+    }
+}
+
+impl<P: ValueType> LUDecomp<P> {
+    /// Get the memory used by the factorisation (using the
+    /// SuperLU *QuerySpace routines)
+    pub fn mem_usage(&self) -> MemUsage {
+        let mem_usage = unsafe { P::c_query_space(self.l().super_matrix(), self.u().super_matrix()) };
+        MemUsage::from_c_mem_usage(&mem_usage)
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,88 @@
+use super::*;
+use crate::c::options::ColumnPermPolicy;
+use crate::c::stat::CSuperluStat;
+use crate::c::value_type::Error;
+use crate::comp_col::CompColMatrix;
+use crate::dense::DenseMatrix;
+use crate::simple_driver::{SimpleError, SimpleSystem};
+use crate::test_utils::{user_guide_matrix, X_TRUE};
+
+/// The user guide system, with a right-hand side of ones
+fn user_guide_system() -> SimpleSystem<f64> {
+    let b = DenseMatrix::from_vectors(5, 1, vec![1.0; 5]);
+    SimpleSystem { a: user_guide_matrix(), b }
+}
+
+#[test]
+fn work_buffer_size() {
+    assert_eq!(WorkBuffer::new(16).size(), 16);
+    assert_eq!(WorkBuffer::new(17).size(), 24);
+    assert_eq!(WorkBuffer::new(0).size(), 0);
+}
+
+#[test]
+fn mem_usage_after_factorisation() {
+    let mut stat = CSuperluStat::new();
+    let solution = user_guide_system().solve(&mut stat, ColumnPermPolicy::Natural).unwrap();
+    let mem_usage = solution.lu.mem_usage();
+    assert!(mem_usage.for_lu > 0.0);
+    assert!(mem_usage.for_lu <= mem_usage.total_needed);
+}
+
+#[test]
+fn solve_with_work_buffer() {
+    let mut stat = CSuperluStat::new();
+    let solution = user_guide_system()
+        .solve_with_work(&mut stat, ColumnPermPolicy::Natural, WorkBuffer::new(1 << 16))
+        .unwrap();
+    for (value, expected) in solution.x.column(0).iter().zip(X_TRUE) {
+        assert!((value - expected).abs() < 1e-8);
+    }
+    assert!(solution.lu.mem_usage().total_needed <= (1 << 16) as f64);
+    assert!(!solution.ill_conditioned);
+}
+
+#[test]
+fn nearly_singular_system_is_solved() {
+    // U is non-singular, but the reciprocal condition number
+    // is less than machine precision
+    let delta = f64::EPSILON;
+    let a = CompColMatrix::from_vectors(2, vec![1.0, 1.0, 1.0, 1.0 + delta], vec![0, 1, 0, 1], vec![0, 2, 4]);
+    let b = DenseMatrix::from_vectors(2, 1, vec![2.0, 2.0 + delta]);
+    let mut stat = CSuperluStat::new();
+    let solution = SimpleSystem { a, b }
+        .solve_with_work(&mut stat, ColumnPermPolicy::Natural, WorkBuffer::new(1 << 16))
+        .unwrap();
+    assert!(solution.ill_conditioned);
+    assert!(solution.x.column(0).iter().all(|value| value.is_finite()));
+}
+
+#[test]
+fn work_buffer_too_small() {
+    let mut stat = CSuperluStat::new();
+    let result = user_guide_system().solve_with_work(&mut stat, ColumnPermPolicy::Natural, WorkBuffer::new(64));
+    assert!(matches!(result, Err(SimpleError::Other(Error::OutOfMemory { .. }))));
+}
+
+#[test]
+fn empty_work_buffer_is_rejected() {
+    let mut stat = CSuperluStat::new();
+    let result = user_guide_system().solve_with_work(&mut stat, ColumnPermPolicy::Natural, WorkBuffer::new(0));
+    assert!(matches!(
+        result,
+        Err(SimpleError::Other(Error::OutOfMemory { mem_alloc_at_failure: 0 }))
+    ));
+}
+
+#[test]
+fn estimate_memory_without_factorising() {
+    let mut stat = CSuperluStat::new();
+    let system = user_guide_system();
+    let estimate = system.estimate_memory(&mut stat, ColumnPermPolicy::Natural).unwrap();
+    assert!(estimate.total_needed > 0.0);
+    // The system can still be solved afterwards
+    let solution = system
+        .solve_with_work(&mut stat, ColumnPermPolicy::Natural, WorkBuffer::new(1 << 16))
+        .unwrap();
+    assert!((solution.x.column(0)[0] - X_TRUE[0]).abs() < 1e-8);
+}
@@ -0,0 +1,309 @@
+//! Solve double-precision systems using a single-precision
+//! factorisation
+//!
+//! The $L$ and $U$ factors usually take most of the memory (and
+//! time) in a sparse solve. A [MixedPrecisionSystem] converts
+//! $A$ and $B$ to single precision (f64 to f32, or `Complex<f64>`
+//! to `Complex<f32>`), factors and solves in single precision
+//! (using sgssv or cgssv), and then improves the solution using
+//! iterative refinement:
+//!
+//! 1. Compute the residual $R = B - AX$ in double precision.
+//! 2. Stop if the residual is small enough (see below).
+//! 3. Solve $AD = R$ using the single-precision factors, and
+//!    update the solution $X = X + D$.
+//!
+//! This is the same method as the LAPACK routine dsgesv. A
+//! column $x$ of the solution has converged when
+//! $$\lVert r \rVert_\infty \le \sqrt{n} \epsilon \lVert x
+//! \rVert_\infty \lVert A \rVert_\infty,$$ where $\epsilon$ is
+//! the double precision machine epsilon, so the solution is as
+//! accurate as one computed with a double-precision factorisation.
+//!
+//! Each step reduces the error by a factor of roughly
+//! $\kappa(A)\epsilon_\text{single}$, so refinement only
+//! converges when $A$ is not too ill-conditioned for single
+//! precision. If the largest relative residual does not halve in
+//! a step, if refinement has not converged after
+//! [MAX_REFINEMENT_STEPS] steps, or if $A$ is singular in single
+//! precision, $A$ is factored again in double precision (reusing
+//! the column permutation), and the double-precision solution is
+//! returned. The same happens without a single-precision
+//! factorisation if $A$ or $B$ contain values that are too large
+//! for single precision.
+//!
+
+use num::Complex;
+
+use crate::c::options::ColumnPermPolicy;
+use crate::c::stat::CSuperluStat;
+use crate::c::value_type::{Error, ValueType};
+use crate::comp_col::CompColMatrix;
+use crate::dense::DenseMatrix;
+use crate::factorization::Factorization;
+use crate::simple_driver::{ColumnPerm, SamePattern, SimpleError, SimpleSolution, SimpleSystem};
+
+/// The maximum number of refinement steps before falling back to
+/// a double-precision factorisation (the same as in dsgesv)
+pub const MAX_REFINEMENT_STEPS: usize = 30;
+
+/// Double-precision value types, which can be factored in the
+/// corresponding single-precision type
+pub trait Demote: ValueType {
+    /// The single-precision type
+    type Low: ValueType;
+
+    /// Round the value to single precision (values that are too
+    /// large become infinite)
+    fn demote(self) -> Self::Low;
+
+    /// Convert a single-precision value to double precision
+    fn promote(low: Self::Low) -> Self;
+}
+
+impl Demote for f64 {
+    type Low = f32;
+
+    fn demote(self) -> f32 {
+        self as f32
+    }
+
+    fn promote(low: f32) -> Self {
+        low as f64
+    }
+}
+
+impl Demote for Complex<f64> {
+    type Low = Complex<f32>;
+
+    fn demote(self) -> Complex<f32> {
+        Complex::new(self.re as f32, self.im as f32)
+    }
+
+    fn promote(low: Complex<f32>) -> Self {
+        Complex::new(low.re as f64, low.im as f64)
+    }
+}
+
+/// Defines a sparse linear system $AX = B$ in double precision,
+/// to be solved using a single-precision factorisation
+pub struct MixedPrecisionSystem<P: Demote> {
+    /// The (sparse) matrix $A$
+    pub a: CompColMatrix<P>,
+    /// The right-hand side(s) matrix $B$
+    pub b: DenseMatrix<P>,
+}
+
+/// The factorisation that was used for the solution
+pub enum MixedFactorization<P: Demote> {
+    /// Refinement converged using the single-precision
+    /// factorisation
+    Single(Factorization<P::Low>),
+    /// Refinement did not converge, and $A$ was factored in
+    /// double precision
+    Double(Factorization<P>),
+}
+
+/// The solution of a [MixedPrecisionSystem]
+pub struct MixedPrecisionSolution<P: Demote> {
+    /// The matrix $A$
+    pub a: CompColMatrix<P>,
+    /// The solution $X$
+    pub x: DenseMatrix<P>,
+    /// The factorisation of $A$
+    pub factorization: MixedFactorization<P>,
+    /// The number of refinement steps that were taken using the
+    /// single-precision factorisation (including steps taken
+    /// before falling back to double precision)
+    pub refinement_steps: usize,
+}
+
+impl<P: Demote> MixedPrecisionSolution<P> {
+    /// Check whether the single-precision factorisation was used
+    /// for the solution
+    pub fn is_single_precision(&self) -> bool {
+        matches!(self.factorization, MixedFactorization::Single(_))
+    }
+}
+
+impl<P: Demote> MixedPrecisionSystem<P> {
+    /// Solve the system using a single-precision factorisation
+    /// and iterative refinement, falling back to a
+    /// double-precision factorisation if refinement does not
+    /// converge (see [mixed_precision](crate::mixed_precision))
+    ///
+    /// The column permutation is chosen according to the policy,
+    /// in the same way as for
+    /// [SimpleSystem::solve](crate::simple_driver::SimpleSystem::solve).
+    ///
+    /// # Errors
+    ///
+    /// If $A$ is not square, or the number of rows of $B$ does
+    /// not match $A$, [Error::DimensionMismatch] is returned. If
+    /// $A$ is singular in double precision, the singular
+    /// double-precision factorisation is returned. Other errors
+    /// from the single-precision factorisation (for example,
+    /// [Error::OutOfMemory]) are returned without trying double
+    /// precision.
+    ///
+    pub fn solve(
+        self,
+        stat: &mut CSuperluStat,
+        column_perm_policy: ColumnPermPolicy,
+    ) -> Result<MixedPrecisionSolution<P>, SimpleError<P>> {
+        let MixedPrecisionSystem { a, b } = self;
+        if a.num_rows() != a.num_columns() || b.num_rows() != a.num_rows() {
+            return Err(SimpleError::Other(Error::DimensionMismatch));
+        }
+
+        let (a_low, b_low) = match (demote_comp_col(&a), demote_dense(&b)) {
+            (Some(a_low), Some(b_low)) => (a_low, b_low),
+            _ => {
+                let solution = SimpleSystem { a, b }.solve(stat, column_perm_policy)?;
+                return Ok(double_precision_solution(solution, 0));
+            }
+        };
+
+        let (mut x, factorization) = match (SimpleSystem { a: a_low, b: b_low }).solve(stat, column_perm_policy) {
+            Ok(solution) => (promote_dense(&solution.x), Factorization::from(solution)),
+            Err(SimpleError::Singular { column_perm, .. }) => {
+                return solve_double_precision(a, b, column_perm, 0, stat);
+            }
+            Err(SimpleError::Other(error)) => return Err(SimpleError::Other(error)),
+        };
+
+        let norm_a = a.norm_inf();
+        let tolerance = f64::EPSILON * (a.num_rows() as f64).sqrt();
+        let mut previous_error = f64::INFINITY;
+        let mut refinement_steps = 0;
+        loop {
+            let residual = residual(&a, &b, &x);
+            let error = relative_residual(&residual, &x, norm_a);
+            if error <= tolerance {
+                return Ok(MixedPrecisionSolution {
+                    a,
+                    x,
+                    factorization: MixedFactorization::Single(factorization),
+                    refinement_steps,
+                });
+            }
+            if refinement_steps == MAX_REFINEMENT_STEPS || error.is_nan() || error > 0.5 * previous_error {
+                break;
+            }
+            let Some(residual_low) = demote_dense(&residual) else {
+                break;
+            };
+            let correction = factorization.solve(residual_low, stat).map_err(SimpleError::Other)?;
+            for col in 0..x.num_columns() {
+                for (value, &delta) in x.column_mut(col).iter_mut().zip(correction.column(col)) {
+                    *value = *value + P::promote(delta);
+                }
+            }
+            previous_error = error;
+            refinement_steps += 1;
+        }
+
+        let Factorization { column_perm, .. } = factorization;
+        solve_double_precision(a, b, column_perm, refinement_steps, stat)
+    }
+}
+
+/// Factor and solve in double precision, using the column
+/// permutation from the single-precision factorisation
+fn solve_double_precision<P: Demote>(
+    a: CompColMatrix<P>,
+    b: DenseMatrix<P>,
+    column_perm: ColumnPerm,
+    refinement_steps: usize,
+    stat: &mut CSuperluStat,
+) -> Result<MixedPrecisionSolution<P>, SimpleError<P>> {
+    let solution = SamePattern { a, b, column_perm }.solve(stat)?;
+    Ok(double_precision_solution(solution, refinement_steps))
+}
+
+fn double_precision_solution<P: Demote>(
+    solution: SimpleSolution<P>,
+    refinement_steps: usize,
+) -> MixedPrecisionSolution<P> {
+    let SimpleSolution { a, x, lu, column_perm, row_perm, .. } = solution;
+    let factorization = Factorization::from_parts(&a, lu, column_perm, row_perm);
+    MixedPrecisionSolution {
+        a,
+        x,
+        factorization: MixedFactorization::Double(factorization),
+        refinement_steps,
+    }
+}
+
+/// Returns true if the value did not overflow when it was
+/// converted to single precision
+fn is_finite<Q: ValueType>(value: Q) -> bool {
+    value.magnitude().is_finite()
+}
+
+/// Convert a compressed-column matrix to single precision, or
+/// return None if a value is too large
+fn demote_comp_col<P: Demote>(a: &CompColMatrix<P>) -> Option<CompColMatrix<P::Low>> {
+    let non_zero_values: Vec<P::Low> = a.non_zero_values().iter().map(|&value| value.demote()).collect();
+    if !non_zero_values.iter().all(|&value| is_finite(value)) {
+        return None;
+    }
+    Some(CompColMatrix::from_vectors(
+        a.num_rows(),
+        non_zero_values,
+        a.row_indices().to_vec(),
+        a.column_offsets().to_vec(),
+    ))
+}
+
+/// Convert a dense matrix to single precision, or return None if
+/// a value is too large
+fn demote_dense<P: Demote>(b: &DenseMatrix<P>) -> Option<DenseMatrix<P::Low>> {
+    let mut values = Vec::with_capacity(b.num_rows() * b.num_columns());
+    for col in 0..b.num_columns() {
+        values.extend(b.column(col).iter().map(|&value| value.demote()));
+    }
+    if !values.iter().all(|&value| is_finite(value)) {
+        return None;
+    }
+    Some(DenseMatrix::from_vectors(b.num_rows(), b.num_columns(), values))
+}
+
+fn promote_dense<P: Demote>(x: &DenseMatrix<P::Low>) -> DenseMatrix<P> {
+    let mut values = Vec::with_capacity(x.num_rows() * x.num_columns());
+    for col in 0..x.num_columns() {
+        values.extend(x.column(col).iter().map(|&value| P::promote(value)));
+    }
+    DenseMatrix::from_vectors(x.num_rows(), x.num_columns(), values)
+}
+
+/// Compute $R = B - AX$
+fn residual<P: ValueType>(a: &CompColMatrix<P>, b: &DenseMatrix<P>, x: &DenseMatrix<P>) -> DenseMatrix<P> {
+    let mut values = Vec::with_capacity(b.num_rows() * b.num_columns());
+    for rhs in 0..b.num_columns() {
+        let ax = a.multiply_vector(x.column(rhs));
+        values.extend(b.column(rhs).iter().zip(ax).map(|(&b_value, ax_value)| b_value - ax_value));
+    }
+    DenseMatrix::from_vectors(b.num_rows(), b.num_columns(), values)
+}
+
+/// Compute the largest $\lVert r \rVert_\infty / (\lVert x
+/// \rVert_\infty \lVert A \rVert_\infty)$ over the columns,
+/// which is NaN if any of the values are NaN
+fn relative_residual<P: ValueType>(r: &DenseMatrix<P>, x: &DenseMatrix<P>, norm_a: f64) -> f64 {
+    let max = |max: f64, value: f64| if value > max || value.is_nan() { value } else { max };
+    let norm = |values: &[P]| values.iter().map(|value| value.magnitude()).fold(0.0, max);
+    (0..r.num_columns())
+        .map(|col| {
+            let norm_r = norm(r.column(col));
+            if norm_r == 0.0 {
+                0.0
+            } else {
+                norm_r / (norm(x.column(col)) * norm_a)
+            }
+        })
+        .fold(0.0, max)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,121 @@
+use super::*;
+use crate::test_utils::{user_guide_matrix, X_TRUE};
+
+fn assert_close(x: &[f64], x_true: &[f64], tol: f64) {
+    for (value, expected) in x.iter().zip(x_true) {
+        assert!((value - expected).abs() <= tol * expected.abs(), "{value} != {expected}");
+    }
+}
+
+/// The 2x2 matrix [1 1; 1 1 + delta]
+fn nearly_singular_matrix(delta: f64) -> CompColMatrix<f64> {
+    CompColMatrix::from_vectors(2, vec![1.0, 1.0, 1.0, 1.0 + delta], vec![0, 1, 0, 1], vec![0, 2, 4])
+}
+
+#[test]
+fn refinement_reaches_double_precision() {
+    let a = user_guide_matrix();
+    let b = DenseMatrix::from_vectors(5, 1, vec![1.0; 5]);
+    let mut stat = CSuperluStat::new();
+    let solution = MixedPrecisionSystem { a, b }
+        .solve(&mut stat, ColumnPermPolicy::Natural)
+        .unwrap();
+    assert!(solution.is_single_precision());
+    assert!(solution.refinement_steps > 0);
+    assert_close(solution.x.column(0), &X_TRUE, 1e-14);
+}
+
+#[test]
+fn multiple_right_hand_sides() {
+    let a = user_guide_matrix();
+    let b = DenseMatrix::from_vectors(5, 2, [vec![1.0; 5], vec![2.0; 5]].concat());
+    let mut stat = CSuperluStat::new();
+    let solution = MixedPrecisionSystem { a, b }
+        .solve(&mut stat, ColumnPermPolicy::Natural)
+        .unwrap();
+    assert!(solution.is_single_precision());
+    let x_true_2: Vec<f64> = X_TRUE.iter().map(|value| 2.0 * value).collect();
+    assert_close(solution.x.column(0), &X_TRUE, 1e-14);
+    assert_close(solution.x.column(1), &x_true_2, 1e-14);
+}
+
+#[test]
+fn complex_refinement() {
+    // [2 i; -i 3] x = [2 + i, 3 - i] has the solution x = [1, 1]
+    let a = CompColMatrix::from_vectors(
+        2,
+        vec![Complex::new(2.0, 0.0), Complex::new(0.0, -1.0), Complex::new(0.0, 1.0), Complex::new(3.0, 0.0)],
+        vec![0, 1, 0, 1],
+        vec![0, 2, 4],
+    );
+    let b = DenseMatrix::from_vectors(2, 1, vec![Complex::new(2.0, 1.0), Complex::new(3.0, -1.0)]);
+    let mut stat = CSuperluStat::new();
+    let solution = MixedPrecisionSystem { a, b }
+        .solve(&mut stat, ColumnPermPolicy::Natural)
+        .unwrap();
+    assert!(solution.is_single_precision());
+    for value in solution.x.column(0) {
+        assert!((value - Complex::new(1.0, 0.0)).norm() < 1e-15);
+    }
+}
+
+#[test]
+fn singular_in_single_precision() {
+    // 1 + 1e-9 rounds to 1 in single precision
+    let a = nearly_singular_matrix(1e-9);
+    let b = DenseMatrix::from_vectors(2, 1, vec![2.0, 2.0 + 1e-9]);
+    let mut stat = CSuperluStat::new();
+    let solution = MixedPrecisionSystem { a, b }
+        .solve(&mut stat, ColumnPermPolicy::Natural)
+        .unwrap();
+    assert!(!solution.is_single_precision());
+    assert_eq!(solution.refinement_steps, 0);
+    for value in solution.x.column(0) {
+        assert!((value - 1.0).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn ill_conditioned_solution_is_accurate() {
+    // Close to the limit of single precision, refinement either
+    // converges or falls back to double precision
+    let a = nearly_singular_matrix(3e-7);
+    let b = DenseMatrix::from_vectors(2, 1, vec![2.0, 2.0 + 3e-7]);
+    let mut stat = CSuperluStat::new();
+    let solution = MixedPrecisionSystem { a, b }
+        .solve(&mut stat, ColumnPermPolicy::Natural)
+        .unwrap();
+    for value in solution.x.column(0) {
+        assert!((value - 1.0).abs() < 1e-8);
+    }
+}
+
+#[test]
+fn too_large_for_single_precision() {
+    let a = CompColMatrix::from_vectors(2, vec![1e300, 1.0], vec![0, 1], vec![0, 1, 2]);
+    let b = DenseMatrix::from_vectors(2, 1, vec![1e300, 1.0]);
+    let mut stat = CSuperluStat::new();
+    let solution = MixedPrecisionSystem { a, b }
+        .solve(&mut stat, ColumnPermPolicy::Natural)
+        .unwrap();
+    assert!(!solution.is_single_precision());
+    assert_eq!(solution.x.column(0), &[1.0, 1.0]);
+}
+
+#[test]
+fn singular_in_double_precision() {
+    let a = nearly_singular_matrix(0.0);
+    let b = DenseMatrix::from_vectors(2, 1, vec![1.0, 2.0]);
+    let mut stat = CSuperluStat::new();
+    let result = MixedPrecisionSystem { a, b }.solve(&mut stat, ColumnPermPolicy::Natural);
+    assert!(matches!(result, Err(SimpleError::Singular { singular_column: 1, .. })));
+}
+
+#[test]
+fn rectangular_matrix_is_an_error() {
+    let a = CompColMatrix::from_vectors(3, vec![1.0, 1.0], vec![0, 1], vec![0, 1, 2]);
+    let b = DenseMatrix::from_vectors(3, 1, vec![1.0; 3]);
+    let mut stat = CSuperluStat::new();
+    let result = MixedPrecisionSystem { a, b }.solve(&mut stat, ColumnPermPolicy::Natural);
+    assert!(matches!(result, Err(SimpleError::Other(Error::DimensionMismatch))));
+}
@@ -0,0 +1,458 @@
+//! Column orderings computed outside SuperLU
+//!
+//! The column permutation $P_c$ determines how much fill-in
+//! occurs in the factors $L$ and $U$. SuperLU provides several
+//! orderings (see [ColumnPermPolicy]), but a better ordering can
+//! often be found using knowledge about where the matrix comes
+//! from. A [ColumnOrdering] computes a column permutation from
+//! the pattern of $A$ (the positions of the stored elements).
+//! It can be used as a policy, with [ColumnPermPolicy::Custom],
+//! in which case the solver calls it and passes the permutation
+//! to SuperLU:
+//!
+//! ```ignore
+//! let policy = ColumnPermPolicy::Custom(Box::new(MyOrdering::new()));
+//! let solution = SimpleSystem { a, b }.solve(&mut stat, policy)?;
+//! ```
+//!
+//! Any function or closure that takes a [SparsityPattern] and
+//! returns a [ColumnPerm] is a ColumnOrdering.
+//!
+//! SuperLU still postorders the columns using the column
+//! elimination tree, so the permutation in the solution may not
+//! be exactly the one returned by the ordering.
+//!
+//! Two orderings are implemented in this module, both of which
+//! work on the graph of $A^T + A$ (so the matrix must be square):
+//!
+//! * [ReverseCuthillMcKee] reduces the bandwidth of the matrix.
+//!   Starting from a pseudo-peripheral node, the nodes are
+//!   numbered in breadth-first order (visiting neighbours with
+//!   low degree first), and the order is reversed.
+//! * [NestedDissection] finds a small set of nodes (a separator)
+//!   which splits the graph into two parts, orders the parts
+//!   recursively, and numbers the separator last. The separator
+//!   is the middle level of a level structure rooted at a
+//!   pseudo-peripheral node, trimmed to the nodes that connect
+//!   both parts. For matrices from 2D and 3D meshes, nested
+//!   dissection usually gives much less fill-in than the
+//!   minimum degree orderings.
+//!
+//! The permutations can also be computed directly, for example
+//! to use with [SamePattern](crate::simple_driver::SamePattern):
+//!
+//! ```ignore
+//! let column_perm = NestedDissection::new().column_perm(&a.pattern());
+//! ```
+//!
+//! [ColumnPermPolicy]: crate::c::options::ColumnPermPolicy
+//! [ColumnPermPolicy::Custom]: crate::c::options::ColumnPermPolicy::Custom
+//!
+
+use std::collections::HashSet;
+
+use crate::c::options::CColumnOrdering;
+use crate::simple_driver::ColumnPerm;
+
+/// The pattern of the stored elements in a compressed-column
+/// matrix
+#[derive(Debug, Clone, Copy)]
+pub struct SparsityPattern<'a> {
+    num_rows: usize,
+    column_offsets: &'a [i32],
+    row_indices: &'a [i32],
+}
+
+impl<'a> SparsityPattern<'a> {
+    /// Make a pattern from the compressed-column offsets and row
+    /// indices
+    ///
+    /// # Panics
+    ///
+    /// If column_offsets is empty, or its last element is not the
+    /// number of row indices.
+    ///
+    pub fn new(num_rows: usize, column_offsets: &'a [i32], row_indices: &'a [i32]) -> Self {
+        assert!(
+            column_offsets.last().map(|&end| end as usize) == Some(row_indices.len()),
+            "Column offsets do not match the row indices"
+        );
+        Self {
+            num_rows,
+            column_offsets,
+            row_indices,
+        }
+    }
+
+    /// Get the number of rows in the matrix
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    /// Get the number of columns in the matrix
+    pub fn num_columns(&self) -> usize {
+        self.column_offsets.len() - 1
+    }
+
+    /// Get the number of stored elements
+    pub fn num_non_zeros(&self) -> usize {
+        self.row_indices.len()
+    }
+
+    /// Get the column offsets (the start of each column in the
+    /// row indices, followed by the number of row indices)
+    pub fn column_offsets(&self) -> &'a [i32] {
+        self.column_offsets
+    }
+
+    /// Get the row indices of the stored elements
+    pub fn row_indices(&self) -> &'a [i32] {
+        self.row_indices
+    }
+
+    /// Get the row indices of the stored elements in a column
+    ///
+    /// # Panics
+    ///
+    /// If the column index is out of range.
+    pub fn column(&self, col: usize) -> &'a [i32] {
+        let start = self.column_offsets[col] as usize;
+        let end = self.column_offsets[col + 1] as usize;
+        &self.row_indices[start..end]
+    }
+}
+
+/// An algorithm that computes a column permutation from the
+/// pattern of a matrix
+///
+/// Every ColumnOrdering is also a [CColumnOrdering], which is
+/// how the solver passes the permutation to SuperLU.
+pub trait ColumnOrdering: CColumnOrdering {
+    /// Compute the column permutation $P_c$ for a matrix with
+    /// the given pattern. Element j of the permutation is the
+    /// position of column j in $AP_c$.
+    fn column_perm(&self, pattern: &SparsityPattern) -> ColumnPerm;
+}
+
+impl<F: Fn(&SparsityPattern) -> ColumnPerm> ColumnOrdering for F {
+    fn column_perm(&self, pattern: &SparsityPattern) -> ColumnPerm {
+        self(pattern)
+    }
+}
+
+impl<T: ColumnOrdering + ?Sized> CColumnOrdering for T {
+    fn c_column_perm(&self, num_rows: usize, column_offsets: &[i32], row_indices: &[i32]) -> Vec<i32> {
+        let pattern = SparsityPattern::new(num_rows, column_offsets, row_indices);
+        self.column_perm(&pattern).as_slice().to_vec()
+    }
+}
+
+/// Make a column permutation from the list of columns in the
+/// order that they appear in $AP_c$
+fn perm_from_order(order: &[usize]) -> ColumnPerm {
+    let mut column_perm = vec![0; order.len()];
+    for (position, &col) in order.iter().enumerate() {
+        column_perm[col] = position as i32;
+    }
+    ColumnPerm::new(column_perm, order.len()).expect("Ordering did not visit every column once")
+}
+
+/// The graph of $A^T + A$, without self-loops, stored as
+/// adjacency lists
+struct Graph {
+    offsets: Vec<usize>,
+    neighbours: Vec<usize>,
+}
+
+impl Graph {
+    /// Make the graph of $A^T + A$ from the pattern of a square
+    /// matrix
+    fn from_pattern(pattern: &SparsityPattern) -> Self {
+        assert!(
+            pattern.num_rows() == pattern.num_columns(),
+            "The ordering requires a square matrix"
+        );
+        let size = pattern.num_columns();
+        let mut adjacency = vec![Vec::new(); size];
+        for col in 0..size {
+            for &row in pattern.column(col) {
+                let row = row as usize;
+                if row != col {
+                    adjacency[row].push(col);
+                    adjacency[col].push(row);
+                }
+            }
+        }
+        let mut offsets = Vec::with_capacity(size + 1);
+        let mut neighbours = Vec::new();
+        offsets.push(0);
+        for mut list in adjacency {
+            list.sort_unstable();
+            list.dedup();
+            neighbours.extend(list);
+            offsets.push(neighbours.len());
+        }
+        Self { offsets, neighbours }
+    }
+
+    fn num_nodes(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    fn neighbours(&self, node: usize) -> &[usize] {
+        &self.neighbours[self.offsets[node]..self.offsets[node + 1]]
+    }
+
+    fn degree(&self, node: usize) -> usize {
+        self.offsets[node + 1] - self.offsets[node]
+    }
+
+    /// Get the breadth-first level structure rooted at a node,
+    /// only visiting nodes with the same label as the root
+    fn level_structure(&self, root: usize, labels: &[usize]) -> Vec<Vec<usize>> {
+        let label = labels[root];
+        let mut visited = HashSet::from([root]);
+        let mut levels = vec![vec![root]];
+        loop {
+            let mut next = Vec::new();
+            for &node in levels.last().unwrap() {
+                for &neighbour in self.neighbours(node) {
+                    if labels[neighbour] == label && visited.insert(neighbour) {
+                        next.push(neighbour);
+                    }
+                }
+            }
+            if next.is_empty() {
+                return levels;
+            }
+            levels.push(next);
+        }
+    }
+
+    /// Find a pseudo-peripheral node (a node with approximately
+    /// the largest eccentricity) in the connected set containing
+    /// start, using the algorithm of George and Liu. Returns the
+    /// node and its level structure.
+    fn pseudo_peripheral_node(&self, start: usize, labels: &[usize]) -> (usize, Vec<Vec<usize>>) {
+        let mut root = start;
+        let mut levels = self.level_structure(root, labels);
+        loop {
+            let candidate = *levels
+                .last()
+                .unwrap()
+                .iter()
+                .min_by_key(|&&node| self.degree(node))
+                .unwrap();
+            let candidate_levels = self.level_structure(candidate, labels);
+            if candidate_levels.len() <= levels.len() {
+                return (root, levels);
+            }
+            root = candidate;
+            levels = candidate_levels;
+        }
+    }
+}
+
+/// The reverse Cuthill-McKee ordering, which reduces the
+/// bandwidth of $A^T + A$
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ReverseCuthillMcKee;
+
+impl ReverseCuthillMcKee {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl ColumnOrdering for ReverseCuthillMcKee {
+    /// # Panics
+    ///
+    /// If the matrix is not square.
+    ///
+    fn column_perm(&self, pattern: &SparsityPattern) -> ColumnPerm {
+        let graph = Graph::from_pattern(pattern);
+        let size = graph.num_nodes();
+        let labels = vec![0; size];
+        let mut visited = vec![false; size];
+        let mut order = Vec::with_capacity(size);
+        // Start each connected component at the unvisited node
+        // with the lowest degree
+        let mut starts: Vec<usize> = (0..size).collect();
+        starts.sort_by_key(|&node| graph.degree(node));
+        for start in starts {
+            if visited[start] {
+                continue;
+            }
+            let (root, _) = graph.pseudo_peripheral_node(start, &labels);
+            let first = order.len();
+            order.push(root);
+            visited[root] = true;
+            let mut next = first;
+            while next < order.len() {
+                let node = order[next];
+                next += 1;
+                let mut neighbours: Vec<usize> = graph
+                    .neighbours(node)
+                    .iter()
+                    .copied()
+                    .filter(|&neighbour| !visited[neighbour])
+                    .collect();
+                neighbours.sort_by_key(|&neighbour| graph.degree(neighbour));
+                for neighbour in neighbours {
+                    visited[neighbour] = true;
+                    order.push(neighbour);
+                }
+            }
+        }
+        order.reverse();
+        perm_from_order(&order)
+    }
+}
+
+/// The nested dissection ordering on the graph of $A^T + A$,
+/// using level-structure separators
+#[derive(Debug, Clone, Copy)]
+pub struct NestedDissection {
+    leaf_size: usize,
+}
+
+impl Default for NestedDissection {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NestedDissection {
+    /// Make a nested dissection ordering, which stops dividing
+    /// the graph when a part has at most 16 nodes
+    pub fn new() -> Self {
+        Self { leaf_size: 16 }
+    }
+
+    /// Set the largest part that is not divided further. The
+    /// nodes in these parts are numbered in their original order.
+    pub fn leaf_size(mut self, leaf_size: usize) -> Self {
+        self.leaf_size = leaf_size.max(1);
+        self
+    }
+
+    /// Order the nodes which have the given label, appending
+    /// them to the order. New labels are taken from next_label.
+    fn dissect(
+        &self,
+        graph: &Graph,
+        mut nodes: Vec<usize>,
+        labels: &mut [usize],
+        next_label: &mut usize,
+        order: &mut Vec<usize>,
+    ) {
+        if nodes.len() <= self.leaf_size {
+            nodes.sort_unstable();
+            order.extend(nodes);
+            return;
+        }
+
+        // Order each connected component separately
+        let (_, levels) = graph.pseudo_peripheral_node(nodes[0], labels);
+        let component_size: usize = levels.iter().map(Vec::len).sum();
+        if component_size < nodes.len() {
+            let component: Vec<usize> = levels.into_iter().flatten().collect();
+            let component_label = new_label(next_label);
+            for &node in &component {
+                labels[node] = component_label;
+            }
+            let rest_label = new_label(next_label);
+            let rest: Vec<usize> = nodes.into_iter().filter(|&node| labels[node] != component_label).collect();
+            for &node in &rest {
+                labels[node] = rest_label;
+            }
+            self.dissect(graph, component, labels, next_label, order);
+            self.dissect(graph, rest, labels, next_label, order);
+            return;
+        }
+
+        // A graph with fewer than three levels (such as a
+        // clique) has no useful level-structure separator
+        if levels.len() < 3 {
+            nodes.sort_unstable();
+            order.extend(nodes);
+            return;
+        }
+
+        // Use the level that splits the other nodes most evenly
+        // as the separator (not the first or last level)
+        let mut before = 0;
+        let mut middle = 1;
+        let mut best = usize::MAX;
+        for (index, level) in levels.iter().enumerate().take(levels.len() - 1) {
+            let after = nodes.len() - before - level.len();
+            if index > 0 && before.max(after) < best {
+                best = before.max(after);
+                middle = index;
+            }
+            before += level.len();
+        }
+        let first_label = new_label(next_label);
+        let second_label = new_label(next_label);
+        let separator_label = new_label(next_label);
+        for (index, level) in levels.iter().enumerate() {
+            let label = match index.cmp(&middle) {
+                std::cmp::Ordering::Less => first_label,
+                std::cmp::Ordering::Equal => separator_label,
+                std::cmp::Ordering::Greater => second_label,
+            };
+            for &node in level {
+                labels[node] = label;
+            }
+        }
+
+        // Move separator nodes that are only adjacent to one of
+        // the parts into that part
+        let mut separator = Vec::new();
+        for &node in &levels[middle] {
+            let adjacent = |label| graph.neighbours(node).iter().any(|&neighbour| labels[neighbour] == label);
+            if !adjacent(second_label) {
+                labels[node] = first_label;
+            } else if !adjacent(first_label) {
+                labels[node] = second_label;
+            } else {
+                separator.push(node);
+            }
+        }
+
+        let (first, second): (Vec<usize>, Vec<usize>) = levels
+            .into_iter()
+            .flatten()
+            .filter(|&node| labels[node] != separator_label)
+            .partition(|&node| labels[node] == first_label);
+        self.dissect(graph, first, labels, next_label, order);
+        self.dissect(graph, second, labels, next_label, order);
+        separator.sort_unstable();
+        order.extend(separator);
+    }
+}
+
+/// Get a new label for a set of nodes
+fn new_label(next_label: &mut usize) -> usize {
+    *next_label += 1;
+    *next_label
+}
+
+impl ColumnOrdering for NestedDissection {
+    /// # Panics
+    ///
+    /// If the matrix is not square.
+    ///
+    fn column_perm(&self, pattern: &SparsityPattern) -> ColumnPerm {
+        let graph = Graph::from_pattern(pattern);
+        let size = graph.num_nodes();
+        let mut labels = vec![0; size];
+        let mut next_label = 0;
+        let mut order = Vec::with_capacity(size);
+        self.dissect(&graph, (0..size).collect(), &mut labels, &mut next_label, &mut order);
+        perm_from_order(&order)
+    }
+}
+
+#[cfg(test)]
+mod tests;
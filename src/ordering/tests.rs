@@ -0,0 +1,277 @@
+use super::*;
+use std::cell::Cell;
+use std::collections::HashSet;
+use std::rc::Rc;
+
+use crate::c::options::ColumnPermPolicy;
+use crate::c::stat::CSuperluStat;
+use crate::c::value_type::Error;
+use crate::comp_col::CompColMatrix;
+use crate::dense::DenseMatrix;
+use crate::simple_driver::{SimpleError, SimpleSystem};
+use crate::test_utils::tridiagonal;
+
+/// Reverses the column order, and counts the calls
+struct Reverse {
+    calls: Rc<Cell<usize>>,
+}
+
+impl ColumnOrdering for Reverse {
+    fn column_perm(&self, pattern: &SparsityPattern) -> ColumnPerm {
+        self.calls.set(self.calls.get() + 1);
+        let n = pattern.num_columns() as i32;
+        ColumnPerm::new((0..n).map(|col| n - 1 - col).collect(), pattern.num_columns()).unwrap()
+    }
+}
+
+#[test]
+fn pattern_of_comp_col_matrix() {
+    let a = tridiagonal();
+    let pattern = a.pattern();
+    assert_eq!(pattern.num_rows(), 3);
+    assert_eq!(pattern.num_columns(), 3);
+    assert_eq!(pattern.num_non_zeros(), 7);
+    assert_eq!(pattern.column(1), &[0, 1, 2]);
+    assert_eq!(pattern.column(2), &[1, 2]);
+}
+
+#[test]
+fn custom_ordering_is_used_by_solver() {
+    let calls = Rc::new(Cell::new(0));
+    let ordering = Reverse { calls: calls.clone() };
+    let mut stat = CSuperluStat::new();
+    let b = DenseMatrix::from_vectors(3, 1, vec![5.0, 6.0, 5.0]);
+    let solution = SimpleSystem { a: tridiagonal(), b }
+        .solve(&mut stat, ColumnPermPolicy::Custom(Box::new(ordering)))
+        .unwrap();
+    assert_eq!(calls.get(), 1);
+    for value in solution.x.column(0) {
+        assert!((value - 1.0).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn closure_ordering() {
+    let mut stat = CSuperluStat::new();
+    let b = DenseMatrix::from_vectors(3, 1, vec![5.0, 6.0, 5.0]);
+    let natural = |pattern: &SparsityPattern| {
+        ColumnPerm::new((0..pattern.num_columns() as i32).collect(), pattern.num_columns()).unwrap()
+    };
+    let solution = SimpleSystem { a: tridiagonal(), b }
+        .solve(&mut stat, ColumnPermPolicy::Custom(Box::new(natural)))
+        .unwrap();
+    for value in solution.x.column(0) {
+        assert!((value - 1.0).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn invalid_custom_ordering_is_an_error() {
+    let mut stat = CSuperluStat::new();
+    let b = DenseMatrix::from_vectors(3, 1, vec![5.0, 6.0, 5.0]);
+    let invalid = |_: &SparsityPattern| unsafe { ColumnPerm::from_raw(vec![0, 0, 1]) };
+    let result = SimpleSystem { a: tridiagonal(), b }.solve(&mut stat, ColumnPermPolicy::Custom(Box::new(invalid)));
+    assert!(matches!(result, Err(SimpleError::Other(Error::PermutationError))));
+}
+
+/// The pattern of the 5-point Laplacian on a grid, with the
+/// nodes numbered in row-major order
+fn grid(size: usize) -> (Vec<i32>, Vec<i32>) {
+    let mut column_offsets = vec![0];
+    let mut row_indices = Vec::new();
+    for col in 0..size * size {
+        let (i, j) = (col / size, col % size);
+        if i > 0 {
+            row_indices.push((col - size) as i32);
+        }
+        if j > 0 {
+            row_indices.push((col - 1) as i32);
+        }
+        row_indices.push(col as i32);
+        if j + 1 < size {
+            row_indices.push((col + 1) as i32);
+        }
+        if i + 1 < size {
+            row_indices.push((col + size) as i32);
+        }
+        column_offsets.push(row_indices.len() as i32);
+    }
+    (column_offsets, row_indices)
+}
+
+/// The symmetric pattern with the given edges and a full
+/// diagonal
+fn from_edges(size: usize, edges: &[(usize, usize)]) -> (Vec<i32>, Vec<i32>) {
+    let mut columns: Vec<Vec<i32>> = (0..size).map(|col| vec![col as i32]).collect();
+    for &(a, b) in edges {
+        columns[a].push(b as i32);
+        columns[b].push(a as i32);
+    }
+    let mut column_offsets = vec![0];
+    let mut row_indices = Vec::new();
+    for mut column in columns {
+        column.sort_unstable();
+        row_indices.extend(column);
+        column_offsets.push(row_indices.len() as i32);
+    }
+    (column_offsets, row_indices)
+}
+
+/// Get the bandwidth of the symmetric pattern after permuting
+/// the rows and columns
+fn bandwidth(pattern: &SparsityPattern, perm: &ColumnPerm) -> usize {
+    let perm = perm.as_slice();
+    (0..pattern.num_columns())
+        .flat_map(|col| {
+            pattern
+                .column(col)
+                .iter()
+                .map(move |&row| (perm[row as usize] - perm[col]).unsigned_abs() as usize)
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+/// Count the non-zeros in the Cholesky factor of the symmetric
+/// pattern after permuting the rows and columns
+fn cholesky_fill(pattern: &SparsityPattern, perm: &ColumnPerm) -> usize {
+    let size = pattern.num_columns();
+    let perm = perm.as_slice();
+    let mut rows: Vec<HashSet<usize>> = vec![HashSet::new(); size];
+    for col in 0..size {
+        for &row in pattern.column(col) {
+            let (row, col) = (perm[row as usize] as usize, perm[col] as usize);
+            if row > col {
+                rows[col].insert(row);
+            }
+        }
+    }
+    let mut fill = 0;
+    for col in 0..size {
+        let below: Vec<usize> = rows[col].iter().copied().collect();
+        fill += below.len();
+        if let Some(&parent) = below.iter().min() {
+            for row in below {
+                if row != parent {
+                    rows[parent].insert(row);
+                }
+            }
+        }
+    }
+    fill
+}
+
+#[test]
+fn reverse_cuthill_mckee_reduces_bandwidth() {
+    // Number the grid in a scrambled order
+    let size = 12;
+    let (column_offsets, row_indices) = grid(size);
+    let n = size * size;
+    let scramble: Vec<usize> = (0..n).map(|node| node * 37 % n).collect();
+    let mut scrambled = vec![Vec::new(); n];
+    for col in 0..n {
+        let start = column_offsets[col] as usize;
+        let end = column_offsets[col + 1] as usize;
+        for &row in &row_indices[start..end] {
+            scrambled[scramble[col]].push(scramble[row as usize] as i32);
+        }
+    }
+    let mut column_offsets = vec![0];
+    let mut row_indices = Vec::new();
+    for mut column in scrambled {
+        column.sort_unstable();
+        row_indices.extend(column);
+        column_offsets.push(row_indices.len() as i32);
+    }
+    let pattern = SparsityPattern::new(n, &column_offsets, &row_indices);
+    let natural = ColumnPerm::new((0..n as i32).collect(), n).unwrap();
+    let rcm = ReverseCuthillMcKee::new().column_perm(&pattern);
+    assert!(bandwidth(&pattern, &natural) > 2 * size);
+    assert!(bandwidth(&pattern, &rcm) <= size + 1);
+}
+
+#[test]
+fn reverse_cuthill_mckee_orders_a_path() {
+    // A path 0 - 2 - 4 - 1 - 3
+    let (column_offsets, row_indices) = from_edges(5, &[(0, 2), (2, 4), (4, 1), (1, 3)]);
+    let pattern = SparsityPattern::new(5, &column_offsets, &row_indices);
+    let rcm = ReverseCuthillMcKee::new().column_perm(&pattern);
+    assert_eq!(bandwidth(&pattern, &rcm), 1);
+}
+
+#[test]
+fn nested_dissection_reduces_fill() {
+    let size = 20;
+    let n = size * size;
+    let (column_offsets, row_indices) = grid(size);
+    let pattern = SparsityPattern::new(n, &column_offsets, &row_indices);
+    let natural = ColumnPerm::new((0..n as i32).collect(), n).unwrap();
+    let rcm = ReverseCuthillMcKee::new().column_perm(&pattern);
+    let nd = NestedDissection::new().column_perm(&pattern);
+    let natural_fill = cholesky_fill(&pattern, &natural);
+    assert!(cholesky_fill(&pattern, &rcm) <= natural_fill);
+    assert!(cholesky_fill(&pattern, &nd) < natural_fill * 3 / 4);
+}
+
+#[test]
+fn nested_dissection_numbers_separator_last() {
+    // Two 3x3 grids joined through a single node (numbered 0),
+    // which is the only sensible separator
+    let edges = [
+        (1, 2), (2, 3), (4, 5), (5, 6), (7, 8), (8, 9),
+        (1, 4), (4, 7), (2, 5), (5, 8), (3, 6), (6, 9),
+        (10, 11), (11, 12), (13, 14), (14, 15), (16, 17), (17, 18),
+        (10, 13), (13, 16), (11, 14), (14, 17), (12, 15), (15, 18),
+        (0, 9), (0, 10),
+    ];
+    let n = 19;
+    let (column_offsets, row_indices) = from_edges(n, &edges);
+    let pattern = SparsityPattern::new(n, &column_offsets, &row_indices);
+    let nd = NestedDissection::new().leaf_size(9).column_perm(&pattern);
+    assert_eq!(nd.as_slice()[0], n as i32 - 1);
+}
+
+#[test]
+fn nested_dissection_of_disconnected_graph() {
+    // Diagonal matrix: no edges at all
+    let n = 40;
+    let column_offsets: Vec<i32> = (0..=n as i32).collect();
+    let row_indices: Vec<i32> = (0..n as i32).collect();
+    let pattern = SparsityPattern::new(n, &column_offsets, &row_indices);
+    let nd = NestedDissection::new().leaf_size(4).column_perm(&pattern);
+    assert_eq!(nd.as_slice().len(), n);
+}
+
+#[test]
+fn orderings_solve_grid_system() {
+    let size = 8;
+    let n = size * size;
+    let (column_offsets, row_indices) = grid(size);
+    let values: Vec<f64> = (0..n)
+        .flat_map(|col| {
+            let start = column_offsets[col] as usize;
+            let end = column_offsets[col + 1] as usize;
+            row_indices[start..end]
+                .iter()
+                .map(move |&row| if row as usize == col { 4.0 } else { -1.0 })
+        })
+        .collect();
+    let orderings: Vec<Box<dyn ColumnOrdering>> =
+        vec![Box::new(ReverseCuthillMcKee::new()), Box::new(NestedDissection::new().leaf_size(4))];
+    for ordering in orderings {
+        let a = CompColMatrix::from_vectors(n, values.clone(), row_indices.clone(), column_offsets.clone());
+        let b = DenseMatrix::from_vectors(n, 1, vec![1.0; n]);
+        let mut stat = CSuperluStat::new();
+        let solution = SimpleSystem { a, b }
+            .solve(&mut stat, ColumnPermPolicy::Custom(ordering))
+            .unwrap();
+        let x = solution.x.column(0);
+        // The matrix is symmetric, so row i of A is column i
+        for col in 0..n {
+            let start = column_offsets[col] as usize;
+            let end = column_offsets[col + 1] as usize;
+            let sum: f64 = (start..end).map(|k| values[k] * x[row_indices[k] as usize]).sum();
+            assert!((sum - 1.0).abs() < 1e-10);
+        }
+    }
+}
@@ -0,0 +1,200 @@
+//! Detect the structural and numerical properties of a matrix
+//!
+//! Some SuperLU options only work well for some matrices. In
+//! particular, symmetric mode (see
+//! [SimpleDriverOptions::set_diagonally_dominant]) is designed
+//! for matrices which are diagonally dominant, and whose pattern
+//! is symmetric (or nearly symmetric), so that the column
+//! ordering can be computed from $A^T + A$. This module computes
+//! these properties from a [CompColMatrix]:
+//!
+//! * The pattern is structurally symmetric if $A_{ij}$ is stored
+//!   whenever $A_{ji}$ is stored (explicit zeros count as stored
+//!   elements).
+//! * $A$ is symmetric (or Hermitian) to within a relative
+//!   tolerance $\tau$ if
+//!   $|A_{ij} - A_{ji}| \le \tau \max(|A_{ij}|, |A_{ji}|)$ (or the
+//!   same with $\overline{A_{ji}}$) for all $i$ and $j$. A tolerance of
+//!   zero checks for exact symmetry.
+//! * $A$ is (weakly, row) diagonally dominant if
+//!   $|A_{ii}| \ge \sum_{j \ne i} |A_{ij}|$ for every row $i$.
+//! * The lower and upper bandwidths are the largest $i - j$ and
+//!   $j - i$ over the stored elements.
+//!
+//! Only square matrices can be symmetric or diagonally dominant.
+//! [MatrixProperties] collects all the properties, and
+//! [SimpleDriverOptions::auto] uses the diagonal dominance and
+//! structural symmetry to choose the options.
+//!
+
+use crate::c::options::SimpleDriverOptions;
+use crate::c::value_type::ValueType;
+use crate::comp_col::CompColMatrix;
+
+/// The diagonal pivot threshold used by [SimpleDriverOptions::auto]
+/// in symmetric mode
+pub const AUTO_DIAGONAL_PIVOT_THRESHOLD: f64 = 0.01;
+
+/// A summary of the properties of a matrix (see
+/// [properties](crate::properties))
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MatrixProperties {
+    /// The number of rows
+    pub num_rows: usize,
+    /// The number of columns
+    pub num_columns: usize,
+    /// The number of stored elements
+    pub num_non_zeros: usize,
+    /// Whether the pattern is symmetric
+    pub structurally_symmetric: bool,
+    /// Whether the values are symmetric (within the tolerance)
+    pub symmetric: bool,
+    /// Whether the values are Hermitian (within the tolerance).
+    /// This is the same as symmetric for real matrices.
+    pub hermitian: bool,
+    /// Whether the matrix is diagonally dominant (by rows)
+    pub diagonally_dominant: bool,
+    /// The lower and upper bandwidths
+    pub bandwidth: (usize, usize),
+}
+
+impl<P: ValueType> CompColMatrix<P> {
+    /// Check whether the pattern of the matrix is symmetric
+    pub fn is_structurally_symmetric(&self) -> bool {
+        self.compare_with_transpose(|a, a_transpose| a.is_some() == a_transpose.is_some())
+    }
+
+    /// Check whether the matrix is symmetric, to within the
+    /// relative tolerance (see [properties](crate::properties))
+    pub fn is_symmetric(&self, tol: f64) -> bool {
+        self.compare_with_transpose(|a, a_transpose| {
+            is_close(a.unwrap_or(P::zero()), a_transpose.unwrap_or(P::zero()), tol)
+        })
+    }
+
+    /// Check whether the matrix is Hermitian, to within the
+    /// relative tolerance (see [properties](crate::properties))
+    pub fn is_hermitian(&self, tol: f64) -> bool {
+        self.compare_with_transpose(|a, a_transpose| {
+            is_close(a.unwrap_or(P::zero()), a_transpose.unwrap_or(P::zero()).conj(), tol)
+        })
+    }
+
+    /// Check whether the matrix is (weakly) diagonally dominant
+    /// by rows
+    pub fn is_diagonally_dominant(&self) -> bool {
+        if self.num_rows() != self.num_columns() {
+            return false;
+        }
+        let mut diagonal = vec![0.0; self.num_rows()];
+        let mut off_diagonal = vec![0.0; self.num_rows()];
+        for col in 0..self.num_columns() {
+            for (row, value) in self.column(col) {
+                if row == col {
+                    diagonal[row] += value.magnitude();
+                } else {
+                    off_diagonal[row] += value.magnitude();
+                }
+            }
+        }
+        diagonal.iter().zip(&off_diagonal).all(|(diagonal, off_diagonal)| diagonal >= off_diagonal)
+    }
+
+    /// Get the (lower, upper) bandwidths of the matrix, which
+    /// are the largest distances of a stored element below and
+    /// above the diagonal
+    pub fn bandwidth(&self) -> (usize, usize) {
+        let (mut lower, mut upper) = (0, 0);
+        for col in 0..self.num_columns() {
+            for (row, _) in self.column(col) {
+                lower = lower.max(row.saturating_sub(col));
+                upper = upper.max(col.saturating_sub(row));
+            }
+        }
+        (lower, upper)
+    }
+
+    /// Compute all the properties of the matrix, using the
+    /// relative tolerance to check for symmetry
+    pub fn properties(&self, tol: f64) -> MatrixProperties {
+        MatrixProperties {
+            num_rows: self.num_rows(),
+            num_columns: self.num_columns(),
+            num_non_zeros: self.non_zero_values().len(),
+            structurally_symmetric: self.is_structurally_symmetric(),
+            symmetric: self.is_symmetric(tol),
+            hermitian: self.is_hermitian(tol),
+            diagonally_dominant: self.is_diagonally_dominant(),
+            bandwidth: self.bandwidth(),
+        }
+    }
+
+    /// Call f with each pair of elements ($A_{ij}$, $A_{ji}$),
+    /// where None means that the element is not stored, and
+    /// check that it returns true for all of them. Pairs where
+    /// neither element is stored are skipped.
+    fn compare_with_transpose(&self, f: impl Fn(Option<P>, Option<P>) -> bool) -> bool {
+        if self.num_rows() != self.num_columns() {
+            return false;
+        }
+        // Transposing twice sorts the row indices of A
+        let transpose = self.transpose();
+        let a = transpose.transpose();
+        for col in 0..self.num_columns() {
+            let a_column: Vec<_> = a.column(col).collect();
+            let transpose_column: Vec<_> = transpose.column(col).collect();
+            let (mut i, mut j) = (0, 0);
+            while i < a_column.len() || j < transpose_column.len() {
+                let a_row = a_column.get(i).map_or(usize::MAX, |&(row, _)| row);
+                let transpose_row = transpose_column.get(j).map_or(usize::MAX, |&(row, _)| row);
+                let (a_value, transpose_value) = if a_row < transpose_row {
+                    i += 1;
+                    (Some(a_column[i - 1].1), None)
+                } else if transpose_row < a_row {
+                    j += 1;
+                    (None, Some(transpose_column[j - 1].1))
+                } else {
+                    i += 1;
+                    j += 1;
+                    (Some(a_column[i - 1].1), Some(transpose_column[j - 1].1))
+                };
+                if !f(a_value, transpose_value) {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+}
+
+/// Check whether |a - b| <= tol * max(|a|, |b|)
+fn is_close<P: ValueType>(a: P, b: P, tol: f64) -> bool {
+    (a - b).magnitude() <= tol * a.magnitude().max(b.magnitude())
+}
+
+impl SimpleDriverOptions {
+    /// Choose the options based on the properties of $A$
+    ///
+    /// If $A$ is diagonally dominant and structurally symmetric,
+    /// symmetric mode is used (see
+    /// [SimpleDriverOptions::set_diagonally_dominant]), with the
+    /// diagonal pivot threshold [AUTO_DIAGONAL_PIVOT_THRESHOLD]
+    /// and the column ordering computed from $A^T + A$. Otherwise,
+    /// the default options are used (partial pivoting, with the
+    /// COLAMD ordering).
+    ///
+    /// Only these two properties are computed, and structural
+    /// symmetry (which needs the transpose of $A$) is only
+    /// checked if $A$ is diagonally dominant.
+    ///
+    pub fn auto<P: ValueType>(a: &CompColMatrix<P>) -> Self {
+        let mut options = Self::new();
+        if a.is_diagonally_dominant() && a.is_structurally_symmetric() {
+            options.set_diagonally_dominant(true, AUTO_DIAGONAL_PIVOT_THRESHOLD);
+        }
+        options
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,117 @@
+use super::*;
+use crate::c::stat::CSuperluStat;
+use crate::dense::DenseMatrix;
+use crate::simple_driver::SimpleSystem;
+use crate::test_utils::tridiagonal;
+use csuperlu_sys::{colperm_t_COLAMD, colperm_t_MMD_AT_PLUS_A, yes_no_t_NO, yes_no_t_YES};
+use num::Complex;
+
+/// The matrix
+///
+///  1  0 -3
+/// -2  4  0
+///  0  0  5
+fn unsymmetric() -> CompColMatrix<f64> {
+    CompColMatrix::from_vectors(
+        3,
+        vec![1.0, -2.0, 4.0, -3.0, 5.0],
+        vec![0, 1, 1, 0, 2],
+        vec![0, 2, 3, 5],
+    )
+}
+
+#[test]
+fn symmetric_matrix() {
+    let properties = tridiagonal().properties(0.0);
+    assert_eq!(
+        properties,
+        MatrixProperties {
+            num_rows: 3,
+            num_columns: 3,
+            num_non_zeros: 7,
+            structurally_symmetric: true,
+            symmetric: true,
+            hermitian: true,
+            diagonally_dominant: true,
+            bandwidth: (1, 1),
+        }
+    );
+}
+
+#[test]
+fn unsymmetric_matrix() {
+    let a = unsymmetric();
+    assert!(!a.is_structurally_symmetric());
+    assert!(!a.is_symmetric(0.1));
+    assert!(!a.is_diagonally_dominant());
+    assert_eq!(a.bandwidth(), (1, 2));
+}
+
+#[test]
+fn symmetric_within_tolerance() {
+    // The pattern is symmetric, but the values differ slightly,
+    // and the rows in column 0 are not sorted
+    let a = CompColMatrix::from_vectors(
+        2,
+        vec![1.0 + 1e-10, 2.0, 1.0, 3.0],
+        vec![1, 0, 0, 1],
+        vec![0, 2, 4],
+    );
+    assert!(a.is_structurally_symmetric());
+    assert!(!a.is_symmetric(0.0));
+    assert!(a.is_symmetric(1e-8));
+    // An explicit zero is part of the pattern, but has the same
+    // value as a missing element
+    let b = CompColMatrix::from_vectors(2, vec![1.0, 0.0, 1.0], vec![0, 1, 1], vec![0, 2, 3]);
+    assert!(!b.is_structurally_symmetric());
+    assert!(b.is_symmetric(0.0));
+}
+
+#[test]
+fn hermitian_matrix() {
+    // [2, 1 - i; 1 + i, 3]
+    let a = CompColMatrix::from_vectors(
+        2,
+        vec![Complex::new(2.0, 0.0), Complex::new(1.0, 1.0), Complex::new(1.0, -1.0), Complex::new(3.0, 0.0)],
+        vec![0, 1, 0, 1],
+        vec![0, 2, 4],
+    );
+    assert!(a.is_hermitian(0.0));
+    assert!(!a.is_symmetric(0.0));
+    // A complex diagonal is not Hermitian
+    let b = CompColMatrix::from_vectors(1, vec![Complex::new(1.0, 1.0)], vec![0], vec![0, 1]);
+    assert!(b.is_symmetric(0.0));
+    assert!(!b.is_hermitian(0.0));
+}
+
+#[test]
+fn rectangular_matrix() {
+    let a = CompColMatrix::from_vectors(2, vec![1.0], vec![1], vec![0, 1, 1, 1]);
+    let properties = a.properties(0.0);
+    assert!(!properties.structurally_symmetric);
+    assert!(!properties.symmetric);
+    assert!(!properties.diagonally_dominant);
+    assert_eq!(properties.bandwidth, (1, 0));
+}
+
+#[test]
+fn auto_options() {
+    let options = SimpleDriverOptions::auto(&tridiagonal());
+    assert_eq!(options.get_options().SymmetricMode, yes_no_t_YES);
+    assert_eq!(options.get_options().ColPerm, colperm_t_MMD_AT_PLUS_A);
+    assert_eq!(options.get_options().DiagPivotThresh, AUTO_DIAGONAL_PIVOT_THRESHOLD);
+
+    let options = SimpleDriverOptions::auto(&unsymmetric());
+    assert_eq!(options.get_options().SymmetricMode, yes_no_t_NO);
+    assert_eq!(options.get_options().ColPerm, colperm_t_COLAMD);
+}
+
+#[test]
+fn solve_auto() {
+    let b = DenseMatrix::from_vectors(3, 1, vec![5.0, 6.0, 5.0]);
+    let mut stat = CSuperluStat::new();
+    let solution = SimpleSystem { a: tridiagonal(), b }.solve_auto(&mut stat).unwrap();
+    for value in solution.x.column(0) {
+        assert!((value - 1.0).abs() < 1e-12);
+    }
+}
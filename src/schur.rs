@@ -0,0 +1,206 @@
+//! Compute the Schur complement of a 2x2 block matrix
+//!
+//! Split a square matrix $A$ after its first $k$ rows and
+//! columns, so that
+//!
+//! $$A = \begin{pmatrix} A_{11} & B \\\\ C & D \end{pmatrix},$$
+//!
+//! where $A_{11}$ is $k \times k$. The Schur complement of
+//! $A_{11}$ in $A$ is
+//!
+//! $$S = D - CA_{11}^{-1}B.$$
+//!
+//! It is the matrix that remains after eliminating the first $k$
+//! unknowns (for example, the internal nodes of a circuit, or the
+//! interior of a subdomain in domain decomposition).
+//!
+//! The function [schur_complement] factors $A_{11}$ using the
+//! simple driver, solving for $A_{11}^{-1}B$ at the same time.
+//! Only the non-zero columns of $B$ are passed to the solver as
+//! right-hand sides, because the zero columns do not contribute
+//! to $CA_{11}^{-1}B$. $A_{11}$ is factored even if $B$ is zero,
+//! because the Schur complement only exists if $A_{11}$ is
+//! non-singular.
+//!
+
+use crate::c::options::ColumnPermPolicy;
+use crate::c::stat::CSuperluStat;
+use crate::c::value_type::ValueType;
+use crate::comp_col::{to_index, CompColMatrix};
+use crate::dense::DenseMatrix;
+use crate::simple_driver::{SimpleError, SimpleSolution, SimpleSystem};
+
+/// Errors that can occur when computing a Schur complement
+#[derive(Debug)]
+pub enum SchurError<P: ValueType> {
+    /// The matrix is not square
+    NotSquare,
+    /// The split index must satisfy 0 < split < size
+    InvalidSplit { split: usize, size: usize },
+    /// The simple driver failed to factor the leading block
+    /// (for example, because it is singular)
+    Solver(SimpleError<P>),
+}
+
+impl<P: ValueType> From<SimpleError<P>> for SchurError<P> {
+    fn from(err: SimpleError<P>) -> Self {
+        Self::Solver(err)
+    }
+}
+
+/// The Schur complement $S = D - CA_{11}^{-1}B$, stored as a
+/// dense matrix
+///
+/// The Schur complement is usually much denser than $A$, so it
+/// is computed in dense form. Use [to_sparse](Self::to_sparse)
+/// to drop small elements and obtain a compressed-column matrix.
+///
+#[derive(Debug, Clone)]
+pub struct SchurComplement<P: ValueType> {
+    size: usize,
+    values: Vec<P>,
+}
+
+impl<P: ValueType> SchurComplement<P> {
+    /// Get the number of rows (and columns) in $S$
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Get the value of $S$ at (row, column)
+    ///
+    /// # Panics
+    ///
+    /// If the row or column index is out of range.
+    pub fn value(&self, row: usize, col: usize) -> P {
+        assert!(row < self.size, "Row index out of range");
+        assert!(col < self.size, "Column index out of range");
+        self.values[row + col * self.size]
+    }
+
+    /// Get the values of $S$ in column-major order
+    pub fn column_major_values(&self) -> &[P] {
+        &self.values
+    }
+
+    /// Copy $S$ into a dense matrix
+    pub fn to_dense(&self) -> DenseMatrix<P> {
+        DenseMatrix::from_vectors(self.size, self.size, self.values.clone())
+    }
+
+    /// Copy the elements of $S$ with magnitude greater than
+    /// drop_tolerance into a compressed-column matrix. Use a
+    /// drop tolerance of zero to keep all non-zero elements.
+    pub fn to_sparse(&self, drop_tolerance: f64) -> CompColMatrix<P> {
+        let mut non_zero_values = Vec::new();
+        let mut row_indices = Vec::new();
+        let mut column_offsets = Vec::with_capacity(self.size + 1);
+        column_offsets.push(0);
+        for col in 0..self.size {
+            for row in 0..self.size {
+                let value = self.values[row + col * self.size];
+                if value != P::zero() && value.magnitude() > drop_tolerance {
+                    non_zero_values.push(value);
+                    row_indices.push(to_index(row));
+                }
+            }
+            column_offsets.push(to_index(non_zero_values.len()));
+        }
+        CompColMatrix::from_vectors(self.size, non_zero_values, row_indices, column_offsets)
+    }
+}
+
+/// Compute the Schur complement of the leading split x split
+/// block of a square matrix
+///
+/// The leading block $A_{11}$ is factored by the simple driver,
+/// using the column permutation policy. If $B$ is zero, then
+/// $S = D$, but $A_{11}$ is still factored to check that it is
+/// non-singular.
+///
+/// # Errors
+///
+/// If the matrix is not square, or the split is not strictly
+/// between zero and the matrix size, an error is returned. If
+/// the simple driver fails (for example, because $A_{11}$ is
+/// singular), the error is returned as [SchurError::Solver].
+///
+pub fn schur_complement<P: ValueType>(
+    a: &CompColMatrix<P>,
+    split: usize,
+    stat: &mut CSuperluStat,
+    column_perm_policy: ColumnPermPolicy,
+) -> Result<SchurComplement<P>, SchurError<P>> {
+    let size = a.num_rows();
+    if a.num_columns() != size {
+        return Err(SchurError::NotSquare);
+    }
+    if split == 0 || split >= size {
+        return Err(SchurError::InvalidSplit { split, size });
+    }
+    let schur_size = size - split;
+    // Split the leading columns into A11 and C
+    let mut a11_values = Vec::new();
+    let mut a11_row_indices = Vec::new();
+    let mut a11_column_offsets = vec![0];
+    let mut c_columns = Vec::with_capacity(split);
+    for col in 0..split {
+        let mut c_column = Vec::new();
+        for (row, value) in a.column(col) {
+            if row < split {
+                a11_values.push(value);
+                a11_row_indices.push(to_index(row));
+            } else {
+                c_column.push((row - split, value));
+            }
+        }
+        a11_column_offsets.push(to_index(a11_values.len()));
+        c_columns.push(c_column);
+    }
+
+    // Split the trailing columns into B (keeping only the
+    // non-zero columns, as right-hand sides) and D (which is
+    // the starting value of S)
+    let mut values = vec![P::zero(); schur_size * schur_size];
+    let mut rhs = Vec::new();
+    let mut rhs_columns = Vec::new();
+    for col in split..size {
+        let mut b_column = vec![P::zero(); split];
+        let mut b_non_zero = false;
+        for (row, value) in a.column(col) {
+            if row < split {
+                b_column[row] = value;
+                b_non_zero = true;
+            } else {
+                values[(row - split) + (col - split) * schur_size] = value;
+            }
+        }
+        if b_non_zero {
+            rhs.extend(b_column);
+            rhs_columns.push(col - split);
+        }
+    }
+
+    // If B is zero, solve with a single zero right-hand side,
+    // which is not used, so that A11 is still checked
+    let num_rhs = rhs_columns.len().max(1);
+    rhs.resize(split * num_rhs, P::zero());
+    let a11 = CompColMatrix::from_vectors(split, a11_values, a11_row_indices, a11_column_offsets);
+    let b = DenseMatrix::from_vectors(split, num_rhs, rhs);
+    let SimpleSolution { x, .. } = SimpleSystem { a: a11, b }.solve(stat, column_perm_policy)?;
+    // Subtract C * (A11^{-1} B) from each column of S
+    for (q, &col) in rhs_columns.iter().enumerate() {
+        let x_column = x.column(q);
+        let s_column = &mut values[col * schur_size..][..schur_size];
+        for (j, c_column) in c_columns.iter().enumerate() {
+            for &(row, value) in c_column {
+                s_column[row] = s_column[row] - value * x_column[j];
+            }
+        }
+    }
+
+    Ok(SchurComplement { size: schur_size, values })
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,85 @@
+use super::*;
+use crate::sparse_matrix::SparseMat;
+
+fn from_rows(rows: &[&[f64]]) -> CompColMatrix<f64> {
+    let mut a = SparseMat::new(rows.len(), rows[0].len());
+    for (row, values) in rows.iter().enumerate() {
+        for (col, &value) in values.iter().enumerate() {
+            a.insert(row, col, value);
+        }
+    }
+    a.compressed_column_format()
+}
+
+#[test]
+fn two_by_two_blocks() {
+    let a = from_rows(&[
+        &[4.0, 1.0, 1.0, 0.0],
+        &[1.0, 3.0, 0.0, 2.0],
+        &[2.0, 0.0, 5.0, 1.0],
+        &[0.0, 1.0, 1.0, 6.0],
+    ]);
+    let mut stat = CSuperluStat::new();
+    let s = schur_complement(&a, 2, &mut stat, ColumnPermPolicy::Natural).unwrap();
+    assert_eq!(s.size(), 2);
+    let expected = [49.0 / 11.0, 12.0 / 11.0, 15.0 / 11.0, 58.0 / 11.0];
+    for (value, expected) in s.column_major_values().iter().zip(expected) {
+        assert!((value - expected).abs() < 1e-12);
+    }
+    assert!((s.to_dense()[(0, 1)] - 15.0 / 11.0).abs() < 1e-12);
+}
+
+#[test]
+fn zero_coupling_and_drop_tolerance() {
+    // B is zero, so S = D
+    let a = from_rows(&[
+        &[2.0, 0.0, 0.0],
+        &[1.0, 3.0, 1e-10],
+        &[0.0, 0.0, 7.0],
+    ]);
+    let mut stat = CSuperluStat::new();
+    let s = schur_complement(&a, 1, &mut stat, ColumnPermPolicy::Natural).unwrap();
+    assert_eq!(s.column_major_values(), &[3.0, 0.0, 1e-10, 7.0]);
+    let sparse = s.to_sparse(0.0);
+    assert_eq!(sparse.non_zero_values().len(), 3);
+    let sparse = s.to_sparse(1e-8);
+    assert_eq!(sparse.non_zero_values(), &[3.0, 7.0]);
+    assert_eq!(sparse.row_indices(), &[0, 1]);
+    assert_eq!(sparse.column_offsets(), &[0, 1, 2]);
+}
+
+#[test]
+fn invalid_arguments() {
+    let a = from_rows(&[&[1.0, 2.0], &[3.0, 4.0]]);
+    let mut stat = CSuperluStat::new();
+    let result = schur_complement(&a, 2, &mut stat, ColumnPermPolicy::Natural);
+    assert!(matches!(result, Err(SchurError::InvalidSplit { split: 2, size: 2 })));
+    let result = schur_complement(&a, 0, &mut stat, ColumnPermPolicy::Natural);
+    assert!(matches!(result, Err(SchurError::InvalidSplit { split: 0, size: 2 })));
+}
+
+#[test]
+fn singular_leading_block() {
+    let a = from_rows(&[
+        &[1.0, 1.0, 1.0],
+        &[1.0, 1.0, 0.0],
+        &[1.0, 0.0, 1.0],
+    ]);
+    let mut stat = CSuperluStat::new();
+    let result = schur_complement(&a, 2, &mut stat, ColumnPermPolicy::Natural);
+    assert!(matches!(result, Err(SchurError::Solver(SimpleError::Singular { .. }))));
+}
+
+#[test]
+fn singular_leading_block_with_zero_coupling() {
+    // B is zero, but S = D is not returned because A11 is
+    // singular
+    let a = from_rows(&[
+        &[1.0, 1.0, 0.0],
+        &[1.0, 1.0, 0.0],
+        &[1.0, 0.0, 2.0],
+    ]);
+    let mut stat = CSuperluStat::new();
+    let result = schur_complement(&a, 2, &mut stat, ColumnPermPolicy::Natural);
+    assert!(matches!(result, Err(SchurError::Solver(SimpleError::Singular { .. }))));
+}
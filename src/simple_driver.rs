@@ -17,14 +17,31 @@
 //! pivoting.
 //! 3. Solve the equation $AX = B$ using the $LU$-decomposition
 //! in step 2.
+//!
+//! The row permutation can also be chosen before the
+//! factorisation (static pivoting), for example to reuse the
+//! pivots from an earlier factorisation, or to use a row
+//! permutation that puts large elements on the diagonal (see
+//! [transversal](crate::transversal)). SuperLU's drivers only
+//! accept a row permutation when they also reuse the $L$ and $U$
+//! structures from an earlier factorisation, so [UserRowPerm] and
+//! [SamePatternSameRowPerm] instead permute the rows of $A$ before
+//! it is factored, and set the diagonal pivot threshold to zero.
+//! SuperLU then uses each chosen pivot unless it is exactly zero
+//! (in which case it falls back to partial pivoting). The row
+//! permutation in the solution is the one that was actually
+//! used, relative to the original $A$.
 
 use crate::comp_col::CompColMatrix;
 use crate::dense::DenseMatrix;
 use crate::c::options::{ColumnPermPolicy, SimpleDriverOptions};
 use crate::c::stat::CSuperluStat;
-use crate::c::value_type::{ValueType, CSimpleResult, Error};
+#[cfg(feature = "tuning")]
+use crate::c::tuning::TuningParams;
+use crate::c::value_type::{ValueType, CSimpleResult, CWork, Error};
 
 use crate::lu_decomp::LUDecomp;
+use crate::memory::{MemUsage, WorkBuffer};
 use crate::super_node::SuperNodeMatrix;
 
 #[derive(Debug)]
@@ -51,6 +68,35 @@ pub struct SimpleSolution<P: ValueType> {
     pub lu: LUDecomp<P>,
     pub column_perm: ColumnPerm,
     pub row_perm: RowPerm,
+    /// The expert driver (used by [SimpleSystem::solve_with_work])
+    /// found that the reciprocal condition number of $A$ is less
+    /// than machine precision, so $x$ may be inaccurate. This is
+    /// always false for the simple driver, which does not
+    /// estimate the condition number.
+    pub ill_conditioned: bool,
+}
+
+impl<P: ValueType> SimpleSolution<P> {
+    /// Compute the reciprocal pivot growth of the factorisation
+    /// (see [LUDecomp::reciprocal_pivot_growth])
+    pub fn reciprocal_pivot_growth(&self) -> f64 {
+	self.lu.reciprocal_pivot_growth(&self.a, &self.column_perm, self.a.num_columns())
+    }
+}
+
+impl<P: ValueType> SimpleError<P> {
+    /// Compute the reciprocal pivot growth of the columns that
+    /// were factored before the singular column was found
+    /// (including the singular column). This shows how far the
+    /// factorisation got before it broke down. Returns None for
+    /// other errors.
+    pub fn reciprocal_pivot_growth(&self) -> Option<f64> {
+	match self {
+	    Self::Singular { a, singular_column, lu, column_perm, .. } =>
+		Some(lu.reciprocal_pivot_growth(a, column_perm, singular_column + 1)),
+	    Self::Other(_) => None,
+	}
+    }
 }
 
 /// This function turns the result type from c_simple_driver into
@@ -58,6 +104,17 @@ pub struct SimpleSolution<P: ValueType> {
 unsafe fn from_c_result<P: ValueType>(
     a: CompColMatrix<P>,
     result: CSimpleResult
+) -> Result<SimpleSolution<P>, SimpleError<P>> {
+    from_c_result_with_work(a, result, None)
+}
+
+/// Turn the result type from c_simple_driver or c_expert_driver
+/// into a solution, passing ownership of the work buffer (if the
+/// factors are stored in it) to the $LU$ decomposition
+unsafe fn from_c_result_with_work<P: ValueType>(
+    a: CompColMatrix<P>,
+    result: CSimpleResult,
+    work: Option<WorkBuffer>,
 ) -> Result<SimpleSolution<P>, SimpleError<P>> {
     match result {
 	CSimpleResult::Solution {
@@ -65,15 +122,16 @@ unsafe fn from_c_result<P: ValueType>(
 	    perm_c,
 	    perm_r,
 	    l,
-	    u
+	    u,
+	    ill_conditioned,
 	} => {
 	    let l = SuperNodeMatrix::from_super_matrix(l);
 	    let u = CompColMatrix::from_super_matrix(u);
-	    let lu = LUDecomp::from_matrices(l, u);
+	    let lu = LUDecomp::from_parts(l, u, work);
 	    let x = DenseMatrix::<P>::from_super_matrix(x);
 	    let column_perm = ColumnPerm::from_raw(perm_c);
 	    let row_perm = RowPerm::from_raw(perm_r);
-	    Ok(SimpleSolution { a, x, lu, column_perm, row_perm })
+	    Ok(SimpleSolution { a, x, lu, column_perm, row_perm, ill_conditioned })
 	},
 	CSimpleResult::SingularFact {
 	    singular_column,
@@ -84,7 +142,7 @@ unsafe fn from_c_result<P: ValueType>(
 	} => {
 	    let l = SuperNodeMatrix::from_super_matrix(l);
 	    let u = CompColMatrix::from_super_matrix(u);
-	    let lu = LUDecomp::from_matrices(l, u);
+	    let lu = LUDecomp::from_parts(l, u, work);
 	    let column_perm = ColumnPerm::from_raw(perm_c);
 	    let row_perm = RowPerm::from_raw(perm_r);
 	    Err(SimpleError::Singular { a, lu, singular_column, column_perm, row_perm })
@@ -93,13 +151,61 @@ unsafe fn from_c_result<P: ValueType>(
     }
 }
 
+/// Check that a vector is a permutation of 0..size
+fn is_permutation(perm: &[i32], size: usize) -> bool {
+    if perm.len() != size {
+	return false;
+    }
+    let mut seen = vec![false; size];
+    for &index in perm {
+	if index < 0 || index as usize >= size || seen[index as usize] {
+	    return false;
+	}
+	seen[index as usize] = true;
+    }
+    true
+}
+
+/// Compute the column permutation for a custom ordering policy.
+/// Returns None for the policies that are computed by SuperLU.
+fn custom_column_perm<P: ValueType>(
+    a: &CompColMatrix<P>,
+    column_perm_policy: &ColumnPermPolicy,
+) -> Result<Option<Vec<i32>>, Error> {
+    match column_perm_policy {
+	ColumnPermPolicy::Custom(ordering) => {
+	    let column_perm = ordering.c_column_perm(a.num_rows(), a.column_offsets(), a.row_indices());
+	    if !is_permutation(&column_perm, a.num_columns()) {
+		return Err(Error::PermutationError);
+	    }
+	    Ok(Some(column_perm))
+	},
+	_ => Ok(None),
+    }
+}
+
 /// Stores a column permutation vector
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ColumnPerm {
     column_perm: Vec<i32>,
 }
 
 impl ColumnPerm {
+    /// Make a column permutation from a vector, where element j
+    /// is the position of column j in the permuted matrix
+    ///
+    /// # Errors
+    ///
+    /// If the vector is not a permutation of 0..num_columns, an
+    /// error is returned.
+    ///
+    pub fn new(column_perm: Vec<i32>, num_columns: usize) -> Result<Self, Error> {
+	if !is_permutation(&column_perm, num_columns) {
+	    return Err(Error::PermutationError);
+	}
+	Ok(Self { column_perm })
+    }
+
     /// Unsafe because content of Vec is not checked
     /// (elements need to be unique)
     pub unsafe fn from_raw(column_perm: Vec<i32>) -> Self {
@@ -107,15 +213,36 @@ impl ColumnPerm {
 	    column_perm,
 	}
     }
+
+    /// Get the permutation vector. Element j is the position
+    /// of column j of $A$ in the permuted matrix $AP_c$.
+    pub fn as_slice(&self) -> &[i32] {
+	&self.column_perm
+    }
 }
 
 /// Stores a row permutation vector
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct RowPerm {
     row_perm: Vec<i32>,
 }
 
 impl RowPerm {
+    /// Make a row permutation from a vector, where element i is
+    /// the position of row i in the permuted matrix
+    ///
+    /// # Errors
+    ///
+    /// If the vector is not a permutation of 0..num_rows, an
+    /// error is returned.
+    ///
+    pub fn new(row_perm: Vec<i32>, num_rows: usize) -> Result<Self, Error> {
+	if !is_permutation(&row_perm, num_rows) {
+	    return Err(Error::PermutationError);
+	}
+	Ok(Self { row_perm })
+    }
+
     /// Unsafe because content of Vec is not checked
     /// (elements need to be unique)
     pub unsafe fn from_raw(row_perm: Vec<i32>) -> Self {
@@ -123,6 +250,12 @@ impl RowPerm {
 	    row_perm,
 	}
     }
+
+    /// Get the permutation vector. Element i is the position
+    /// of row i of $A$ in the permuted matrix $P_rA$.
+    pub fn as_slice(&self) -> &[i32] {
+	&self.row_perm
+    }
 }
 
 /// Defines a simple sparse linear system $AX = B$
@@ -191,20 +324,52 @@ impl<P: ValueType> SimpleSystem<P> {
     ///
     /// Column permutations are chosen according to a policy. The
     /// available policies are documented in the SuperLU manual
-    /// Section 1.3.5.
+    /// Section 1.3.5. A custom policy is called with the pattern
+    /// of $A$, and the resulting permutation is passed to SuperLU;
+    /// if it is not a valid permutation, [Error::PermutationError]
+    /// is returned.
     ///
     pub fn solve(
 	self,
 	stat: &mut CSuperluStat,
 	column_perm_policy: ColumnPermPolicy,
     ) -> Result<SimpleSolution<P>, SimpleError<P>> {
+	self.solve_with_options(stat, column_perm_policy, SimpleDriverOptions::new())
+    }
+
+    /// Solve the system in the same way as [SimpleSystem::solve],
+    /// overriding SuperLU's tuning parameters (the panel size,
+    /// supernode sizes and fill estimate) for this solve only
+    /// (see [tuning](crate::c::tuning))
+    #[cfg(feature = "tuning")]
+    pub fn solve_with_tuning(
+	self,
+	stat: &mut CSuperluStat,
+	column_perm_policy: ColumnPermPolicy,
+	tuning_params: TuningParams,
+    ) -> Result<SimpleSolution<P>, SimpleError<P>> {
+	let mut options = SimpleDriverOptions::new();
+	options.set_tuning_params(tuning_params);
+	self.solve_with_options(stat, column_perm_policy, options)
+    }
+
+    /// Solve the system with the simple driver, setting the
+    /// column permutation in the options from the policy
+    fn solve_with_options(
+	self,
+	stat: &mut CSuperluStat,
+	column_perm_policy: ColumnPermPolicy,
+	mut options: SimpleDriverOptions,
+    ) -> Result<SimpleSolution<P>, SimpleError<P>> {
 
 	let SimpleSystem {a, b} = self;
 
 	// TODO: Check for invalid dimensions
 
-	let mut options = SimpleDriverOptions::new();
-	options.set_superlu_column_perm(column_perm_policy);
+	let column_perm = custom_column_perm(&a, &column_perm_policy).map_err(SimpleError::Other)?;
+	if column_perm.is_none() {
+	    options.set_superlu_column_perm(column_perm_policy);
+	}
 	
 	unsafe {
             let b_super_matrix = b.into_super_matrix();
@@ -212,6 +377,33 @@ impl<P: ValueType> SimpleSystem<P> {
 	    let result = P::c_simple_driver(
 		options,
 		&mut a.super_matrix(),
+		column_perm,
+		b_super_matrix,
+		stat,
+            );
+
+	    from_c_result::<P>(a, result)
+	}
+    }
+
+    /// Solve the system with the options chosen from the
+    /// properties of $A$ (see
+    /// [SimpleDriverOptions::auto]). Symmetric mode is used
+    /// if $A$ is diagonally dominant and structurally symmetric.
+    pub fn solve_auto(
+	self,
+	stat: &mut CSuperluStat,
+    ) -> Result<SimpleSolution<P>, SimpleError<P>> {
+
+	let SimpleSystem {a, b} = self;
+	let options = SimpleDriverOptions::auto(&a);
+
+	unsafe {
+            let b_super_matrix = b.into_super_matrix();
+
+	    let result = P::c_simple_driver(
+		options,
+		a.super_matrix(),
 		None,
 		b_super_matrix,
 		stat,
@@ -220,4 +412,298 @@ impl<P: ValueType> SimpleSystem<P> {
 	    from_c_result::<P>(a, result)
 	}
     }
+
+    /// Solve the system in the same way as [SimpleSystem::solve],
+    /// but take all of the memory for the factorisation from a
+    /// work buffer allocated in Rust (see [memory](crate::memory)).
+    /// The buffer is owned by the $LU$ decomposition in the
+    /// solution (or in the singular error).
+    ///
+    /// # Errors
+    ///
+    /// If the buffer is too small (or empty),
+    /// [Error::OutOfMemory] is returned, and the buffer is
+    /// freed. SuperLU does not allocate any more memory for $L$
+    /// and $U$.
+    ///
+    pub fn solve_with_work(
+	self,
+	stat: &mut CSuperluStat,
+	column_perm_policy: ColumnPermPolicy,
+	mut work: WorkBuffer,
+    ) -> Result<SimpleSolution<P>, SimpleError<P>> {
+
+	// An empty buffer would give lwork = 0, which makes SuperLU
+	// allocate the factors with malloc instead
+	if work.size() == 0 {
+	    return Err(SimpleError::Other(Error::OutOfMemory { mem_alloc_at_failure: 0 }));
+	}
+
+	let SimpleSystem {a, b} = self;
+
+	let column_perm = custom_column_perm(&a, &column_perm_policy).map_err(SimpleError::Other)?;
+	let mut options = SimpleDriverOptions::new();
+	if column_perm.is_none() {
+	    options.set_superlu_column_perm(column_perm_policy);
+	}
+
+	unsafe {
+	    let x = DenseMatrix::<P>::zeros(b.num_rows(), b.num_columns());
+	    let (result, _) = P::c_expert_driver(
+		options,
+		a.super_matrix(),
+		column_perm,
+		b.into_super_matrix(),
+		x.into_super_matrix(),
+		CWork::Buffer(work.as_mut_slice()),
+		stat,
+	    );
+	    from_c_result_with_work::<P>(a, result, Some(work))
+	}
+    }
+
+    /// Estimate the memory needed to solve the system (see
+    /// [memory](crate::memory)), without factorising $A$. Only
+    /// the total_needed field of the result is set.
+    ///
+    /// # Errors
+    ///
+    /// If the custom column ordering does not give a valid
+    /// permutation, or SuperLU reports an error, the error is
+    /// returned.
+    ///
+    pub fn estimate_memory(
+	&self,
+	stat: &mut CSuperluStat,
+	column_perm_policy: ColumnPermPolicy,
+    ) -> Result<MemUsage, Error> {
+
+	let column_perm = custom_column_perm(&self.a, &column_perm_policy)?;
+	let mut options = SimpleDriverOptions::new();
+	if column_perm.is_none() {
+	    options.set_superlu_column_perm(column_perm_policy);
+	}
+
+	let (num_rows, num_columns) = (self.b.num_rows(), self.b.num_columns());
+	let (result, mem_usage) = unsafe {
+	    P::c_expert_driver(
+		options,
+		self.a.super_matrix(),
+		column_perm,
+		DenseMatrix::<P>::zeros(num_rows, num_columns).into_super_matrix(),
+		DenseMatrix::<P>::zeros(num_rows, num_columns).into_super_matrix(),
+		CWork::Query,
+		stat,
+	    )
+	};
+	// The estimate is returned as an out-of-memory error
+	match result {
+	    CSimpleResult::Err(Error::OutOfMemory { .. }) => Ok(MemUsage::from_c_mem_usage(&mem_usage)),
+	    CSimpleResult::Err(err) => Err(err),
+	    _ => Err(Error::UnknownError),
+	}
+    }
+}
+
+/// Defines a sparse linear system $AX = B$ and a row permutation
+/// $P_r$, such that the diagonal elements of $P_rA$ are used as
+/// the pivots (static pivoting)
+///
+/// SuperLU's factorisation routine (*gstrf, which both the simple
+/// and the expert drivers call) only reads a row permutation when
+/// it reuses the $L$ and $U$ structures of an earlier
+/// factorisation (SamePattern_SameRowPerm). Setting RowPerm to
+/// MY_PERMR is not enough, so the expert driver gives no more
+/// control over the pivots than the simple driver. Instead, the
+/// rows of $A$ are permuted so that the chosen pivots are on the
+/// diagonal, and $P_rA$ is factored by the simple driver (*gssv)
+/// with DiagPivotThresh = 0. With a threshold of zero, SuperLU
+/// uses the diagonal element as the pivot whenever it is
+/// non-zero, so the pivots are the ones chosen by $P_r$. The
+/// expert driver is not used because its extra steps
+/// (equilibration, condition estimation and iterative
+/// refinement) do not affect the choice of pivots.
+pub struct UserRowPerm<P: ValueType> {
+    /// The (sparse) matrix $A$
+    pub a: CompColMatrix<P>,
+    /// The right-hand side(s) matrix $B$
+    pub b: DenseMatrix<P>,
+    /// The row permutation; element i is the position of row i
+    /// in $P_rA$
+    pub row_perm: RowPerm,
+}
+
+/// Defines a sparse linear system $AX = B$, and the column and
+/// row permutations from an earlier factorisation of a matrix
+/// with the same pattern. The same pivots are used.
+pub struct SamePatternSameRowPerm<P: ValueType> {
+    /// The (sparse) matrix $A$
+    pub a: CompColMatrix<P>,
+    /// The right-hand side(s) matrix $B$
+    pub b: DenseMatrix<P>,
+    /// The column permutation to use for the solution
+    pub column_perm: ColumnPerm,
+    /// The row permutation to use for the solution
+    pub row_perm: RowPerm,
+}
+
+/// Solve a linear system using chosen pivots. The row of $A$
+/// that is chosen as the pivot for column j is moved to row j
+/// (where SuperLU looks for the diagonal pivot), and the rows of
+/// $B$ are moved in the same way.
+///
+/// pivot_rows\[i\] is the column of $A$ for which row i of $A$
+/// is the pivot.
+fn solve_with_pivots<P: ValueType>(
+    a: CompColMatrix<P>,
+    b: DenseMatrix<P>,
+    pivot_rows: Vec<usize>,
+    column_perm: Option<Vec<i32>>,
+    column_perm_policy: ColumnPermPolicy,
+    stat: &mut CSuperluStat,
+) -> Result<SimpleSolution<P>, SimpleError<P>> {
+    let row_indices = a.row_indices().iter().map(|&row| pivot_rows[row as usize] as i32).collect();
+    let permuted_a = CompColMatrix::from_vectors(
+	a.num_rows(),
+	a.non_zero_values().to_vec(),
+	row_indices,
+	a.column_offsets().to_vec(),
+    );
+    let mut permuted_b = DenseMatrix::zeros(b.num_rows(), b.num_columns());
+    for col in 0..b.num_columns() {
+	for (row, &value) in b.column(col).iter().enumerate() {
+	    permuted_b[(pivot_rows[row], col)] = value;
+	}
+    }
+
+    let column_perm = match column_perm {
+	Some(column_perm) => Some(column_perm),
+	None => custom_column_perm(&a, &column_perm_policy).map_err(SimpleError::Other)?,
+    };
+    let mut options = SimpleDriverOptions::new();
+    if column_perm.is_none() {
+	options.set_superlu_column_perm(column_perm_policy);
+    }
+    options.set_diagonal_pivot_threshold(0.0);
+
+    let result = unsafe {
+	let result = P::c_simple_driver(
+	    options,
+	    permuted_a.super_matrix(),
+	    column_perm,
+	    permuted_b.into_super_matrix(),
+	    stat,
+	);
+	from_c_result::<P>(permuted_a, result)
+    };
+
+    // Express the row permutation relative to the original A
+    let original_row_perm = |row_perm: RowPerm| {
+	let row_perm = pivot_rows.iter().map(|&row| row_perm.as_slice()[row]).collect();
+	RowPerm { row_perm }
+    };
+    match result {
+	Ok(SimpleSolution { x, lu, column_perm, row_perm, ill_conditioned, .. }) => {
+	    let row_perm = original_row_perm(row_perm);
+	    Ok(SimpleSolution { a, x, lu, column_perm, row_perm, ill_conditioned })
+	}
+	Err(SimpleError::Singular { singular_column, lu, column_perm, row_perm, .. }) => {
+	    let row_perm = original_row_perm(row_perm);
+	    Err(SimpleError::Singular { a, singular_column, lu, column_perm, row_perm })
+	}
+	Err(err) => Err(err),
+    }
 }
+
+/// Check the dimensions of a system before solving it with
+/// chosen pivots
+fn check_pivot_dimensions<P: ValueType>(
+    a: &CompColMatrix<P>,
+    b: &DenseMatrix<P>,
+    row_perm: &RowPerm,
+) -> Result<(), SimpleError<P>> {
+    if a.num_rows() != a.num_columns() || b.num_rows() != a.num_rows() {
+	return Err(SimpleError::Other(Error::DimensionMismatch));
+    }
+    if !is_permutation(row_perm.as_slice(), a.num_rows()) {
+	return Err(SimpleError::Other(Error::PermutationError));
+    }
+    Ok(())
+}
+
+impl<P: ValueType> UserRowPerm<P> {
+    /// Solve the linear system $AX = B$ using the diagonal
+    /// elements of $P_rA$ as the pivots
+    ///
+    /// The column permutation $P_c$ is chosen according to the
+    /// policy. Pivot $k$ of the factorisation
+    /// $P_r'AP_c = LU$ is the diagonal element of $P_rA$ in
+    /// column $k$ of $AP_c$, unless that element is zero (then
+    /// SuperLU uses partial pivoting for that column). The row
+    /// permutation $P_r'$ in the solution is the one that was
+    /// used.
+    ///
+    /// # Errors
+    ///
+    /// If $A$ is not square, or the number of rows of $B$ does
+    /// not match $A$, [Error::DimensionMismatch] is returned. If
+    /// the row permutation is not a permutation of the rows of
+    /// $A$, [Error::PermutationError] is returned. Other errors
+    /// are the same as for [SimpleSystem::solve].
+    ///
+    pub fn solve(
+	self,
+	stat: &mut CSuperluStat,
+	column_perm_policy: ColumnPermPolicy,
+    ) -> Result<SimpleSolution<P>, SimpleError<P>> {
+	let UserRowPerm { a, b, row_perm } = self;
+	check_pivot_dimensions(&a, &b, &row_perm)?;
+	let pivot_rows = row_perm.as_slice().iter().map(|&position| position as usize).collect();
+	solve_with_pivots(a, b, pivot_rows, None, column_perm_policy, stat)
+    }
+}
+
+impl<P: ValueType> SamePatternSameRowPerm<P> {
+    /// Solve the linear system $AX = B$ using the pivots from
+    /// an earlier factorisation $P_rA'P_c = L'U'$
+    ///
+    /// The row of $A$ that was the pivot for each column of
+    /// $A'$ is used as the pivot for the same column of $A$
+    /// (unless it is zero). SuperLU may still reorder the
+    /// columns in its postordering step, so the permutations in
+    /// the solution are not always the same as the input
+    /// permutations, but the pivots are the same.
+    ///
+    /// # Errors
+    ///
+    /// If $A$ is not square, or the number of rows of $B$ does
+    /// not match $A$, [Error::DimensionMismatch] is returned. If
+    /// either permutation does not match $A$,
+    /// [Error::PermutationError] is returned. Other errors are
+    /// the same as for [SimpleSystem::solve].
+    ///
+    pub fn solve(self, stat: &mut CSuperluStat) -> Result<SimpleSolution<P>, SimpleError<P>> {
+	let SamePatternSameRowPerm { a, b, column_perm, row_perm } = self;
+	check_pivot_dimensions(&a, &b, &row_perm)?;
+	let column_perm = column_perm.column_perm;
+	if !is_permutation(&column_perm, a.num_columns()) {
+	    return Err(SimpleError::Other(Error::PermutationError));
+	}
+	// Row i of A is the pivot in position row_perm[i], which
+	// is the position of the column j with column_perm[j]
+	// equal to row_perm[i]
+	let mut columns_at_positions = vec![0; column_perm.len()];
+	for (col, &position) in column_perm.iter().enumerate() {
+	    columns_at_positions[position as usize] = col;
+	}
+	let pivot_rows = row_perm
+	    .as_slice()
+	    .iter()
+	    .map(|&position| columns_at_positions[position as usize])
+	    .collect();
+	solve_with_pivots(a, b, pivot_rows, Some(column_perm), ColumnPermPolicy::Natural, stat)
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,128 @@
+use super::*;
+use crate::test_utils::comp_col;
+
+/// Get the row of A that is the pivot for each column of A
+fn pivots(solution: &SimpleSolution<f64>) -> Vec<usize> {
+    let row_perm = solution.row_perm.as_slice();
+    solution
+        .column_perm
+        .as_slice()
+        .iter()
+        .map(|&position| row_perm.iter().position(|&p| p == position).unwrap())
+        .collect()
+}
+
+fn check_solution(rows: &[Vec<f64>], x: &DenseMatrix<f64>, b: &[f64]) {
+    for (row, values) in rows.iter().enumerate() {
+        let ax: f64 = values.iter().zip(x.column(0)).map(|(a, x)| a * x).sum();
+        assert!((ax - b[row]).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn permutation_constructors_validate() {
+    assert!(RowPerm::new(vec![2, 0, 1], 3).is_ok());
+    assert!(matches!(RowPerm::new(vec![0, 0, 1], 3), Err(Error::PermutationError)));
+    assert!(matches!(RowPerm::new(vec![0, 1], 3), Err(Error::PermutationError)));
+    assert!(matches!(ColumnPerm::new(vec![0, 3, 1], 3), Err(Error::PermutationError)));
+}
+
+#[test]
+fn user_row_perm_overrides_partial_pivoting() {
+    // Partial pivoting would choose row 1 as the first pivot
+    let rows = vec![
+        vec![2.0, 1.0, 0.0],
+        vec![3.0, 10.0, 1.0],
+        vec![0.0, 1.0, 5.0],
+    ];
+    let b_values = vec![1.0, 2.0, 3.0];
+    let mut stat = CSuperluStat::new();
+    let solution = UserRowPerm {
+        a: comp_col(&rows),
+        b: DenseMatrix::from_vectors(3, 1, b_values.clone()),
+        row_perm: RowPerm::new(vec![0, 1, 2], 3).unwrap(),
+    }
+    .solve(&mut stat, ColumnPermPolicy::Natural)
+    .unwrap();
+    assert_eq!(solution.row_perm.as_slice(), &[0, 1, 2]);
+    check_solution(&rows, &solution.x, &b_values);
+
+    // Swapping the rows moves the pivots off the diagonal of A
+    let rows = vec![
+        vec![0.0, 4.0, 1.0],
+        vec![3.0, 1.0, 0.0],
+        vec![0.0, 1.0, 5.0],
+    ];
+    let solution = UserRowPerm {
+        a: comp_col(&rows),
+        b: DenseMatrix::from_vectors(3, 1, b_values.clone()),
+        row_perm: RowPerm::new(vec![1, 0, 2], 3).unwrap(),
+    }
+    .solve(&mut stat, ColumnPermPolicy::Natural)
+    .unwrap();
+    assert_eq!(pivots(&solution), vec![1, 0, 2]);
+    check_solution(&rows, &solution.x, &b_values);
+}
+
+#[test]
+fn same_pattern_same_row_perm_reuses_pivots() {
+    let rows = vec![
+        vec![4.0, 1.0, 0.0, 2.0],
+        vec![1.0, 5.0, 1.0, 0.0],
+        vec![0.0, 2.0, 6.0, 1.0],
+        vec![3.0, 0.0, 1.0, 3.0],
+    ];
+    let b_values = vec![1.0, -1.0, 2.0, 0.5];
+    let mut stat = CSuperluStat::new();
+    let first = SimpleSystem {
+        a: comp_col(&rows),
+        b: DenseMatrix::from_vectors(4, 1, b_values.clone()),
+    }
+    .solve(&mut stat, ColumnPermPolicy::ColAMD)
+    .unwrap();
+
+    // Partial pivoting would choose different pivots for the
+    // new values
+    let new_rows: Vec<Vec<f64>> = rows
+        .iter()
+        .enumerate()
+        .map(|(row, values)| values.iter().map(|v| v * (1.0 + 10.0 * row as f64)).collect())
+        .collect();
+    let second = SamePatternSameRowPerm {
+        a: comp_col(&new_rows),
+        b: DenseMatrix::from_vectors(4, 1, b_values.clone()),
+        column_perm: first.column_perm.clone(),
+        row_perm: first.row_perm.clone(),
+    }
+    .solve(&mut stat)
+    .unwrap();
+    assert_eq!(pivots(&second), pivots(&first));
+    check_solution(&new_rows, &second.x, &b_values);
+}
+
+#[test]
+fn user_row_perm_checks_dimensions() {
+    let mut stat = CSuperluStat::new();
+    let rows = vec![vec![1.0, 0.0], vec![0.0, 1.0]];
+    let result = UserRowPerm {
+        a: comp_col(&rows),
+        b: DenseMatrix::from_vectors(2, 1, vec![1.0, 1.0]),
+        row_perm: unsafe { RowPerm::from_raw(vec![0, 0]) },
+    }
+    .solve(&mut stat, ColumnPermPolicy::Natural);
+    assert!(matches!(result, Err(SimpleError::Other(Error::PermutationError))));
+    let result = UserRowPerm {
+        a: comp_col(&rows),
+        b: DenseMatrix::from_vectors(3, 1, vec![1.0, 1.0, 1.0]),
+        row_perm: RowPerm::new(vec![0, 1], 2).unwrap(),
+    }
+    .solve(&mut stat, ColumnPermPolicy::Natural);
+    assert!(matches!(result, Err(SimpleError::Other(Error::DimensionMismatch))));
+    let result = UserRowPerm {
+        a: CompColMatrix::from_vectors(2, vec![1.0, 1.0], vec![0, 1], vec![0, 1, 2, 2]),
+        b: DenseMatrix::from_vectors(2, 1, vec![1.0, 1.0]),
+        row_perm: RowPerm::new(vec![0, 1], 2).unwrap(),
+    }
+    .solve(&mut stat, ColumnPermPolicy::Natural);
+    assert!(matches!(result, Err(SimpleError::Other(Error::DimensionMismatch))));
+}
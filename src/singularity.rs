@@ -0,0 +1,154 @@
+//! Diagnose why a matrix is singular
+//!
+//! When the simple driver finds a zero pivot, it returns
+//! [SimpleError::Singular] with the index of the singular column
+//! in the permuted matrix $P_rAP_c$. This module maps that pivot
+//! back to a column and row of the original matrix $A$, using the
+//! permutations, and checks whether the singularity is
+//! structural or numerical:
+//!
+//! * A matrix is structurally singular if it is singular for any
+//!   values of its non-zero elements. This happens if the
+//!   structural rank (the size of a maximum transversal, see
+//!   [transversal](crate::transversal)) is less than the size of
+//!   the matrix. In a circuit, it usually means
+//!   that a node is floating (it has no path to ground), or that
+//!   there is a loop of voltage sources.
+//! * Otherwise, the matrix is numerically singular: the pattern
+//!   allows a non-singular matrix, but the values cancel (or
+//!   nearly cancel) during the factorisation.
+//!
+//! For a structurally singular matrix, the rows and columns that
+//! are not matched in a maximum transversal are also reported.
+//! They are not unique, but each unmatched column is a column
+//! that cannot be given its own pivot, so they point to the
+//! equations and unknowns that caused the problem.
+//!
+
+use std::fmt;
+
+use crate::c::value_type::ValueType;
+use crate::comp_col::CompColMatrix;
+use crate::simple_driver::{ColumnPerm, RowPerm, SimpleError};
+use crate::transversal::maximum_matching;
+
+/// Whether a singular matrix is singular because of its pattern
+/// or because of its values
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SingularityKind {
+    /// The structural rank is less than the size of the matrix
+    Structural,
+    /// The matrix has full structural rank, but the values make
+    /// it singular
+    Numerical,
+}
+
+/// The result of analysing a singular matrix
+#[derive(Debug, Clone)]
+pub struct SingularityAnalysis {
+    /// The index of the zero pivot in $P_rAP_c$
+    pub pivot: usize,
+    /// The column of $A$ that was permuted to the pivot
+    pub column: usize,
+    /// The row of $A$ that was permuted to the pivot, if the
+    /// row permutation assigned one
+    pub row: Option<usize>,
+    /// Whether the singularity is structural or numerical
+    pub kind: SingularityKind,
+    /// The structural rank of $A$
+    pub structural_rank: usize,
+    /// The rows of $A$ that are not matched in a maximum
+    /// transversal (empty if $A$ has full structural rank)
+    pub unmatched_rows: Vec<usize>,
+    /// The columns of $A$ that are not matched in a maximum
+    /// transversal (empty if $A$ has full structural rank)
+    pub unmatched_columns: Vec<usize>,
+}
+
+impl fmt::Display for SingularityAnalysis {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Zero pivot {} at column {}", self.pivot, self.column)?;
+        if let Some(row) = self.row {
+            write!(f, ", row {row}")?;
+        }
+        match self.kind {
+            SingularityKind::Structural => write!(
+                f,
+                "; the matrix is structurally singular (structural rank {}), unmatched columns {:?}, unmatched rows {:?}",
+                self.structural_rank, self.unmatched_columns, self.unmatched_rows
+            ),
+            SingularityKind::Numerical => write!(f, "; the matrix is numerically singular"),
+        }
+    }
+}
+
+/// Analyse a singular matrix, given the index of the zero pivot
+/// in $P_rAP_c$ and the permutations used in the factorisation
+///
+/// # Panics
+///
+/// If the pivot is out of range, or the permutations do not
+/// match the size of a.
+///
+pub fn analyse_singularity<P: ValueType>(
+    a: &CompColMatrix<P>,
+    pivot: usize,
+    column_perm: &ColumnPerm,
+    row_perm: &RowPerm,
+) -> SingularityAnalysis {
+    let column_perm = column_perm.as_slice();
+    let row_perm = row_perm.as_slice();
+    assert!(column_perm.len() == a.num_columns(), "Column permutation does not match the matrix");
+    assert!(row_perm.len() == a.num_rows(), "Row permutation does not match the matrix");
+    assert!(pivot < a.num_columns(), "Pivot out of range");
+
+    // Element j of the permutation is the position of column
+    // (or row) j in the permuted matrix
+    let column = column_perm
+        .iter()
+        .position(|&position| position as usize == pivot)
+        .expect("Column permutation is not a permutation");
+    let row = row_perm.iter().position(|&position| position as usize == pivot);
+
+    let matching = maximum_matching(a);
+    let structural_rank = matching.size();
+    let kind = if structural_rank < a.num_columns().min(a.num_rows()) {
+        SingularityKind::Structural
+    } else {
+        SingularityKind::Numerical
+    };
+    let unmatched = |matches: &[Option<usize>]| {
+        matches
+            .iter()
+            .enumerate()
+            .filter(|(_, matched)| matched.is_none())
+            .map(|(index, _)| index)
+            .collect()
+    };
+    SingularityAnalysis {
+        pivot,
+        column,
+        row,
+        kind,
+        structural_rank,
+        unmatched_rows: unmatched(&matching.row_match),
+        unmatched_columns: unmatched(&matching.column_match),
+    }
+}
+
+impl<P: ValueType> SimpleError<P> {
+    /// Analyse the singular matrix (see
+    /// [singularity](crate::singularity)). Returns None for
+    /// errors other than [SimpleError::Singular].
+    pub fn analyse_singularity(&self) -> Option<SingularityAnalysis> {
+        match self {
+            Self::Singular { a, singular_column, column_perm, row_perm, .. } => {
+                Some(analyse_singularity(a, *singular_column, column_perm, row_perm))
+            }
+            Self::Other(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
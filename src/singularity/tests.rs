@@ -0,0 +1,90 @@
+use super::*;
+use crate::c::options::ColumnPermPolicy;
+use crate::c::stat::CSuperluStat;
+use crate::dense::DenseMatrix;
+use crate::simple_driver::SimpleSystem;
+
+fn solve_singular(a: CompColMatrix<f64>) -> SimpleError<f64> {
+    let b = DenseMatrix::from_vectors(a.num_rows(), 1, vec![1.0; a.num_rows()]);
+    let mut stat = CSuperluStat::new();
+    let result = SimpleSystem { a, b }.solve(&mut stat, ColumnPermPolicy::Natural);
+    match result {
+        Err(err) => err,
+        Ok(_) => panic!("Expected the matrix to be singular"),
+    }
+}
+
+#[test]
+fn numerically_singular_matrix() {
+    //  2  1  0
+    //  4  2  0
+    //  0  0  1
+    let a = CompColMatrix::from_vectors(
+        3,
+        vec![2.0, 4.0, 1.0, 2.0, 1.0],
+        vec![0, 1, 0, 1, 2],
+        vec![0, 2, 4, 5],
+    );
+    let analysis = solve_singular(a).analyse_singularity().unwrap();
+    assert_eq!(analysis.pivot, 1);
+    assert_eq!(analysis.column, 1);
+    assert_eq!(analysis.kind, SingularityKind::Numerical);
+    assert_eq!(analysis.structural_rank, 3);
+    assert!(analysis.unmatched_rows.is_empty());
+    assert!(analysis.unmatched_columns.is_empty());
+}
+
+#[test]
+fn structurally_singular_matrix() {
+    // Columns 1 and 2 only have an element in row 0, so one of
+    // them cannot be matched
+    //  1  1  1
+    //  1  0  0
+    //  1  0  0
+    let a = CompColMatrix::from_vectors(
+        3,
+        vec![1.0, 1.0, 1.0, 1.0, 1.0],
+        vec![0, 1, 2, 0, 0],
+        vec![0, 3, 4, 5],
+    );
+    let analysis = solve_singular(a).analyse_singularity().unwrap();
+    assert_eq!(analysis.kind, SingularityKind::Structural);
+    assert_eq!(analysis.structural_rank, 2);
+    assert_eq!(analysis.unmatched_columns.len(), 1);
+    assert!([1, 2].contains(&analysis.unmatched_columns[0]));
+    assert_eq!(analysis.unmatched_rows.len(), 1);
+    assert!([1, 2].contains(&analysis.unmatched_rows[0]));
+}
+
+#[test]
+fn pivot_is_mapped_through_permutations() {
+    // The zero pivot is the third column of AP_c, which is
+    // column 0 of A, in the third row of P_rA, which is row 2
+    let a = CompColMatrix::from_vectors(3, vec![1.0, 2.0], vec![0, 1], vec![0, 0, 1, 2]);
+    let column_perm = unsafe { ColumnPerm::from_raw(vec![2, 0, 1]) };
+    let row_perm = unsafe { RowPerm::from_raw(vec![0, 1, 2]) };
+    let analysis = analyse_singularity(&a, 2, &column_perm, &row_perm);
+    assert_eq!(analysis.column, 0);
+    assert_eq!(analysis.row, Some(2));
+    assert_eq!(analysis.kind, SingularityKind::Structural);
+    assert_eq!(analysis.unmatched_columns, vec![0]);
+    assert_eq!(analysis.unmatched_rows, vec![2]);
+}
+
+#[test]
+fn matching_requires_augmenting_paths() {
+    // The cheap assignment matches column 0 to row 0, so column
+    // 1 (which only has row 0) needs an augmenting path
+    //  1  1  0
+    //  1  0  1
+    //  0  0  1
+    let a = CompColMatrix::from_vectors(
+        3,
+        vec![1.0, 1.0, 1.0, 1.0, 1.0],
+        vec![0, 1, 0, 1, 2],
+        vec![0, 2, 3, 5],
+    );
+    let matching = maximum_matching(&a);
+    assert_eq!(matching.size(), 3);
+    assert_eq!(matching.column_match, vec![Some(1), Some(0), Some(2)]);
+}
@@ -163,7 +163,10 @@ impl<P: ValueType> SparseMat<P> {
 	    non_zero_values.push(self.non_zero_vals[key]);
 	    row_indices.push(key.0 as i32);
 	}
-	column_offsets.push(num_non_zeros as i32);
+	// Close the last non-empty column, and any empty columns after it
+	while column_offsets.len() < self.num_cols + 1 {
+	    column_offsets.push(num_non_zeros as i32);
+	}
 
 	CompColMatrix::from_vectors(self.num_rows, non_zero_values, row_indices, column_offsets)
     }
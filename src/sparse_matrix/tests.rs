@@ -147,3 +147,13 @@ fn invalid_resize_cols() {
     a.resize_cols(2);
 }
 
+
+#[test]
+fn compressed_column_trailing_empty_columns() {
+    let mut a = SparseMat::<f64>::new(3, 4);
+    a.insert(1, 0, 2.0);
+    a.insert(2, 1, 3.0);
+    let b = a.compressed_column_format();
+    assert_eq!(b.num_columns(), 4);
+    assert_eq!(b.column_offsets(), &[0, 1, 2, 2, 2]);
+}
@@ -0,0 +1,428 @@
+//! Plot the sparsity pattern of a matrix
+//!
+//! A spy plot draws one square cell for each stored element of a
+//! matrix. This module renders spy plots for [SparseMat],
+//! [CompColMatrix] and the factors in an [LUDecomp], as SVG or as
+//! PPM images (and as PNG images, if the `png` cargo feature is
+//! enabled).
+//!
+//! Plots can be divided into blocks by horizontal and vertical
+//! partition lines, and the cells can be coloured by the
+//! magnitude of the element (on a log scale). The function
+//! [SpyPlot::fill_in] overlays the pattern of the original matrix
+//! $A$ with the $L$ and $U$ factors of $P_rAP_c$, colouring the
+//! fill-in (elements of $L$ and $U$ which are not in $A$)
+//! differently. This makes it possible to compare the effect of
+//! column ordering policies on the factorisation.
+//!
+//! ```ignore
+//! let plot = SpyPlot::fill_in(&a, &lu, &column_perm, &row_perm)
+//!     .partition(vec![10, 20])
+//!     .colour_by_magnitude(true);
+//! std::fs::write("lu.svg", plot.to_svg())?;
+//! ```
+//!
+
+use std::fmt::Write as _;
+use std::io::{self, Write};
+
+use crate::c::value_type::ValueType;
+use crate::comp_col::CompColMatrix;
+use crate::lu_decomp::LUDecomp;
+use crate::simple_driver::{ColumnPerm, RowPerm};
+use crate::sparse_matrix::SparseMat;
+
+/// Whether an element in a spy plot is part of the original
+/// matrix, or was created during the factorisation
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EntryKind {
+    /// The element is stored in the original matrix
+    Original,
+    /// The element is fill-in (stored in the factors, but
+    /// not in the original matrix)
+    FillIn,
+}
+
+/// An element shown in a spy plot
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpyEntry {
+    pub row: usize,
+    pub col: usize,
+    /// The absolute value of the element
+    pub magnitude: f64,
+    pub kind: EntryKind,
+}
+
+type Rgb = [u8; 3];
+
+const BACKGROUND: Rgb = [255, 255, 255];
+const PARTITION: Rgb = [150, 150, 150];
+const ORIGINAL_LIGHT: Rgb = [158, 202, 225];
+const ORIGINAL_DARK: Rgb = [8, 48, 107];
+const FILL_IN_LIGHT: Rgb = [252, 146, 114];
+const FILL_IN_DARK: Rgb = [153, 0, 13];
+
+/// A spy plot of the sparsity pattern of a matrix
+///
+/// Create a plot using one of the from_* functions (or
+/// [fill_in](Self::fill_in)), set the display options, and then
+/// render it using [to_svg](Self::to_svg) or one of the write_*
+/// functions.
+///
+#[derive(Debug, Clone)]
+pub struct SpyPlot {
+    num_rows: usize,
+    num_columns: usize,
+    entries: Vec<SpyEntry>,
+    row_partition: Vec<usize>,
+    column_partition: Vec<usize>,
+    colour_by_magnitude: bool,
+    cell_size: usize,
+}
+
+impl SpyPlot {
+    /// Create a spy plot from a list of elements
+    ///
+    /// # Panics
+    ///
+    /// If any element is out of range.
+    ///
+    pub fn from_entries(num_rows: usize, num_columns: usize, entries: Vec<SpyEntry>) -> Self {
+        for entry in &entries {
+            assert!(
+                entry.row < num_rows && entry.col < num_columns,
+                "Spy plot element ({}, {}) out of range for matrix size {}x{}",
+                entry.row, entry.col, num_rows, num_columns
+            );
+        }
+        Self {
+            num_rows,
+            num_columns,
+            entries,
+            row_partition: Vec::new(),
+            column_partition: Vec::new(),
+            colour_by_magnitude: false,
+            cell_size: 4,
+        }
+    }
+
+    /// Plot the non-zero elements of a sparse matrix
+    pub fn from_sparse_mat<P: ValueType>(a: &SparseMat<P>) -> Self {
+        let entries = a
+            .non_zero_vals()
+            .iter()
+            .map(|(&(row, col), &value)| original(row, col, value))
+            .collect();
+        Self::from_entries(a.num_rows(), a.num_cols(), entries)
+    }
+
+    /// Plot the stored elements of a compressed-column matrix
+    pub fn from_comp_col<P: ValueType>(a: &CompColMatrix<P>) -> Self {
+        let column_offsets = a.column_offsets();
+        let mut entries = Vec::with_capacity(a.non_zero_values().len());
+        for col in 0..a.num_columns() {
+            let start = column_offsets[col] as usize;
+            let end = column_offsets[col + 1] as usize;
+            for k in start..end {
+                entries.push(original(a.row_indices()[k] as usize, col, a.non_zero_values()[k]));
+            }
+        }
+        Self::from_entries(a.num_rows(), a.num_columns(), entries)
+    }
+
+    /// Plot the stored elements of $L$ and $U$ in one plot (the
+    /// unit diagonal of $L$ is not shown)
+    pub fn from_lu<P: ValueType>(lu: &LUDecomp<P>) -> Self {
+        Self::from_entries(lu.num_rows(), lu.num_columns(), lu_entries(lu))
+    }
+
+    /// Plot the stored elements of $L$, including its unit diagonal
+    pub fn from_l<P: ValueType>(lu: &LUDecomp<P>) -> Self {
+        let mut entries = Vec::new();
+        for col in 0..lu.num_columns() {
+            entries.push(original(col, col, P::one()));
+            entries.extend(lu.l_column(col).map(|(row, value)| original(row, col, value)));
+        }
+        Self::from_entries(lu.num_rows(), lu.num_columns(), entries)
+    }
+
+    /// Plot the stored elements of $U$
+    pub fn from_u<P: ValueType>(lu: &LUDecomp<P>) -> Self {
+        let mut entries = Vec::new();
+        for col in 0..lu.num_columns() {
+            entries.extend(lu.u_column(col).map(|(row, value)| original(row, col, value)));
+        }
+        Self::from_entries(lu.num_rows(), lu.num_columns(), entries)
+    }
+
+    /// Plot $L$ and $U$, marking the fill-in
+    ///
+    /// The factors are for the permuted matrix $P_rAP_c$, so the
+    /// pattern of $A$ is permuted using the row and column
+    /// permutations before it is compared with $L$ and $U$. The
+    /// elements of $L$ and $U$ which are not in the permuted
+    /// $A$ are marked as [EntryKind::FillIn].
+    ///
+    /// # Panics
+    ///
+    /// If the sizes of the matrices and permutations do not
+    /// match.
+    ///
+    pub fn fill_in<P: ValueType>(
+        a: &CompColMatrix<P>,
+        lu: &LUDecomp<P>,
+        column_perm: &ColumnPerm,
+        row_perm: &RowPerm,
+    ) -> Self {
+        let perm_c = column_perm.as_slice();
+        let perm_r = row_perm.as_slice();
+        assert!(
+            a.num_rows() == lu.num_rows() && a.num_columns() == lu.num_columns(),
+            "Matrix and factors must have the same size"
+        );
+        assert!(
+            perm_c.len() == a.num_columns() && perm_r.len() == a.num_rows(),
+            "Permutation lengths must match the matrix size"
+        );
+        let mut in_a = vec![Vec::new(); a.num_columns()];
+        let column_offsets = a.column_offsets();
+        for col in 0..a.num_columns() {
+            let start = column_offsets[col] as usize;
+            let end = column_offsets[col + 1] as usize;
+            for &row in &a.row_indices()[start..end] {
+                in_a[perm_c[col] as usize].push(perm_r[row as usize] as usize);
+            }
+        }
+        for rows in in_a.iter_mut() {
+            rows.sort_unstable();
+        }
+        let mut entries = lu_entries(lu);
+        for entry in entries.iter_mut() {
+            if in_a[entry.col].binary_search(&entry.row).is_err() {
+                entry.kind = EntryKind::FillIn;
+            }
+        }
+        Self::from_entries(lu.num_rows(), lu.num_columns(), entries)
+    }
+
+    /// Get the elements shown in the plot
+    pub fn entries(&self) -> &[SpyEntry] {
+        &self.entries
+    }
+
+    /// Get the number of elements marked as fill-in
+    pub fn num_fill_in(&self) -> usize {
+        self.entries.iter().filter(|entry| entry.kind == EntryKind::FillIn).count()
+    }
+
+    /// Draw horizontal partition lines before each of the
+    /// given rows
+    pub fn row_partition(mut self, boundaries: Vec<usize>) -> Self {
+        self.row_partition = boundaries;
+        self
+    }
+
+    /// Draw vertical partition lines before each of the
+    /// given columns
+    pub fn column_partition(mut self, boundaries: Vec<usize>) -> Self {
+        self.column_partition = boundaries;
+        self
+    }
+
+    /// Use the same partition for the rows and columns
+    pub fn partition(self, boundaries: Vec<usize>) -> Self {
+        self.row_partition(boundaries.clone()).column_partition(boundaries)
+    }
+
+    /// Colour the elements by magnitude (darker elements are
+    /// larger, on a log scale), instead of using a single colour
+    pub fn colour_by_magnitude(mut self, colour_by_magnitude: bool) -> Self {
+        self.colour_by_magnitude = colour_by_magnitude;
+        self
+    }
+
+    /// Set the size of each element, in pixels
+    pub fn cell_size(mut self, cell_size: usize) -> Self {
+        self.cell_size = cell_size.max(1);
+        self
+    }
+
+    /// Get the colour of every element, in the same order as
+    /// the entries
+    fn colours(&self) -> Vec<Rgb> {
+        let log_magnitudes = || {
+            self.entries
+                .iter()
+                .filter(|entry| entry.magnitude > 0.0)
+                .map(|entry| entry.magnitude.log10())
+        };
+        let min = log_magnitudes().fold(f64::INFINITY, f64::min);
+        let max = log_magnitudes().fold(f64::NEG_INFINITY, f64::max);
+        self.entries
+            .iter()
+            .map(|entry| {
+                let (light, dark) = match entry.kind {
+                    EntryKind::Original => (ORIGINAL_LIGHT, ORIGINAL_DARK),
+                    EntryKind::FillIn => (FILL_IN_LIGHT, FILL_IN_DARK),
+                };
+                if !self.colour_by_magnitude {
+                    return dark;
+                }
+                let t = if entry.magnitude > 0.0 && max > min {
+                    (entry.magnitude.log10() - min) / (max - min)
+                } else if entry.magnitude > 0.0 {
+                    1.0
+                } else {
+                    0.0
+                };
+                interpolate(light, dark, t)
+            })
+            .collect()
+    }
+
+    /// Render the plot as an SVG document
+    ///
+    /// Each element is drawn as a unit square, and the image is
+    /// scaled by the cell size.
+    pub fn to_svg(&self) -> String {
+        let width = self.num_columns * self.cell_size;
+        let height = self.num_rows * self.cell_size;
+        let mut svg = String::new();
+        writeln!(
+            svg,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+             viewBox=\"0 0 {} {}\" shape-rendering=\"crispEdges\">",
+            self.num_columns, self.num_rows
+        ).unwrap();
+        writeln!(
+            svg,
+            "<rect width=\"{}\" height=\"{}\" fill=\"{}\"/>",
+            self.num_columns, self.num_rows, hex(BACKGROUND)
+        ).unwrap();
+        for (entry, colour) in self.entries.iter().zip(self.colours()) {
+            writeln!(
+                svg,
+                "<rect x=\"{}\" y=\"{}\" width=\"1\" height=\"1\" fill=\"{}\"/>",
+                entry.col, entry.row, hex(colour)
+            ).unwrap();
+        }
+        for &row in &self.row_partition {
+            writeln!(
+                svg,
+                "<line x1=\"0\" y1=\"{row}\" x2=\"{}\" y2=\"{row}\" stroke=\"{}\" \
+                 vector-effect=\"non-scaling-stroke\"/>",
+                self.num_columns, hex(PARTITION)
+            ).unwrap();
+        }
+        for &col in &self.column_partition {
+            writeln!(
+                svg,
+                "<line x1=\"{col}\" y1=\"0\" x2=\"{col}\" y2=\"{}\" stroke=\"{}\" \
+                 vector-effect=\"non-scaling-stroke\"/>",
+                self.num_rows, hex(PARTITION)
+            ).unwrap();
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// Write the plot to an SVG file (see [to_svg](Self::to_svg))
+    pub fn write_svg<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writer.write_all(self.to_svg().as_bytes())
+    }
+
+    /// Render the plot into an RGB pixel buffer, returning
+    /// the width, height and pixels (in row-major order)
+    fn rasterise(&self) -> (usize, usize, Vec<u8>) {
+        let cell_size = self.cell_size;
+        let width = self.num_columns * cell_size;
+        let height = self.num_rows * cell_size;
+        let mut pixels = BACKGROUND.repeat(width * height);
+        let mut set_pixel = |x: usize, y: usize, colour: Rgb| {
+            let offset = 3 * (y * width + x);
+            pixels[offset..offset + 3].copy_from_slice(&colour);
+        };
+        for (entry, colour) in self.entries.iter().zip(self.colours()) {
+            for y in entry.row * cell_size..(entry.row + 1) * cell_size {
+                for x in entry.col * cell_size..(entry.col + 1) * cell_size {
+                    set_pixel(x, y, colour);
+                }
+            }
+        }
+        for &row in self.row_partition.iter().filter(|&&row| row < self.num_rows) {
+            for x in 0..width {
+                set_pixel(x, row * cell_size, PARTITION);
+            }
+        }
+        for &col in self.column_partition.iter().filter(|&&col| col < self.num_columns) {
+            for y in 0..height {
+                set_pixel(col * cell_size, y, PARTITION);
+            }
+        }
+        (width, height, pixels)
+    }
+
+    /// Write the plot as a binary PPM (portable pixmap) image
+    pub fn write_ppm<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let (width, height, pixels) = self.rasterise();
+        write!(writer, "P6\n{width} {height}\n255\n")?;
+        writer.write_all(&pixels)
+    }
+
+    /// Write the plot as a PNG image
+    ///
+    /// This function requires the `png` cargo feature.
+    #[cfg(feature = "png")]
+    pub fn write_png<W: Write>(&self, writer: W) -> io::Result<()> {
+        let (width, height, pixels) = self.rasterise();
+        let to_u32 = |size: usize| {
+            u32::try_from(size).map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "Image too large"))
+        };
+        let mut encoder = png::Encoder::new(writer, to_u32(width)?, to_u32(height)?);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&pixels)?;
+        Ok(writer.finish()?)
+    }
+}
+
+/// Make an element of an original matrix
+fn original<P: ValueType>(row: usize, col: usize, value: P) -> SpyEntry {
+    SpyEntry {
+        row,
+        col,
+        magnitude: value.magnitude(),
+        kind: EntryKind::Original,
+    }
+}
+
+/// Get the stored elements of $L$ (without the unit diagonal)
+/// and $U$
+fn lu_entries<P: ValueType>(lu: &LUDecomp<P>) -> Vec<SpyEntry> {
+    let mut entries = Vec::new();
+    for col in 0..lu.num_columns() {
+        entries.extend(lu.u_column(col).map(|(row, value)| original(row, col, value)));
+        entries.extend(lu.l_column(col).map(|(row, value)| original(row, col, value)));
+    }
+    entries
+}
+
+/// Linearly interpolate between two colours (t = 0 gives a,
+/// t = 1 gives b)
+fn interpolate(a: Rgb, b: Rgb, t: f64) -> Rgb {
+    let t = t.clamp(0.0, 1.0);
+    let mut colour = [0; 3];
+    for k in 0..3 {
+        colour[k] = (a[k] as f64 + t * (b[k] as f64 - a[k] as f64)).round() as u8;
+    }
+    colour
+}
+
+/// Format a colour as an SVG hex colour
+fn hex(colour: Rgb) -> String {
+    format!("#{:02x}{:02x}{:02x}", colour[0], colour[1], colour[2])
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,89 @@
+use super::*;
+use crate::test_utils::{comp_col_3x3, lu_decomp_3x3};
+
+#[test]
+fn sparse_mat_and_comp_col_agree() {
+    let mut a = SparseMat::new(3, 3);
+    a.insert(0, 0, 1.0);
+    a.insert(2, 0, 4.0);
+    a.insert(1, 1, 3.0);
+    a.insert(0, 2, 2.0);
+    a.insert(2, 2, 5.0);
+    let mut from_sparse_mat = SpyPlot::from_sparse_mat(&a).entries().to_vec();
+    let mut from_comp_col = SpyPlot::from_comp_col(&comp_col_3x3()).entries().to_vec();
+    from_sparse_mat.sort_by_key(|entry| (entry.col, entry.row));
+    from_comp_col.sort_by_key(|entry| (entry.col, entry.row));
+    assert_eq!(from_sparse_mat, from_comp_col);
+}
+
+#[test]
+fn fill_in_with_identity_perms() {
+    let a = comp_col_3x3();
+    let lu = lu_decomp_3x3();
+    let column_perm = unsafe { ColumnPerm::from_raw(vec![0, 1, 2]) };
+    let row_perm = unsafe { RowPerm::from_raw(vec![0, 1, 2]) };
+    let plot = SpyPlot::fill_in(&a, &lu, &column_perm, &row_perm);
+    assert_eq!(plot.entries().len(), 7);
+    let fill_in: Vec<_> = plot
+        .entries()
+        .iter()
+        .filter(|entry| entry.kind == EntryKind::FillIn)
+        .map(|entry| (entry.row, entry.col))
+        .collect();
+    assert_eq!(fill_in, vec![(2, 1), (1, 2)]);
+}
+
+#[test]
+fn fill_in_with_permuted_rows() {
+    // With rows 1 and 2 swapped, the pattern of A no longer
+    // matches the factors
+    let a = comp_col_3x3();
+    let lu = lu_decomp_3x3();
+    let column_perm = unsafe { ColumnPerm::from_raw(vec![0, 1, 2]) };
+    let row_perm = unsafe { RowPerm::from_raw(vec![0, 2, 1]) };
+    let plot = SpyPlot::fill_in(&a, &lu, &column_perm, &row_perm);
+    assert_eq!(plot.num_fill_in(), 3);
+}
+
+#[test]
+fn svg_output() {
+    let plot = SpyPlot::from_comp_col(&comp_col_3x3()).cell_size(10).partition(vec![1]);
+    let svg = plot.to_svg();
+    assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"30\" height=\"30\""));
+    assert_eq!(svg.matches("width=\"1\" height=\"1\"").count(), 5);
+    assert_eq!(svg.matches("<line").count(), 2);
+    assert!(svg.ends_with("</svg>\n"));
+}
+
+#[test]
+fn ppm_output() {
+    let plot = SpyPlot::from_comp_col(&comp_col_3x3()).cell_size(2).column_partition(vec![2]);
+    let mut ppm = Vec::new();
+    plot.write_ppm(&mut ppm).unwrap();
+    let header = b"P6\n6 6\n255\n";
+    assert_eq!(&ppm[..header.len()], header);
+    let pixels = &ppm[header.len()..];
+    assert_eq!(pixels.len(), 6 * 6 * 3);
+    let pixel = |x: usize, y: usize| &pixels[3 * (y * 6 + x)..][..3];
+    assert_eq!(pixel(0, 0), ORIGINAL_DARK);
+    assert_eq!(pixel(1, 3), BACKGROUND);
+    assert_eq!(pixel(4, 1), PARTITION);
+    assert_eq!(pixel(5, 5), ORIGINAL_DARK);
+}
+
+#[test]
+fn magnitude_colours() {
+    let a = CompColMatrix::from_vectors(2, vec![1.0, -100.0], vec![0, 1], vec![0, 1, 2]);
+    let plot = SpyPlot::from_comp_col(&a).colour_by_magnitude(true);
+    assert_eq!(plot.colours(), vec![ORIGINAL_LIGHT, ORIGINAL_DARK]);
+    let plot = plot.colour_by_magnitude(false);
+    assert_eq!(plot.colours(), vec![ORIGINAL_DARK, ORIGINAL_DARK]);
+}
+
+#[cfg(feature = "png")]
+#[test]
+fn png_output() {
+    let mut png = Vec::new();
+    SpyPlot::from_comp_col(&comp_col_3x3()).write_png(&mut png).unwrap();
+    assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+}
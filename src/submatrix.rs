@@ -0,0 +1,336 @@
+//! Extract and combine parts of compressed-column matrices
+//!
+//! [CompColMatrix::submatrix] extracts the elements of $A$ in a
+//! set of rows and a set of columns (for example, to remove the
+//! ground node from a nodal matrix, or to extract the interior
+//! block of a discretisation). [CompColMatrix::column_slice],
+//! [CompColMatrix::drop_rows] and [CompColMatrix::drop_cols] are
+//! shortcuts for common sets. The result is a [Submatrix], which
+//! contains an [IndexMap] for the rows and the columns. The maps
+//! relate the numbering in the submatrix to the numbering in $A$,
+//! so that a right-hand side can be gathered from the original
+//! numbering, and a solution can be scattered back into it.
+//!
+//! [CompColMatrix::concat_horizontal] and
+//! [CompColMatrix::concat_vertical] join two matrices side by
+//! side or one above the other. The rows (or columns) of the
+//! second matrix are numbered after the rows (or columns) of the
+//! first.
+//!
+//! The row indices in each column of the results are sorted.
+//!
+
+use std::fmt;
+use std::ops::Range;
+
+use crate::c::value_type::ValueType;
+use crate::comp_col::{to_index, CompColMatrix};
+
+/// Errors that can occur when extracting or combining matrices
+#[derive(Debug, PartialEq)]
+pub enum SubmatrixError {
+    /// The index is not less than the number of rows (or
+    /// columns) of the matrix
+    IndexOutOfRange { index: usize, size: usize },
+    /// The index appears more than once in the set of rows (or
+    /// columns)
+    DuplicateIndex(usize),
+    /// The matrices cannot be joined, because they do not have
+    /// the same number of rows (or columns)
+    DimensionMismatch,
+}
+
+impl std::error::Error for SubmatrixError {}
+
+impl fmt::Display for SubmatrixError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::IndexOutOfRange { index, size } =>
+                write!(f, "Index {index} is out of range for size {size}"),
+            Self::DuplicateIndex(index) => write!(f, "Index {index} appears more than once"),
+            Self::DimensionMismatch => write!(f, "Incompatible matrix dimensions"),
+        }
+    }
+}
+
+/// Maps the rows (or columns) of a submatrix to the rows (or
+/// columns) of the original matrix
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndexMap {
+    original_indices: Vec<usize>,
+    new_indices: Vec<Option<usize>>,
+}
+
+impl IndexMap {
+    /// Make a map where index i of the submatrix is
+    /// original_indices\[i\] in a matrix of size original_size
+    ///
+    /// # Errors
+    ///
+    /// If an index is out of range, or appears more than once.
+    ///
+    pub fn new(original_indices: Vec<usize>, original_size: usize) -> Result<Self, SubmatrixError> {
+        let mut new_indices = vec![None; original_size];
+        for (new_index, &index) in original_indices.iter().enumerate() {
+            match new_indices.get_mut(index) {
+                None => return Err(SubmatrixError::IndexOutOfRange { index, size: original_size }),
+                Some(Some(_)) => return Err(SubmatrixError::DuplicateIndex(index)),
+                Some(new) => *new = Some(new_index),
+            }
+        }
+        Ok(Self { original_indices, new_indices })
+    }
+
+    /// Make a map which keeps all the indices except for the
+    /// dropped ones (in the original order)
+    ///
+    /// # Errors
+    ///
+    /// If a dropped index is out of range.
+    ///
+    pub fn without(dropped: &[usize], original_size: usize) -> Result<Self, SubmatrixError> {
+        let mut is_dropped = vec![false; original_size];
+        for &index in dropped {
+            match is_dropped.get_mut(index) {
+                Some(flag) => *flag = true,
+                None => return Err(SubmatrixError::IndexOutOfRange { index, size: original_size }),
+            }
+        }
+        let kept = (0..original_size).filter(|&index| !is_dropped[index]).collect();
+        Self::new(kept, original_size)
+    }
+
+    /// Get the number of indices in the submatrix
+    pub fn len(&self) -> usize {
+        self.original_indices.len()
+    }
+
+    /// Check whether the submatrix has no indices
+    pub fn is_empty(&self) -> bool {
+        self.original_indices.is_empty()
+    }
+
+    /// Get the number of indices in the original matrix
+    pub fn original_size(&self) -> usize {
+        self.new_indices.len()
+    }
+
+    /// Get the original index of each index in the submatrix
+    pub fn original_indices(&self) -> &[usize] {
+        &self.original_indices
+    }
+
+    /// Get the original index of an index in the submatrix
+    ///
+    /// # Panics
+    ///
+    /// If the index is out of range for the submatrix.
+    ///
+    pub fn to_original(&self, index: usize) -> usize {
+        self.original_indices[index]
+    }
+
+    /// Get the index in the submatrix of an original index, or
+    /// None if it is not in the submatrix
+    pub fn to_new(&self, original_index: usize) -> Option<usize> {
+        self.new_indices.get(original_index).copied().flatten()
+    }
+
+    /// Pick out the values at the indices in the submatrix from
+    /// a vector in the original numbering (for example, to get
+    /// the right-hand side for a submatrix)
+    ///
+    /// # Panics
+    ///
+    /// If the length of values is not the original size.
+    ///
+    pub fn gather<P: ValueType>(&self, values: &[P]) -> Vec<P> {
+        assert!(values.len() == self.original_size(), "Vector does not match the original size");
+        self.original_indices.iter().map(|&index| values[index]).collect()
+    }
+
+    /// Write the values of a vector in the submatrix numbering
+    /// into a vector in the original numbering (for example, to
+    /// scatter a solution back). Other elements of the target are
+    /// not changed.
+    ///
+    /// # Panics
+    ///
+    /// If the lengths of the vectors do not match the map.
+    ///
+    pub fn scatter<P: ValueType>(&self, values: &[P], target: &mut [P]) {
+        assert!(values.len() == self.len(), "Vector does not match the submatrix size");
+        assert!(target.len() == self.original_size(), "Vector does not match the original size");
+        for (&index, &value) in self.original_indices.iter().zip(values) {
+            target[index] = value;
+        }
+    }
+}
+
+/// A matrix extracted from another matrix, with the maps from
+/// its rows and columns to the rows and columns of the original
+#[derive(Debug)]
+pub struct Submatrix<P: ValueType> {
+    /// The extracted matrix
+    pub matrix: CompColMatrix<P>,
+    /// The original row of each row of the matrix
+    pub row_map: IndexMap,
+    /// The original column of each column of the matrix
+    pub column_map: IndexMap,
+}
+
+/// Builds a compressed-column matrix one column at a time,
+/// sorting the row indices in each column
+struct ColumnBuilder<P: ValueType> {
+    non_zero_values: Vec<P>,
+    row_indices: Vec<i32>,
+    column_offsets: Vec<i32>,
+    column: Vec<(usize, P)>,
+}
+
+impl<P: ValueType> ColumnBuilder<P> {
+    fn new() -> Self {
+        Self {
+            non_zero_values: Vec::new(),
+            row_indices: Vec::new(),
+            column_offsets: vec![0],
+            column: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, row: usize, value: P) {
+        self.column.push((row, value));
+    }
+
+    fn finish_column(&mut self) {
+        self.column.sort_unstable_by_key(|&(row, _)| row);
+        for (row, value) in self.column.drain(..) {
+            self.row_indices.push(to_index(row));
+            self.non_zero_values.push(value);
+        }
+        self.column_offsets.push(to_index(self.row_indices.len()));
+    }
+
+    fn build(self, num_rows: usize) -> CompColMatrix<P> {
+        CompColMatrix::from_vectors(num_rows, self.non_zero_values, self.row_indices, self.column_offsets)
+    }
+}
+
+impl<P: ValueType> CompColMatrix<P> {
+    /// Extract the elements in the rows and columns, in the
+    /// order they are given (see [submatrix](crate::submatrix))
+    ///
+    /// # Errors
+    ///
+    /// If a row or column is out of range, or appears more than
+    /// once.
+    ///
+    pub fn submatrix(&self, rows: &[usize], cols: &[usize]) -> Result<Submatrix<P>, SubmatrixError> {
+        let row_map = IndexMap::new(rows.to_vec(), self.num_rows())?;
+        let column_map = IndexMap::new(cols.to_vec(), self.num_columns())?;
+        Ok(self.extract(row_map, column_map))
+    }
+
+    /// Extract a range of columns (with all the rows)
+    ///
+    /// # Errors
+    ///
+    /// If the range is not inside the matrix.
+    ///
+    pub fn column_slice(&self, cols: Range<usize>) -> Result<Submatrix<P>, SubmatrixError> {
+        let cols: Vec<usize> = cols.collect();
+        self.submatrix(&(0..self.num_rows()).collect::<Vec<_>>(), &cols)
+    }
+
+    /// Remove the rows, keeping the other rows in their original
+    /// order
+    ///
+    /// # Errors
+    ///
+    /// If a row is out of range.
+    ///
+    pub fn drop_rows(&self, rows: &[usize]) -> Result<Submatrix<P>, SubmatrixError> {
+        let row_map = IndexMap::without(rows, self.num_rows())?;
+        let column_map = IndexMap::without(&[], self.num_columns())?;
+        Ok(self.extract(row_map, column_map))
+    }
+
+    /// Remove the columns, keeping the other columns in their
+    /// original order
+    ///
+    /// # Errors
+    ///
+    /// If a column is out of range.
+    ///
+    pub fn drop_cols(&self, cols: &[usize]) -> Result<Submatrix<P>, SubmatrixError> {
+        let row_map = IndexMap::without(&[], self.num_rows())?;
+        let column_map = IndexMap::without(cols, self.num_columns())?;
+        Ok(self.extract(row_map, column_map))
+    }
+
+    fn extract(&self, row_map: IndexMap, column_map: IndexMap) -> Submatrix<P> {
+        let mut builder = ColumnBuilder::new();
+        for &col in column_map.original_indices() {
+            for (row, value) in self.column(col) {
+                if let Some(new_row) = row_map.to_new(row) {
+                    builder.push(new_row, value);
+                }
+            }
+            builder.finish_column();
+        }
+        Submatrix {
+            matrix: builder.build(row_map.len()),
+            row_map,
+            column_map,
+        }
+    }
+
+    /// Join the matrices side by side, as $(A \\; B)$, where $A$
+    /// is this matrix
+    ///
+    /// # Errors
+    ///
+    /// If the matrices do not have the same number of rows.
+    ///
+    pub fn concat_horizontal(&self, b: &CompColMatrix<P>) -> Result<CompColMatrix<P>, SubmatrixError> {
+        if self.num_rows() != b.num_rows() {
+            return Err(SubmatrixError::DimensionMismatch);
+        }
+        let mut builder = ColumnBuilder::new();
+        for matrix in [self, b] {
+            for col in 0..matrix.num_columns() {
+                for (row, value) in matrix.column(col) {
+                    builder.push(row, value);
+                }
+                builder.finish_column();
+            }
+        }
+        Ok(builder.build(self.num_rows()))
+    }
+
+    /// Join the matrices one above the other, with this matrix
+    /// on top
+    ///
+    /// # Errors
+    ///
+    /// If the matrices do not have the same number of columns.
+    ///
+    pub fn concat_vertical(&self, b: &CompColMatrix<P>) -> Result<CompColMatrix<P>, SubmatrixError> {
+        if self.num_columns() != b.num_columns() {
+            return Err(SubmatrixError::DimensionMismatch);
+        }
+        let mut builder = ColumnBuilder::new();
+        for col in 0..self.num_columns() {
+            for (matrix, row_offset) in [(self, 0), (b, self.num_rows())] {
+                for (row, value) in matrix.column(col) {
+                    builder.push(row + row_offset, value);
+                }
+            }
+            builder.finish_column();
+        }
+        Ok(builder.build(self.num_rows() + b.num_rows()))
+    }
+}
+
+#[cfg(test)]
+mod tests;
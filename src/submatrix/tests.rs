@@ -0,0 +1,83 @@
+use super::*;
+use crate::test_utils::dense;
+
+/// The matrix
+///
+///  1  0 -3
+/// -2  4  0
+///  0  6  5
+fn test_matrix() -> CompColMatrix<f64> {
+    CompColMatrix::from_vectors(
+        3,
+        vec![1.0, -2.0, 4.0, 6.0, -3.0, 5.0],
+        vec![0, 1, 1, 2, 0, 2],
+        vec![0, 2, 4, 6],
+    )
+}
+
+#[test]
+fn extract_submatrix() {
+    let a = test_matrix();
+    // The rows are reordered, so the row indices need sorting
+    let sub = a.submatrix(&[2, 0], &[2, 1]).unwrap();
+    assert_eq!(dense(&sub.matrix), vec![vec![5.0, 6.0], vec![-3.0, 0.0]]);
+    assert_eq!(sub.matrix.row_indices(), &[0, 1, 0]);
+    assert_eq!(sub.row_map.original_indices(), &[2, 0]);
+    assert_eq!(sub.column_map.to_original(0), 2);
+    assert_eq!(sub.row_map.to_new(0), Some(1));
+    assert_eq!(sub.row_map.to_new(1), None);
+}
+
+#[test]
+fn invalid_index_sets() {
+    let a = test_matrix();
+    assert_eq!(
+        a.submatrix(&[0, 3], &[0]).unwrap_err(),
+        SubmatrixError::IndexOutOfRange { index: 3, size: 3 }
+    );
+    assert_eq!(a.submatrix(&[0], &[1, 1]).unwrap_err(), SubmatrixError::DuplicateIndex(1));
+    assert!(a.drop_cols(&[5]).is_err());
+    assert!(a.column_slice(2..4).is_err());
+}
+
+#[test]
+fn column_slice() {
+    let sub = test_matrix().column_slice(1..3).unwrap();
+    assert_eq!(dense(&sub.matrix), vec![vec![0.0, -3.0], vec![4.0, 0.0], vec![6.0, 5.0]]);
+    assert_eq!(sub.column_map.original_indices(), &[1, 2]);
+}
+
+#[test]
+fn drop_ground_node_and_scatter_solution() {
+    let a = test_matrix();
+    let sub = a.drop_rows(&[1]).unwrap().matrix.drop_cols(&[1]).unwrap();
+    assert_eq!(dense(&sub.matrix), vec![vec![1.0, -3.0], vec![0.0, 5.0]]);
+
+    let sub = a.drop_rows(&[1]).unwrap();
+    assert_eq!(sub.row_map.original_indices(), &[0, 2]);
+    let b = vec![10.0, 20.0, 30.0];
+    assert_eq!(sub.row_map.gather(&b), vec![10.0, 30.0]);
+
+    let sub = a.drop_cols(&[0]).unwrap();
+    let mut x = vec![0.0; 3];
+    sub.column_map.scatter(&[7.0, 8.0], &mut x);
+    assert_eq!(x, vec![0.0, 7.0, 8.0]);
+}
+
+#[test]
+fn concatenate() {
+    let a = test_matrix();
+    let column = CompColMatrix::from_vectors(3, vec![9.0], vec![1], vec![0, 1]);
+    let wide = a.concat_horizontal(&column).unwrap();
+    assert_eq!((wide.num_rows(), wide.num_columns()), (3, 4));
+    assert_eq!(dense(&wide)[1], vec![-2.0, 4.0, 0.0, 9.0]);
+
+    let row = CompColMatrix::from_vectors(1, vec![7.0], vec![0], vec![0, 0, 1, 1]);
+    let tall = a.concat_vertical(&row).unwrap();
+    assert_eq!((tall.num_rows(), tall.num_columns()), (4, 3));
+    assert_eq!(dense(&tall)[3], vec![0.0, 7.0, 0.0]);
+    assert_eq!(tall.row_indices(), &[0, 1, 1, 2, 3, 0, 2]);
+
+    assert_eq!(a.concat_horizontal(&row).unwrap_err(), SubmatrixError::DimensionMismatch);
+    assert_eq!(a.concat_vertical(&column).unwrap_err(), SubmatrixError::DimensionMismatch);
+}
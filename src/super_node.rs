@@ -1,9 +1,20 @@
 //! Functions to create matrices in super-node format
 //!
+//! The $L$ factor returned by SuperLU is stored in super-node
+//! format. A super-node is a range of contiguous columns which
+//! have the same sparsity structure below the diagonal block.
+//! The row indices are stored once for each super-node, and the
+//! values for each column are stored separately (including the
+//! values in the diagonal block, above the diagonal, which belong
+//! to $U$). Super-node format is documented in Section 2.3 of the
+//! SuperLU manual.
+//!
 
 use crate::{c::free::c_destroy_super_node_matrix, c::super_matrix::CSuperMatrix};
 use crate::c::value_type::ValueType;
+use crate::display::{DisplayView, MatrixDisplay, MatrixElements};
 use csuperlu_sys::SCformat;
+use std::fmt;
 
 /// Super-node matrix
 ///
@@ -22,20 +33,139 @@ impl<P: ValueType> SuperNodeMatrix<P> {
             marker: std::marker::PhantomData,
         }
     }
-    pub fn values(&mut self) -> &[P] {
+
+    fn store(&self) -> &SCformat {
+        unsafe { self.super_matrix.store::<SCformat>() }
+    }
+
+    /// Get the number of rows in the matrix
+    pub fn num_rows(&self) -> usize {
+        self.super_matrix.num_rows()
+    }
+
+    /// Get the number of columns in the matrix
+    pub fn num_columns(&self) -> usize {
+        self.super_matrix.num_columns()
+    }
+
+    /// Get the number of stored values in the matrix
+    pub fn num_non_zeros(&self) -> usize {
+        self.store().nnz as usize
+    }
+
+    /// Get the number of super-nodes in the matrix
+    pub fn num_super_nodes(&self) -> usize {
+        self.store().nsuper as usize + 1
+    }
+
+    /// Get the super-node which contains a column
+    ///
+    /// # Panics
+    ///
+    /// If the column index is out of range.
+    pub fn super_node_of_column(&self, col: usize) -> usize {
+        assert!(col < self.num_columns(), "Column index out of range");
+        unsafe { *self.store().col_to_sup.add(col) as usize }
+    }
+
+    /// Get the range of columns in a super-node
+    ///
+    /// # Panics
+    ///
+    /// If the super-node index is out of range.
+    pub fn super_node_columns(&self, super_node: usize) -> std::ops::Range<usize> {
+        assert!(super_node < self.num_super_nodes(), "Super-node index out of range");
+        let sup_to_col = self.store().sup_to_col;
+        unsafe {
+            *sup_to_col.add(super_node) as usize..*sup_to_col.add(super_node + 1) as usize
+        }
+    }
+
+    /// Get the row indices and values stored in a column
+    ///
+    /// The row indices are shared by all the columns in the
+    /// same super-node.
+    ///
+    /// # Panics
+    ///
+    /// If the column index is out of range.
+    pub fn column(&self, col: usize) -> (&[i32], &[P]) {
+        let first_col = self.super_node_columns(self.super_node_of_column(col)).start;
+        let store = self.store();
+        unsafe {
+            let row_start = *store.rowind_colptr.add(first_col) as usize;
+            let row_end = *store.rowind_colptr.add(first_col + 1) as usize;
+            let value_start = *store.nzval_colptr.add(col) as usize;
+            let len = row_end - row_start;
+            (
+                std::slice::from_raw_parts(store.rowind.add(row_start), len),
+                std::slice::from_raw_parts((store.nzval as *const P).add(value_start), len),
+            )
+        }
+    }
+
+    /// Get the value of the element at (row, column), which is
+    /// zero if the element is not stored
+    ///
+    /// # Panics
+    ///
+    /// If the row or column index is out of range.
+    pub fn value(&self, row: usize, col: usize) -> P {
+        assert!(row < self.num_rows(), "Row index out of range");
+        let (row_indices, values) = self.column(col);
+        match row_indices.iter().position(|&r| r as usize == row) {
+            Some(k) => values[k],
+            None => P::zero(),
+        }
+    }
+
+    pub fn values(&self) -> &[P] {
+        let store = self.store();
         unsafe {
-            let c_scformat = self.super_matrix.store::<SCformat>();
-            std::slice::from_raw_parts(c_scformat.nzval as *mut P, c_scformat.nnz as usize)
+            std::slice::from_raw_parts(store.nzval as *mut P, store.nnz as usize)
         }
     }
+
     pub fn super_matrix<'a>(&'a self) -> &'a CSuperMatrix {
         &self.super_matrix
     }
-    
+
+    /// Get a configurable view of the matrix for printing
+    /// (see [display](crate::display))
+    pub fn display(&self) -> MatrixDisplay<'_, P> {
+        MatrixDisplay::new(self, DisplayView::Sparse)
+    }
+
+    /// Print the matrix to stdout, preceded by a label
     pub fn print(&self, what: &str) {
-        unsafe {
-            P::c_print_super_node_matrix(what, &self.super_matrix);
-        }
+        println!("{what} = {self}");
+    }
+}
+
+impl<P: ValueType> MatrixElements<P> for SuperNodeMatrix<P> {
+    fn num_rows(&self) -> usize {
+        self.num_rows()
+    }
+    fn num_columns(&self) -> usize {
+        self.num_columns()
+    }
+    fn num_stored(&self) -> usize {
+        (0..self.num_columns()).map(|col| self.column(col).0.len()).sum()
+    }
+    fn stored_elements(&self) -> Box<dyn Iterator<Item = (usize, usize, P)> + '_> {
+        Box::new((0..self.num_columns()).flat_map(move |col| {
+            let (row_indices, values) = self.column(col);
+            row_indices.iter().zip(values).map(move |(&row, &value)| (row as usize, col, value))
+        }))
+    }
+    fn element(&self, row: usize, col: usize) -> P {
+        self.value(row, col)
+    }
+}
+
+impl<P: ValueType> fmt::Display for SuperNodeMatrix<P> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.display().alternate(f.alternate()), f)
     }
 }
 
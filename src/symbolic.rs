@@ -0,0 +1,279 @@
+//! Symbolic analysis of the LU factorisation
+//!
+//! Before the numerical factorisation, the cost of factorising
+//! $AP_c$ can be predicted from the pattern of $A$ and the column
+//! permutation alone. The analysis follows the first steps of
+//! the SuperLU factorisation (sp_preorder and sp_coletree), but is
+//! implemented in Rust:
+//!
+//! * The column elimination tree is the elimination tree of
+//!   $(AP_c)^T(AP_c)$, computed without forming the product. The
+//!   parent of column $j$ is the first column $k > j$ such that
+//!   $U_{jk}$ may be non-zero. SuperLU postorders this tree, so
+//!   that each subtree is numbered consecutively, and uses the
+//!   postorder to permute the columns further.
+//! * The column counts are the numbers of non-zeros in the
+//!   columns of $R^T$, where $R$ is the Cholesky factor of
+//!   $(AP_c)^T(AP_c)$ (or the upper triangular factor in the QR
+//!   factorisation of $AP_c$). George and Ng showed that, for any
+//!   row permutation chosen by partial pivoting, the pattern of
+//!   $L$ is contained in the pattern of $R^T$ and the pattern of
+//!   $U$ is contained in the pattern of $R$. The predicted numbers
+//!   of non-zeros are therefore upper bounds, which are tight for
+//!   some values of the matrix elements. They can be used to
+//!   compare orderings and to size memory before factorising.
+//! * The fundamental supernodes are chains of consecutive columns
+//!   (in the postorder) that have the same pattern below the
+//!   diagonal in $R^T$. SuperLU uses (relaxed versions of) these
+//!   as the blocks of the supernodal factor.
+//!
+//! Column indices in the analysis refer to columns of $AP_c$,
+//! except for the supernodes, which are ranges of columns of the
+//! postordered matrix.
+//!
+
+use std::ops::Range;
+
+use crate::c::value_type::ValueType;
+use crate::comp_col::CompColMatrix;
+use crate::ordering::SparsityPattern;
+use crate::simple_driver::ColumnPerm;
+
+/// The result of the symbolic analysis of $AP_c$ (see
+/// [symbolic](crate::symbolic))
+#[derive(Debug, Clone)]
+pub struct SymbolicAnalysis {
+    /// The parent of each column in the column elimination tree.
+    /// Roots have the parent num_columns.
+    pub etree: Vec<usize>,
+    /// The columns in postorder (element k is the column that
+    /// is numbered k in the postorder)
+    pub postorder: Vec<usize>,
+    /// The number of non-zeros (including the diagonal) in each
+    /// column of $R^T$
+    pub column_counts: Vec<usize>,
+    /// The fundamental supernodes of the postordered matrix
+    pub supernodes: Vec<Range<usize>>,
+    /// The column permutation combined with the postorder, which
+    /// is the permutation SuperLU uses in the factorisation
+    pub column_perm: ColumnPerm,
+}
+
+impl SymbolicAnalysis {
+    /// Analyse the pattern of $AP_c$
+    ///
+    /// # Panics
+    ///
+    /// If the length of the column permutation does not match
+    /// the number of columns.
+    ///
+    pub fn new(pattern: &SparsityPattern, column_perm: &ColumnPerm) -> Self {
+        let num_columns = pattern.num_columns();
+        let perm = column_perm.as_slice();
+        assert!(perm.len() == num_columns, "Column permutation does not match the matrix");
+
+        // The rows of AP_c, as lists of (permuted) columns
+        let mut rows = vec![Vec::new(); pattern.num_rows()];
+        let mut columns = vec![0; num_columns];
+        for (col, &position) in perm.iter().enumerate() {
+            columns[position as usize] = col;
+        }
+        for (position, &col) in columns.iter().enumerate() {
+            for &row in pattern.column(col) {
+                rows[row as usize].push(position);
+            }
+        }
+
+        let etree = column_etree(&rows, num_columns);
+        let postorder = postorder(&etree);
+        let column_counts = column_counts(&rows, &etree);
+        let supernodes = fundamental_supernodes(&etree, &postorder, &column_counts);
+
+        let mut postorder_position = vec![0; num_columns];
+        for (k, &position) in postorder.iter().enumerate() {
+            postorder_position[position] = k as i32;
+        }
+        let column_perm = ColumnPerm::new(
+            perm.iter().map(|&position| postorder_position[position as usize]).collect(),
+            num_columns,
+        )
+        .expect("Postorder is not a permutation");
+
+        Self {
+            etree,
+            postorder,
+            column_counts,
+            supernodes,
+            column_perm,
+        }
+    }
+
+    /// Get the upper bound on the number of non-zeros in $L$
+    /// (including the diagonal)
+    pub fn predicted_nnz_l(&self) -> usize {
+        self.column_counts.iter().sum()
+    }
+
+    /// Get the upper bound on the number of non-zeros in $U$
+    /// (including the diagonal). This is the number of non-zeros
+    /// in $R$, so it is the same as the bound for $L$.
+    pub fn predicted_nnz_u(&self) -> usize {
+        self.column_counts.iter().sum()
+    }
+}
+
+impl<P: ValueType> CompColMatrix<P> {
+    /// Predict the structure of the LU factorisation of $AP_c$
+    /// (see [symbolic](crate::symbolic))
+    ///
+    /// # Panics
+    ///
+    /// If the length of the column permutation does not match
+    /// the number of columns.
+    ///
+    pub fn symbolic_analysis(&self, column_perm: &ColumnPerm) -> SymbolicAnalysis {
+        SymbolicAnalysis::new(&self.pattern(), column_perm)
+    }
+}
+
+/// Find the root of the set containing node, compressing the path
+fn find(set_parent: &mut [usize], mut node: usize) -> usize {
+    let mut root = node;
+    while set_parent[root] != root {
+        root = set_parent[root];
+    }
+    while set_parent[node] != root {
+        let next = set_parent[node];
+        set_parent[node] = root;
+        node = next;
+    }
+    root
+}
+
+/// Compute the column elimination tree, given the columns in each
+/// row (the same algorithm as sp_coletree). Each row is treated as
+/// a clique on its columns, connected through its first column.
+fn column_etree(rows: &[Vec<usize>], num_columns: usize) -> Vec<usize> {
+    let first_columns: Vec<Option<usize>> = rows.iter().map(|row| row.iter().copied().min()).collect();
+    let mut columns = vec![Vec::new(); num_columns];
+    for (row, cols) in rows.iter().enumerate() {
+        for &col in cols {
+            columns[col].push(row);
+        }
+    }
+
+    let mut etree = vec![num_columns; num_columns];
+    // Disjoint sets of columns, each with the root of its subtree
+    let mut set_parent: Vec<usize> = (0..num_columns).collect();
+    let mut set_root: Vec<usize> = (0..num_columns).collect();
+    for (col, col_rows) in columns.iter().enumerate() {
+        let mut col_set = col;
+        for &row in col_rows {
+            let first = first_columns[row].unwrap();
+            if first >= col {
+                continue;
+            }
+            let row_set = find(&mut set_parent, first);
+            let root = set_root[row_set];
+            if root != col {
+                etree[root] = col;
+                set_parent[row_set] = col_set;
+                col_set = find(&mut set_parent, col_set);
+                set_root[col_set] = col;
+            }
+        }
+    }
+    etree
+}
+
+/// Postorder a forest, visiting the children of each node in
+/// increasing order
+fn postorder(etree: &[usize]) -> Vec<usize> {
+    let num_nodes = etree.len();
+    let mut children = vec![Vec::new(); num_nodes + 1];
+    for (node, &parent) in etree.iter().enumerate() {
+        children[parent].push(node);
+    }
+    let mut order = Vec::with_capacity(num_nodes);
+    // Each stack entry is a node and the index of its next child
+    let mut stack = vec![(num_nodes, 0)];
+    while let Some((node, next_child)) = stack.pop() {
+        if let Some(&child) = children[node].get(next_child) {
+            stack.push((node, next_child + 1));
+            stack.push((child, 0));
+        } else if node < num_nodes {
+            order.push(node);
+        }
+    }
+    order
+}
+
+/// Count the non-zeros in each column of $R^T$, by merging the
+/// pattern of each column with the patterns of its children in
+/// the column elimination tree (row-merge symbolic factorisation)
+fn column_counts(rows: &[Vec<usize>], etree: &[usize]) -> Vec<usize> {
+    let num_columns = etree.len();
+    let mut rows_by_first_column = vec![Vec::new(); num_columns];
+    for row in rows {
+        if let Some(&first) = row.iter().min() {
+            rows_by_first_column[first].push(row);
+        }
+    }
+    let mut children = vec![Vec::new(); num_columns];
+    for (col, &parent) in etree.iter().enumerate() {
+        if parent < num_columns {
+            children[parent].push(col);
+        }
+    }
+
+    // Parents are numbered after their children, so the patterns
+    // of the children are always available
+    let mut patterns: Vec<Vec<usize>> = vec![Vec::new(); num_columns];
+    let mut counts = vec![0; num_columns];
+    for col in 0..num_columns {
+        let mut pattern = vec![col];
+        for row in &rows_by_first_column[col] {
+            pattern.extend(row.iter().copied());
+        }
+        for &child in &children[col] {
+            let child_pattern = std::mem::take(&mut patterns[child]);
+            pattern.extend(child_pattern.into_iter().filter(|&row| row != child));
+        }
+        pattern.sort_unstable();
+        pattern.dedup();
+        counts[col] = pattern.len();
+        patterns[col] = pattern;
+    }
+    counts
+}
+
+/// Find the fundamental supernodes: chains of columns in the
+/// postorder where each column is the only child of the next,
+/// and the patterns below the diagonal are the same
+fn fundamental_supernodes(etree: &[usize], postorder: &[usize], column_counts: &[usize]) -> Vec<Range<usize>> {
+    let num_columns = etree.len();
+    let mut num_children = vec![0; num_columns + 1];
+    for &parent in etree {
+        num_children[parent] += 1;
+    }
+    let mut supernodes = Vec::new();
+    let mut start = 0;
+    for k in 1..num_columns {
+        let previous = postorder[k - 1];
+        let col = postorder[k];
+        let same_supernode = etree[previous] == col
+            && num_children[col] == 1
+            && column_counts[previous] == column_counts[col] + 1;
+        if !same_supernode {
+            supernodes.push(start..k);
+            start = k;
+        }
+    }
+    if num_columns > 0 {
+        supernodes.push(start..num_columns);
+    }
+    supernodes
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,180 @@
+use super::*;
+use crate::c::stat::CSuperluStat;
+use crate::dense::DenseMatrix;
+use crate::simple_driver::{SamePattern, SimpleSolution};
+
+/// Make a matrix with the pattern given by the (row, column)
+/// elements, and a value of one in each
+fn from_elements(num_rows: usize, num_columns: usize, elements: &[(usize, usize)]) -> CompColMatrix<f64> {
+    let mut columns = vec![Vec::new(); num_columns];
+    for &(row, col) in elements {
+        columns[col].push(row as i32);
+    }
+    let mut column_offsets = vec![0];
+    let mut row_indices = Vec::new();
+    for mut column in columns {
+        column.sort_unstable();
+        column.dedup();
+        row_indices.extend(column);
+        column_offsets.push(row_indices.len() as i32);
+    }
+    let values = vec![1.0; row_indices.len()];
+    CompColMatrix::from_vectors(num_rows, values, row_indices, column_offsets)
+}
+
+fn natural(num_columns: usize) -> ColumnPerm {
+    ColumnPerm::new((0..num_columns as i32).collect(), num_columns).unwrap()
+}
+
+/// A pseudo-random pattern with a full diagonal
+fn random_elements(size: usize, per_column: usize, seed: u64) -> Vec<(usize, usize)> {
+    let mut state = seed;
+    let mut next = move || {
+        state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        (state >> 33) as usize
+    };
+    let mut elements: Vec<(usize, usize)> = (0..size).map(|k| (k, k)).collect();
+    for col in 0..size {
+        for _ in 0..per_column {
+            elements.push((next() % size, col));
+        }
+    }
+    elements
+}
+
+/// Compute the column counts and elimination tree by forming the
+/// pattern of A^TA and eliminating it densely
+fn brute_force(a: &CompColMatrix<f64>) -> (Vec<usize>, Vec<usize>) {
+    let n = a.num_columns();
+    let pattern = a.pattern();
+    let mut ata = vec![vec![false; n]; n];
+    for j in 0..n {
+        for k in 0..n {
+            ata[j][k] = pattern.column(j).iter().any(|row| pattern.column(k).contains(row));
+        }
+    }
+    let mut etree = vec![n; n];
+    let mut counts = vec![0; n];
+    for j in 0..n {
+        let below: Vec<usize> = (j + 1..n).filter(|&i| ata[i][j]).collect();
+        counts[j] = below.len() + 1;
+        if let Some(&parent) = below.first() {
+            etree[j] = parent;
+        }
+        for &i in &below {
+            for &k in &below {
+                ata[i][k] = true;
+            }
+        }
+    }
+    (etree, counts)
+}
+
+#[test]
+fn tridiagonal_is_a_chain() {
+    let a = from_elements(4, 4, &[(0, 0), (1, 0), (0, 1), (1, 1), (2, 1), (1, 2), (2, 2), (3, 2), (2, 3), (3, 3)]);
+    let analysis = a.symbolic_analysis(&natural(4));
+    assert_eq!(analysis.etree, vec![1, 2, 3, 4]);
+    assert_eq!(analysis.postorder, vec![0, 1, 2, 3]);
+    // A^TA is pentadiagonal
+    assert_eq!(analysis.column_counts, vec![3, 3, 2, 1]);
+    assert_eq!(analysis.predicted_nnz_l(), 9);
+    assert_eq!(analysis.supernodes, vec![0..1, 1..4]);
+}
+
+#[test]
+fn diagonal_is_a_forest() {
+    let a = from_elements(3, 3, &[(0, 0), (1, 1), (2, 2)]);
+    let analysis = a.symbolic_analysis(&natural(3));
+    assert_eq!(analysis.etree, vec![3, 3, 3]);
+    assert_eq!(analysis.column_counts, vec![1, 1, 1]);
+    assert_eq!(analysis.supernodes, vec![0..1, 1..2, 2..3]);
+}
+
+#[test]
+fn matches_brute_force() {
+    for seed in 0..10 {
+        let a = from_elements(30, 30, &random_elements(30, 2, seed));
+        let analysis = a.symbolic_analysis(&natural(30));
+        let (etree, counts) = brute_force(&a);
+        assert_eq!(analysis.etree, etree);
+        assert_eq!(analysis.column_counts, counts);
+    }
+}
+
+#[test]
+fn column_perm_is_applied() {
+    // A dense first column makes A^TA an arrow matrix, which
+    // fills in completely unless the first column is ordered last
+    let n = 6;
+    let mut elements: Vec<(usize, usize)> = (0..n).map(|k| (k, k)).collect();
+    for k in 1..n {
+        elements.push((k, 0));
+    }
+    let a = from_elements(n, n, &elements);
+    let analysis = a.symbolic_analysis(&natural(n));
+    assert_eq!(analysis.predicted_nnz_l(), n * (n + 1) / 2);
+
+    let reversed = ColumnPerm::new((0..n as i32).rev().collect(), n).unwrap();
+    let analysis = a.symbolic_analysis(&reversed);
+    assert_eq!(analysis.predicted_nnz_l(), 2 * n - 1);
+    assert_eq!(analysis.etree, vec![n - 1; n - 1].into_iter().chain([n]).collect::<Vec<_>>());
+}
+
+#[test]
+fn postorder_numbers_subtrees_consecutively() {
+    for seed in 0..5 {
+        let a = from_elements(40, 40, &random_elements(40, 1, seed));
+        let analysis = a.symbolic_analysis(&natural(40));
+        let mut position = vec![0; 40];
+        for (k, &col) in analysis.postorder.iter().enumerate() {
+            position[col] = k;
+        }
+        // Each child comes before its parent
+        for (col, &parent) in analysis.etree.iter().enumerate() {
+            if parent < 40 {
+                assert!(position[col] < position[parent]);
+            }
+        }
+        // Each supernode is a chain in the tree
+        for supernode in &analysis.supernodes {
+            for k in supernode.start + 1..supernode.end {
+                let previous = analysis.postorder[k - 1];
+                assert_eq!(analysis.etree[previous], analysis.postorder[k]);
+            }
+        }
+    }
+}
+
+#[test]
+fn factorisation_is_within_bounds() {
+    let n = 30;
+    let elements = random_elements(n, 2, 7);
+    let a = from_elements(n, n, &elements);
+    let mut values = vec![0.0; a.non_zero_values().len()];
+    // Make the matrix diagonally dominant, with distinct values
+    let (column_offsets, row_indices) = (a.column_offsets().to_vec(), a.row_indices().to_vec());
+    for col in 0..n {
+        for k in column_offsets[col] as usize..column_offsets[col + 1] as usize {
+            values[k] = if row_indices[k] as usize == col { 10.0 } else { 1.0 / (k + 2) as f64 };
+        }
+    }
+    let a = CompColMatrix::from_vectors(n, values, row_indices, column_offsets);
+    let analysis = a.symbolic_analysis(&natural(n));
+
+    let b = DenseMatrix::from_vectors(n, 1, vec![1.0; n]);
+    let mut stat = CSuperluStat::new();
+    let SimpleSolution { lu, .. } = SamePattern { a, b, column_perm: analysis.column_perm.clone() }
+        .solve(&mut stat)
+        .unwrap();
+    let count = |in_factor: &dyn Fn(usize, usize) -> bool| {
+        (0..n)
+            .flat_map(|col| (0..n).map(move |row| (row, col)))
+            .filter(|&(row, col)| in_factor(row, col))
+            .count()
+    };
+    let nnz_l = count(&|row, col| row >= col && lu.l_value(row, col) != 0.0);
+    let nnz_u = count(&|row, col| row <= col && lu.u_value(row, col) != 0.0);
+    assert!(nnz_l <= analysis.predicted_nnz_l());
+    assert!(nnz_u <= analysis.predicted_nnz_u());
+}
@@ -0,0 +1,111 @@
+//! Helpers shared by the unit tests
+//!
+
+use crate::c::super_matrix::CSuperMatrix;
+use crate::c::value_type::ValueType;
+use crate::comp_col::CompColMatrix;
+use crate::lu_decomp::LUDecomp;
+use crate::sparse_matrix::SparseMat;
+use crate::super_node::SuperNodeMatrix;
+use csuperlu_sys::{
+    dCreate_SuperNode_Matrix, Dtype_t_SLU_D, Mtype_t_SLU_TRLU, Stype_t_SLU_SC, SuperMatrix,
+};
+
+/// Make a compressed-column matrix from dense rows (the zeros
+/// are not stored)
+pub(crate) fn comp_col(rows: &[Vec<f64>]) -> CompColMatrix<f64> {
+    let num_columns = rows.first().map_or(0, Vec::len);
+    let mut a = SparseMat::new(rows.len(), num_columns);
+    for (row, values) in rows.iter().enumerate() {
+        for (col, &value) in values.iter().enumerate() {
+            a.insert(row, col, value);
+        }
+    }
+    a.compressed_column_format()
+}
+
+/// Get the elements of a compressed-column matrix as dense rows
+pub(crate) fn dense<P: ValueType>(a: &CompColMatrix<P>) -> Vec<Vec<P>> {
+    (0..a.num_rows())
+        .map(|row| (0..a.num_columns()).map(|col| a.value(row, col)).collect())
+        .collect()
+}
+
+/// The matrix
+///
+///  4  1  0
+///  1  4  1
+///  0  1  4
+pub(crate) fn tridiagonal() -> CompColMatrix<f64> {
+    CompColMatrix::from_vectors(
+        3,
+        vec![4.0, 1.0, 1.0, 4.0, 1.0, 1.0, 4.0],
+        vec![0, 1, 0, 1, 2, 1, 2],
+        vec![0, 2, 5, 7],
+    )
+}
+
+/// The 5x5 example matrix from the SuperLU user guide
+pub(crate) fn user_guide_matrix() -> CompColMatrix<f64> {
+    let (s, u, p, e, r, l) = (19.0, 21.0, 16.0, 5.0, 18.0, 12.0);
+    CompColMatrix::from_vectors(
+        5,
+        vec![s, l, l, u, l, l, u, p, u, e, u, r],
+        vec![0, 1, 4, 1, 2, 4, 0, 2, 0, 3, 3, 4],
+        vec![0, 3, 6, 8, 10, 12],
+    )
+}
+
+/// The solution of the user guide system, with every element of
+/// the right-hand side equal to one
+pub(crate) const X_TRUE: [f64; 5] = [
+    -0.031249999999999976,
+    0.06547619047619045,
+    0.013392857142857161,
+    0.06249999999999996,
+    0.03273809523809525,
+];
+
+/// The 3x3 matrix [[1, 0, 2], [0, 3, 0], [4, 0, 5]]
+pub(crate) fn comp_col_3x3() -> CompColMatrix<f64> {
+    CompColMatrix::from_vectors(
+        3,
+        vec![1.0, 4.0, 3.0, 2.0, 5.0],
+        vec![0, 2, 1, 0, 2],
+        vec![0, 2, 3, 5],
+    )
+}
+
+/// Make the LU factors of the matrix in comp_col_3x3 (without
+/// pivoting). Column 0 is a super-node, and columns 1 and 2
+/// form another super-node with rows 1 and 2. In the second
+/// super-node, the element (1, 2) is an (explicit) zero of U.
+pub(crate) fn lu_decomp_3x3() -> LUDecomp<f64> {
+    // L = [[1, 0, 0], [0, 1, 0], [4, 0, 1]]
+    // U = [[1, 0, 2], [0, 3, 0], [0, 0, -3]]
+    let mut nzval = vec![1.0, 4.0, 3.0, 0.0, 0.0, -3.0];
+    let mut nzval_colptr = vec![0, 2, 4, 6];
+    let mut rowind = vec![0, 2, 1, 2];
+    let mut rowind_colptr = vec![0, 2, 4, 4];
+    let mut col_to_sup = vec![0, 1, 1];
+    let mut sup_to_col = vec![0, 1, 3];
+    let l = unsafe {
+        let mut l = std::mem::zeroed::<SuperMatrix>();
+        dCreate_SuperNode_Matrix(
+            &mut l, 3, 3, 6,
+            nzval.as_mut_ptr(), nzval_colptr.as_mut_ptr(),
+            rowind.as_mut_ptr(), rowind_colptr.as_mut_ptr(),
+            col_to_sup.as_mut_ptr(), sup_to_col.as_mut_ptr(),
+            Stype_t_SLU_SC, Dtype_t_SLU_D, Mtype_t_SLU_TRLU,
+        );
+        std::mem::forget(nzval);
+        std::mem::forget(nzval_colptr);
+        std::mem::forget(rowind);
+        std::mem::forget(rowind_colptr);
+        std::mem::forget(col_to_sup);
+        std::mem::forget(sup_to_col);
+        SuperNodeMatrix::from_super_matrix(CSuperMatrix::from_raw(l))
+    };
+    let u = CompColMatrix::from_vectors(3, vec![2.0], vec![0], vec![0, 0, 0, 1]);
+    LUDecomp::from_matrices(l, u)
+}
@@ -0,0 +1,379 @@
+//! Maximum transversals of the sparsity pattern of a matrix
+//!
+//! A transversal is a set of non-zero elements of $A$, no two of
+//! which are in the same row or column. It is the same as a
+//! matching in the bipartite graph whose vertices are the rows
+//! and columns of $A$, with an edge (row, column) for each
+//! stored element. The size of a maximum transversal is the
+//! structural rank of $A$: if it is less than the size of $A$,
+//! then $A$ is singular for any values of its non-zero elements.
+//!
+//! The maximum transversal is found using the depth-first search
+//! algorithm of Duff (MC21), with a cheap assignment (lookahead)
+//! step before each search. If $A$ has full structural rank, the
+//! transversal gives a row permutation $P_r$ such that $P_rA$
+//! has a zero-free diagonal.
+//!
+//! The weighted transversal (similar to MC64, by Duff and
+//! Koster) chooses, from all of the transversals, the one that
+//! maximises the product of the magnitudes of the diagonal
+//! elements of $P_rA$. It is found by solving a minimum-cost
+//! bipartite matching problem with the costs
+//! $c_{ij} = \log \max_k |a_{kj}| - \log |a_{ij}|$, using
+//! shortest augmenting paths. The dual variables of the matching
+//! problem give row and column scalings $D_r$ and $D_c$, such
+//! that the diagonal elements of $P_rD_rAD_c$ have magnitude one,
+//! and the other elements have magnitude at most one. This is
+//! useful for static pivoting, where the row permutation is
+//! chosen before the factorisation: pass the row permutation to
+//! [UserRowPerm](crate::simple_driver::UserRowPerm), which
+//! factors $P_rA$ with the diagonal elements as the pivots.
+//! (SuperLU ignores a row permutation set with
+//! [set_user_row_perm](crate::c::options::CSuperluOptions::set_user_row_perm)
+//! in a new factorisation, and uses partial pivoting instead.)
+//!
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::fmt;
+
+use crate::c::value_type::ValueType;
+use crate::comp_col::CompColMatrix;
+use crate::simple_driver::RowPerm;
+
+/// Errors that can occur when computing a transversal
+#[derive(Debug)]
+pub enum TransversalError {
+    /// The matrix is not square
+    NotSquare,
+    /// The matrix does not have a transversal that includes all
+    /// of the columns (for the weighted transversal, only the
+    /// non-zero values are used)
+    StructurallySingular { structural_rank: usize },
+}
+
+impl std::error::Error for TransversalError {}
+
+impl fmt::Display for TransversalError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NotSquare => write!(f, "The matrix is not square"),
+            Self::StructurallySingular { structural_rank } => write!(
+                f,
+                "The matrix is structurally singular (structural rank {structural_rank})"
+            ),
+        }
+    }
+}
+
+/// A row permutation that maximises the product of the diagonal
+/// magnitudes, with the scalings from the weighted matching
+#[derive(Debug)]
+pub struct WeightedTransversal {
+    /// The row permutation $P_r$
+    pub row_perm: RowPerm,
+    /// The row scale factors (the diagonal of $D_r$)
+    pub row_scale: Vec<f64>,
+    /// The column scale factors (the diagonal of $D_c$)
+    pub column_scale: Vec<f64>,
+}
+
+impl<P: ValueType> CompColMatrix<P> {
+    /// Get the structural rank of the matrix (the size of a
+    /// maximum transversal, see [transversal](crate::transversal))
+    pub fn structural_rank(&self) -> usize {
+        maximum_matching(self).size()
+    }
+
+    /// Find a row permutation $P_r$ such that $P_rA$ has a
+    /// zero-free diagonal, using a maximum transversal (see
+    /// [transversal](crate::transversal)). Only the pattern of
+    /// stored elements is used.
+    ///
+    /// # Errors
+    ///
+    /// If the matrix is not square, or it does not have full
+    /// structural rank, an error is returned.
+    ///
+    pub fn maximum_transversal(&self) -> Result<RowPerm, TransversalError> {
+        if self.num_rows() != self.num_columns() {
+            return Err(TransversalError::NotSquare);
+        }
+        let matching = maximum_matching(self);
+        matching.row_perm()
+    }
+
+    /// Find a row permutation $P_r$ that maximises the product of
+    /// the magnitudes of the diagonal elements of $P_rA$, and
+    /// the corresponding scalings (see
+    /// [transversal](crate::transversal))
+    ///
+    /// # Errors
+    ///
+    /// If the matrix is not square, or there is no transversal
+    /// of non-zero values that includes all of the columns, an
+    /// error is returned.
+    ///
+    pub fn weighted_transversal(&self) -> Result<WeightedTransversal, TransversalError> {
+        if self.num_rows() != self.num_columns() {
+            return Err(TransversalError::NotSquare);
+        }
+        weighted_matching(self)
+    }
+}
+
+/// A maximum matching between the rows and columns of a matrix
+pub(crate) struct Matching {
+    /// The row matched to each column
+    pub(crate) column_match: Vec<Option<usize>>,
+    /// The column matched to each row
+    pub(crate) row_match: Vec<Option<usize>>,
+}
+
+impl Matching {
+    /// Get the number of matched columns
+    pub(crate) fn size(&self) -> usize {
+        self.column_match.iter().flatten().count()
+    }
+
+    /// Get the row permutation which moves the row matched to
+    /// each column onto the diagonal
+    fn row_perm(&self) -> Result<RowPerm, TransversalError> {
+        let structural_rank = self.size();
+        if structural_rank < self.column_match.len() {
+            return Err(TransversalError::StructurallySingular { structural_rank });
+        }
+        // Element i of the permutation is the position of row i
+        // in the permuted matrix
+        let row_perm = self.row_match.iter().map(|col| col.unwrap() as i32).collect();
+        Ok(unsafe { RowPerm::from_raw(row_perm) })
+    }
+}
+
+/// Find a maximum matching between the rows and columns of a,
+/// using only the pattern of stored elements
+pub(crate) fn maximum_matching<P: ValueType>(a: &CompColMatrix<P>) -> Matching {
+    let num_columns = a.num_columns();
+    let column_offsets = a.column_offsets();
+    let row_indices = a.row_indices();
+    let column_end = |col: usize| column_offsets[col + 1] as usize;
+
+    let mut column_match = vec![None; num_columns];
+    let mut row_match = vec![None; a.num_rows()];
+    // The search that last visited each row
+    let mut visited = vec![usize::MAX; a.num_rows()];
+    // The next element of each column to try in the cheap
+    // assignment (each element is only tried once)
+    let mut lookahead: Vec<usize> = column_offsets[..num_columns]
+        .iter()
+        .map(|&offset| offset as usize)
+        .collect();
+
+    for start in 0..num_columns {
+        // Each stack entry is a column on the search path, the
+        // next element of that column to search, and the row
+        // through which the column was reached
+        let mut stack = vec![(start, column_offsets[start] as usize, None)];
+        while let Some(&mut (col, ref mut next, _)) = stack.last_mut() {
+            // Cheap assignment: look for an unmatched row
+            let mut free_row = None;
+            while lookahead[col] < column_end(col) {
+                let row = row_indices[lookahead[col]] as usize;
+                lookahead[col] += 1;
+                if row_match[row].is_none() {
+                    free_row = Some(row);
+                    break;
+                }
+            }
+            if let Some(mut row) = free_row {
+                // Augment the matching along the search path
+                for &(col, _, via_row) in stack.iter().rev() {
+                    column_match[col] = Some(row);
+                    row_match[row] = Some(col);
+                    match via_row {
+                        Some(via_row) => row = via_row,
+                        None => break,
+                    }
+                }
+                break;
+            }
+            // Search deeper through a matched row that has not
+            // been visited yet
+            let mut deeper = None;
+            while *next < column_end(col) {
+                let row = row_indices[*next] as usize;
+                *next += 1;
+                if visited[row] != start {
+                    visited[row] = start;
+                    deeper = row_match[row].map(|matched_col| (matched_col, row));
+                    if deeper.is_some() {
+                        break;
+                    }
+                }
+            }
+            match deeper {
+                Some((matched_col, row)) => {
+                    stack.push((matched_col, column_offsets[matched_col] as usize, Some(row)))
+                }
+                None => {
+                    stack.pop();
+                }
+            }
+        }
+    }
+    Matching { column_match, row_match }
+}
+
+/// A row in the shortest augmenting path search, ordered so
+/// that the row with the smallest distance is popped first
+struct QueuedRow {
+    distance: f64,
+    row: usize,
+}
+
+impl PartialEq for QueuedRow {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for QueuedRow {}
+
+impl PartialOrd for QueuedRow {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for QueuedRow {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.distance.total_cmp(&self.distance)
+    }
+}
+
+/// Find a minimum-cost matching between the rows and columns of
+/// a square matrix, with the cost of each non-zero element
+/// $c_{ij} = \log \max_k |a_{kj}| - \log |a_{ij}|$
+///
+/// Each column is matched using a shortest augmenting path
+/// (Dijkstra's algorithm on the reduced costs
+/// $c_{ij} - u_i - v_j \geq 0$, which are zero for the matched
+/// elements). The potentials $u$ and $v$ give the scalings.
+fn weighted_matching<P: ValueType>(a: &CompColMatrix<P>) -> Result<WeightedTransversal, TransversalError> {
+    let size = a.num_columns();
+    let column_offsets = a.column_offsets();
+    // The (row, log |a_ij|) of the non-zero values in each column
+    let log_columns: Vec<Vec<(usize, f64)>> = (0..size)
+        .map(|col| {
+            let start = column_offsets[col] as usize;
+            let end = column_offsets[col + 1] as usize;
+            a.row_indices()[start..end]
+                .iter()
+                .zip(&a.non_zero_values()[start..end])
+                .filter(|(_, value)| value.magnitude() > 0.0)
+                .map(|(&row, value)| (row as usize, value.magnitude().ln()))
+                .collect()
+        })
+        .collect();
+    let column_max: Vec<f64> = log_columns
+        .iter()
+        .map(|column| column.iter().map(|&(_, log)| log).fold(f64::NEG_INFINITY, f64::max))
+        .collect();
+
+    let mut row_potential = vec![0.0; size];
+    let mut column_potential = vec![0.0; size];
+    let mut row_match: Vec<Option<usize>> = vec![None; size];
+    let mut column_match: Vec<Option<usize>> = vec![None; size];
+    let mut distance = vec![f64::INFINITY; size];
+    // The column from which each row was reached
+    let mut predecessor = vec![0; size];
+    let mut finalized = vec![false; size];
+
+    for start in 0..size {
+        // Rows whose distance was set, and rows whose shortest
+        // distance is known, in this search
+        let mut reached = Vec::new();
+        let mut done = Vec::new();
+        let mut queue = BinaryHeap::new();
+        let mut free_row = None;
+        let mut next_column = Some((start, 0.0));
+        while let Some((col, column_distance)) = next_column.take() {
+            // Update the distances to the rows in the column
+            for &(row, log) in &log_columns[col] {
+                let reduced = column_max[col] - log - row_potential[row] - column_potential[col];
+                let new_distance = column_distance + reduced.max(0.0);
+                if new_distance < distance[row] {
+                    if distance[row] == f64::INFINITY {
+                        reached.push(row);
+                    }
+                    distance[row] = new_distance;
+                    predecessor[row] = col;
+                    queue.push(QueuedRow { distance: new_distance, row });
+                }
+            }
+            // Find the closest row, and stop if it is unmatched
+            while let Some(QueuedRow { distance: row_distance, row }) = queue.pop() {
+                if finalized[row] || row_distance > distance[row] {
+                    continue;
+                }
+                finalized[row] = true;
+                done.push(row);
+                match row_match[row] {
+                    None => free_row = Some(row),
+                    Some(col) => next_column = Some((col, row_distance)),
+                }
+                break;
+            }
+        }
+
+        let Some(free_row) = free_row else {
+            // The columns are matched one at a time, so the
+            // matching so far may be smaller than the structural
+            // rank
+            let structural_rank = maximum_matching(a).size();
+            return Err(TransversalError::StructurallySingular { structural_rank });
+        };
+
+        // Update the potentials, so that the reduced costs stay
+        // non-negative and are zero along the augmenting path
+        let path_distance = distance[free_row];
+        column_potential[start] += path_distance;
+        for &row in &done {
+            row_potential[row] -= path_distance - distance[row];
+            if let Some(col) = row_match[row] {
+                column_potential[col] += path_distance - distance[row];
+            }
+        }
+
+        // Augment the matching along the path
+        let mut row = free_row;
+        loop {
+            let col = predecessor[row];
+            let previous_row = column_match[col];
+            column_match[col] = Some(row);
+            row_match[row] = Some(col);
+            match previous_row {
+                Some(previous_row) => row = previous_row,
+                None => break,
+            }
+        }
+
+        for &row in &reached {
+            distance[row] = f64::INFINITY;
+            finalized[row] = false;
+        }
+    }
+
+    let matching = Matching { column_match, row_match };
+    let row_perm = matching.row_perm()?;
+    let row_scale = row_potential.iter().map(|u| u.exp()).collect();
+    let column_scale = column_potential
+        .iter()
+        .zip(&column_max)
+        .map(|(v, log_max)| (v - log_max).exp())
+        .collect();
+    Ok(WeightedTransversal { row_perm, row_scale, column_scale })
+}
+
+#[cfg(test)]
+mod tests;
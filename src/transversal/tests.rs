@@ -0,0 +1,117 @@
+use super::*;
+use crate::test_utils::comp_col;
+
+/// Get the row of A that is moved to each diagonal position
+fn diagonal_rows(row_perm: &RowPerm) -> Vec<usize> {
+    let mut rows = vec![0; row_perm.as_slice().len()];
+    for (row, &position) in row_perm.as_slice().iter().enumerate() {
+        rows[position as usize] = row;
+    }
+    rows
+}
+
+/// All permutations of 0..n
+fn permutations(n: usize) -> Vec<Vec<usize>> {
+    if n == 0 {
+        return vec![vec![]];
+    }
+    let mut result = Vec::new();
+    for perm in permutations(n - 1) {
+        for k in 0..n {
+            let mut perm = perm.clone();
+            perm.insert(k, n - 1);
+            result.push(perm);
+        }
+    }
+    result
+}
+
+#[test]
+fn maximum_transversal_gives_zero_free_diagonal() {
+    let rows = vec![
+        vec![0.0, 1.0, 0.0, 2.0],
+        vec![3.0, 0.0, 0.0, 0.0],
+        vec![0.0, 4.0, 5.0, 0.0],
+        vec![0.0, 0.0, 6.0, 0.0],
+    ];
+    let a = comp_col(&rows);
+    assert_eq!(a.structural_rank(), 4);
+    let row_perm = a.maximum_transversal().unwrap();
+    for (col, row) in diagonal_rows(&row_perm).into_iter().enumerate() {
+        assert!(rows[row][col] != 0.0);
+    }
+}
+
+#[test]
+fn structurally_singular_matrix_has_no_transversal() {
+    let rows = vec![
+        vec![1.0, 1.0, 1.0],
+        vec![1.0, 0.0, 0.0],
+        vec![1.0, 0.0, 0.0],
+    ];
+    let a = comp_col(&rows);
+    assert_eq!(a.structural_rank(), 2);
+    assert!(matches!(
+        a.maximum_transversal(),
+        Err(TransversalError::StructurallySingular { structural_rank: 2 })
+    ));
+    assert!(matches!(
+        a.weighted_transversal(),
+        Err(TransversalError::StructurallySingular { structural_rank: 2 })
+    ));
+    // The weighted matching fails at the first column, before
+    // any columns are matched
+    let a = comp_col(&[vec![0.0, 1.0, 1.0], vec![0.0, 1.0, 1.0], vec![0.0, 1.0, 1.0]]);
+    assert!(matches!(
+        a.weighted_transversal(),
+        Err(TransversalError::StructurallySingular { structural_rank: 2 })
+    ));
+}
+
+#[test]
+fn rectangular_matrix() {
+    let a = comp_col(&[vec![1.0, 0.0, 2.0], vec![0.0, 0.0, 3.0]]);
+    assert_eq!(a.structural_rank(), 2);
+    assert!(matches!(a.maximum_transversal(), Err(TransversalError::NotSquare)));
+}
+
+#[test]
+fn weighted_transversal_maximises_diagonal_product() {
+    let rows = vec![
+        vec![1.0, 8.0, 0.0, 2.0, 0.5],
+        vec![9.0, 1.0, 3.0, 0.0, 0.0],
+        vec![0.0, 7.0, 1.0, 4.0, 0.0],
+        vec![2.0, 0.0, 6.0, 1.0, 3.0],
+        vec![0.0, 0.5, 0.0, 5.0, 1.0],
+    ];
+    let a = comp_col(&rows);
+    let best = permutations(5)
+        .into_iter()
+        .map(|perm| perm.iter().enumerate().map(|(col, &row)| rows[row][col].abs()).product::<f64>())
+        .fold(0.0, f64::max);
+
+    let transversal = a.weighted_transversal().unwrap();
+    let diagonal = diagonal_rows(&transversal.row_perm);
+    let product: f64 = diagonal.iter().enumerate().map(|(col, &row)| rows[row][col].abs()).product();
+    assert!((product - best).abs() < 1e-12 * best);
+
+    // The scaled matrix has unit diagonal, and no larger elements
+    for (row, values) in rows.iter().enumerate() {
+        for (col, value) in values.iter().enumerate() {
+            let scaled = value.abs() * transversal.row_scale[row] * transversal.column_scale[col];
+            if diagonal[col] == row {
+                assert!((scaled - 1.0).abs() < 1e-12);
+            } else {
+                assert!(scaled <= 1.0 + 1e-12);
+            }
+        }
+    }
+}
+
+#[test]
+fn weighted_transversal_ignores_stored_zeros() {
+    let a = CompColMatrix::from_vectors(2, vec![0.0, 1.0, 2.0], vec![0, 1, 0], vec![0, 2, 3]);
+    assert_eq!(a.structural_rank(), 2);
+    let transversal = a.weighted_transversal().unwrap();
+    assert_eq!(transversal.row_perm.as_slice(), &[1, 0]);
+}